@@ -28,7 +28,7 @@ fn part2(input: &str) -> usize {
     unreachable!()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(1)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -48,7 +48,7 @@ fn part2(input: &LookAndSay) -> usize {
     (0..50).fold(input.clone(), |v, _| v.go()).len()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = LookAndSay::from_str(&read_input(10)?);
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -81,7 +81,7 @@ fn part2(input: &Password) -> String {
     v.as_string()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     //test();
     let input = Password::from_str(&read_input(11)?);
     println!("Part 1: {}", part1(&input));
@@ -24,7 +24,7 @@ fn part2(red: &Value, input: &Value) -> f64 {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(12)?;
     let input = serde_json::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
@@ -77,7 +77,7 @@ fn part2(input: &HappyTracker) -> i32 {
     part1(&repl)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(13)?;
     let input = HappyTracker::from_str(&input);
     println!("Part 1: {}", part1(&input));
@@ -65,7 +65,7 @@ fn part2(input: &[Reindeer], racelen: usize) -> usize {
     *scores.iter().max().unwrap()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     //let test_input: Vec<Reindeer> = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.\nDancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.\n".lines().map(Reindeer::from_str).collect();
     //println!("Test part 1: {}", part1(&test_input, 2503));
     //println!("Test part 2: {}", part2(&test_input, 1000));
@@ -161,7 +161,7 @@ fn part2(input: &Pantry) -> i32 {
     part2_(input, &mut recipe).unwrap()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = Pantry::from_str("Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8\nCinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3\n");
     println!("Test pantry:");
     test_input.dump();
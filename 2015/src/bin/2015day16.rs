@@ -98,7 +98,7 @@ fn part2(input: &[Sue]) -> usize {
     unreachable!()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Sue> = read_input(16)?.lines().map(Sue::from_str).collect();
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
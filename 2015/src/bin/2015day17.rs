@@ -43,7 +43,7 @@ fn part2(input: &[usize], target: usize) -> usize {
     totmet
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Vec<usize> = vec![20, 15, 10, 5, 5];
     println!("Test 1: {}", part1(&test_input, 25));
     println!("Test 2: {}", part2(&test_input, 25));
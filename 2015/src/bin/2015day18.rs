@@ -124,7 +124,7 @@ fn do_iterate(input: &Grid, count: usize, report: bool, badgrid: bool) -> usize
     mygrid.count_on()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = Grid::from_str(".#.#.#\n...##.\n#....#\n..#...\n#.#..#\n####..\n");
     println!("Test 1: {}", do_iterate(&test_input, 4, false, false));
     println!("Test 2: {}", do_iterate(&test_input, 5, false, true));
@@ -124,7 +124,7 @@ fn part2(input: &Input) -> usize {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input1 = Input::from_str("H => HO\nH => OH\nO => HH\ne => H\ne => O\n\nHOH");
     let test_input2 = Input::from_str("H => HO\nH => OH\nO => HH\ne => H\ne => O\n\nHOHOHO");
     println!("Test1 1: {}", part1(&test_input1));
@@ -51,7 +51,7 @@ fn part2(input: &[Gift]) -> usize {
     input.iter().map(|g| g.ribbon()).sum()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(2)?;
     let input = parse_input(&input);
     println!("Loaded {} gifts", input.len());
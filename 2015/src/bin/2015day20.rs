@@ -64,7 +64,7 @@ fn part2(input: usize) -> usize {
 
 /* Sadly this does need something fast, like --release to be of use :( */
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: usize = read_input(20)?.trim().parse().unwrap();
     for house in 1..=9 {
         println!("House {} got {} presents.", house, presents_at(house));
@@ -258,7 +258,7 @@ fn part2(boss: &Boss, players: &[Player]) -> i32 {
     mostcost
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = Boss::from_str(&read_input(21)?);
     let players: Vec<Player> = Combos::new().collect();
     test_fight();
@@ -304,7 +304,7 @@ fn part2(boss: &Boss) -> i32 {
         .unwrap_or(std::i32::MAX)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = Boss::from_str(&read_input(22)?);
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -141,7 +141,7 @@ fn part2(input: &[Instr]) -> usize {
     vm.reg_b
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Instr> = read_input(23)?.lines().map(Instr::from_str).collect();
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
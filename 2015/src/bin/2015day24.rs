@@ -201,7 +201,7 @@ fn part2(input: &[usize]) -> u128 {
     unreachable!()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<usize> = read_input(24)?
         .lines()
         .map(|s| s.parse().unwrap())
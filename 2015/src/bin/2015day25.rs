@@ -53,7 +53,7 @@ impl ValueGen {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<usize> = read_input(25)?
         .split_whitespace()
         .map(|s| s[0..s.len() - 1].to_owned())
@@ -69,7 +69,7 @@ fn main() -> Result<()> {
     loop {
         let ((row, col), val) = gen.next();
         if (row == input_row) && (col == input_col) {
-            println!("Value: {}", val);
+            println!("Part 1: {}", val);
             break;
         }
     }
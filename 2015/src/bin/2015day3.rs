@@ -59,7 +59,7 @@ fn part2(input: &str) -> usize {
     houses.iter().count()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(3)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -12,7 +12,7 @@ fn run(input: &str, mstr: &str) -> usize {
     unreachable!()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(4)?.trim().to_owned();
     println!("Part 1: {}", run(&input, "00000"));
     println!("Part 2: {}", run(&input, "000000"));
@@ -64,7 +64,7 @@ fn part2(input: &[String]) -> usize {
     input.iter().filter(|s| is_nice(s)).count()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<String> = read_input(5)?.lines().map(|s| s.to_owned()).collect();
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -124,7 +124,7 @@ fn part2(input: &[Instruction]) -> usize {
     tot
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Instruction> = read_input(6)?
         .lines()
         .map(|s| Instruction::from_str(s))
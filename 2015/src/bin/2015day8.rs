@@ -72,7 +72,7 @@ fn part2(input: &[Sticks]) -> usize {
     enclen - codelen
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let testvec = vec![
         Sticks::new(r#""""#),
         Sticks::new(r#""abc""#),
@@ -81,7 +81,7 @@ fn part2(input: &Map) -> usize {
         .fold(std::usize::MIN, |longest, dist| longest.max(*dist))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input_map = Map::new(&read_input(9)?);
     println!("Part 1: {}", part1(&input_map));
     println!("Part 2: {}", part2(&input_map));
@@ -0,0 +1,454 @@
+//! Circuits of 16 bit wires and bitwise gates, as in day 7
+//!
+//! Every wire is driven by one gate, whose inputs are other wires or
+//! constants.  The gates are sorted so that each comes after everything it
+//! depends on, which means the whole circuit can be worked out in one go, and
+//! a loop of wires depending on each other is an error rather than something
+//! which never settles.  Wires can be overridden with a constant afterwards,
+//! which only has to work out again the wires which depend on them.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+/// Errors building or using a circuit
+#[derive(Debug)]
+pub enum Error {
+    /// A line which isn't a gate
+    ParseError(String),
+    /// A wire driven by more than one gate
+    Duplicate(String),
+    /// A wire used as an input which nothing drives
+    Undriven(String),
+    /// Wires which each feed the next, and the last the first
+    Cycle(Vec<String>),
+    UnknownWire(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ParseError(line) => write!(f, "Unable to parse gate: '{}'", line),
+            Error::Duplicate(wire) => write!(f, "Wire {} is driven by more than one gate", wire),
+            Error::Undriven(wire) => write!(f, "Nothing drives wire {}", wire),
+            Error::Cycle(wires) => write!(
+                f,
+                "Wires depend on each other: {} -> {}",
+                wires.join(" -> "),
+                wires[0]
+            ),
+            Error::UnknownWire(wire) => write!(f, "There is no wire {}", wire),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// 2015 works in `io::Result`, so this is what lets day 7 use `?` on the
+/// circuit's results
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Fixed(u16),
+    Wire(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gate {
+    Assign(Input),
+    Not(Input),
+    And(Input, Input),
+    Or(Input, Input),
+    LShift(Input, Input),
+    RShift(Input, Input),
+}
+
+impl Gate {
+    fn inputs(self) -> impl Iterator<Item = Input> {
+        let (a, b) = match self {
+            Gate::Assign(a) | Gate::Not(a) => (a, None),
+            Gate::And(a, b) | Gate::Or(a, b) | Gate::LShift(a, b) | Gate::RShift(a, b) => {
+                (a, Some(b))
+            }
+        };
+        std::iter::once(a).chain(b)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Gate::Assign(_) => "",
+            Gate::Not(_) => "NOT",
+            Gate::And(..) => "AND",
+            Gate::Or(..) => "OR",
+            Gate::LShift(..) => "LSHIFT",
+            Gate::RShift(..) => "RSHIFT",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    names: Vec<String>,
+    wires: HashMap<String, usize>,
+    gates: Vec<Option<Gate>>,
+    /// The wires each wire is an input to
+    feeds: Vec<Vec<usize>>,
+    /// Every wire after everything it depends on, and where each wire is in
+    /// that order
+    order: Vec<usize>,
+    rank: Vec<usize>,
+    values: Vec<u16>,
+    overrides: Vec<Option<u16>>,
+}
+
+impl Circuit {
+    fn wire(&mut self, name: &str) -> usize {
+        if let Some(&wire) = self.wires.get(name) {
+            return wire;
+        }
+        let wire = self.names.len();
+        self.names.push(name.to_owned());
+        self.wires.insert(name.to_owned(), wire);
+        self.gates.push(None);
+        self.feeds.push(Vec::new());
+        wire
+    }
+
+    fn input(&mut self, input: &str) -> Input {
+        match input.parse() {
+            Ok(value) => Input::Fixed(value),
+            Err(_) => Input::Wire(self.wire(input)),
+        }
+    }
+
+    fn add_gate(&mut self, line: &str) -> Result<()> {
+        let bad = || Error::ParseError(line.to_owned());
+        let (gate, wire) = line.split_once(" -> ").ok_or_else(bad)?;
+        let gate = match gate.split(' ').collect::<Vec<_>>()[..] {
+            [val] => Gate::Assign(self.input(val)),
+            ["NOT", val] => Gate::Not(self.input(val)),
+            [in1, op, in2] => {
+                let (in1, in2) = (self.input(in1), self.input(in2));
+                match op {
+                    "AND" => Gate::And(in1, in2),
+                    "OR" => Gate::Or(in1, in2),
+                    "LSHIFT" => Gate::LShift(in1, in2),
+                    "RSHIFT" => Gate::RShift(in1, in2),
+                    _ => return Err(bad()),
+                }
+            }
+            _ => return Err(bad()),
+        };
+        let wire = self.wire(wire);
+        if self.gates[wire].replace(gate).is_some() {
+            return Err(Error::Duplicate(self.names[wire].clone()));
+        }
+        for input in gate.inputs() {
+            if let Input::Wire(input) = input {
+                self.feeds[input].push(wire);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort the wires so each comes after its inputs
+    fn sort(&mut self) -> Result<()> {
+        let mut waiting: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|gate| {
+                gate.iter()
+                    .flat_map(|gate| gate.inputs())
+                    .filter(|input| matches!(input, Input::Wire(_)))
+                    .count()
+            })
+            .collect();
+        let mut ready: Vec<usize> = (0..self.names.len()).filter(|&w| waiting[w] == 0).collect();
+        self.order.clear();
+        while let Some(wire) = ready.pop() {
+            self.order.push(wire);
+            for &fed in &self.feeds[wire] {
+                waiting[fed] -= 1;
+                if waiting[fed] == 0 {
+                    ready.push(fed);
+                }
+            }
+        }
+        if self.order.len() < self.names.len() {
+            return Err(Error::Cycle(self.cycle(&waiting)));
+        }
+        self.rank = vec![0; self.names.len()];
+        for (rank, &wire) in self.order.iter().enumerate() {
+            self.rank[wire] = rank;
+        }
+        Ok(())
+    }
+
+    /// A loop among the wires which never got sorted
+    fn cycle(&self, waiting: &[usize]) -> Vec<String> {
+        // Each of them has an unsorted input, so following those from any of
+        // them has to come back round eventually
+        let unsorted = |wire: usize| {
+            self.gates[wire].and_then(|gate| {
+                gate.inputs().find_map(|input| match input {
+                    Input::Wire(input) if waiting[input] > 0 => Some(input),
+                    _ => None,
+                })
+            })
+        };
+        let mut path = Vec::new();
+        let mut wire = (0..waiting.len()).find(|&w| waiting[w] > 0);
+        while let Some(next) = wire {
+            if let Some(start) = path.iter().position(|&w| w == next) {
+                path.drain(..start);
+                break;
+            }
+            path.push(next);
+            wire = unsorted(next);
+        }
+        // Reversed, so each wire feeds the next
+        path.iter().rev().map(|&w| self.names[w].clone()).collect()
+    }
+
+    fn input_value(&self, input: Input) -> u16 {
+        match input {
+            Input::Fixed(value) => value,
+            Input::Wire(wire) => self.values[wire],
+        }
+    }
+
+    fn evaluate(&mut self, wire: usize) {
+        let val = |input| self.input_value(input);
+        self.values[wire] = match (self.overrides[wire], self.gates[wire]) {
+            (Some(value), _) => value,
+            (None, Some(Gate::Assign(a))) => val(a),
+            (None, Some(Gate::Not(a))) => !val(a),
+            (None, Some(Gate::And(a, b))) => val(a) & val(b),
+            (None, Some(Gate::Or(a, b))) => val(a) | val(b),
+            (None, Some(Gate::LShift(a, b))) => val(a).checked_shl(val(b).into()).unwrap_or(0),
+            (None, Some(Gate::RShift(a, b))) => val(a).checked_shr(val(b).into()).unwrap_or(0),
+            (None, None) => unreachable!("Every wire is driven, see `from_str`"),
+        };
+    }
+
+    fn id(&self, wire: &str) -> Result<usize> {
+        self.wires
+            .get(wire)
+            .copied()
+            .ok_or_else(|| Error::UnknownWire(wire.to_owned()))
+    }
+
+    pub fn value(&self, wire: &str) -> Result<u16> {
+        Ok(self.values[self.id(wire)?])
+    }
+
+    /// Work out everything which depends on `wire` again, returning how
+    /// many wires that was
+    fn propagate(&mut self, wire: usize) -> usize {
+        let mut todo = BTreeSet::new();
+        todo.insert((self.rank[wire], wire));
+        let mut count = 0;
+        // Always the earliest in the order, so each input is done first
+        while let Some((_, wire)) = todo.pop_first() {
+            self.evaluate(wire);
+            count += 1;
+            for &fed in &self.feeds[wire] {
+                todo.insert((self.rank[fed], fed));
+            }
+        }
+        count
+    }
+
+    /// Hold `wire` at `value`, whatever drives it, returning how many wires
+    /// had to be worked out again
+    pub fn set_override(&mut self, wire: &str, value: u16) -> Result<usize> {
+        let wire = self.id(wire)?;
+        self.overrides[wire] = Some(value);
+        Ok(self.propagate(wire))
+    }
+
+    /// Let `wire` be driven by its gate again
+    pub fn clear_override(&mut self, wire: &str) -> Result<usize> {
+        let wire = self.id(wire)?;
+        self.overrides[wire] = None;
+        Ok(self.propagate(wire))
+    }
+
+    /// The circuit as a graphviz graph, with each wire's value
+    pub fn dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n");
+        for (wire, name) in self.names.iter().enumerate() {
+            let style = if self.overrides[wire].is_some() {
+                ", style=filled"
+            } else {
+                ""
+            };
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"{}];\n",
+                name, name, self.values[wire], style
+            ));
+        }
+        for (wire, gate) in self.gates.iter().enumerate() {
+            let gate = match gate {
+                Some(gate) => *gate,
+                None => continue,
+            };
+            // Constant inputs go on the label of the edges from the others
+            let mut label = gate.name().to_owned();
+            for input in gate.inputs() {
+                if let Input::Fixed(value) = input {
+                    label.push_str(&format!(" {}", value));
+                }
+            }
+            let label = label.trim();
+            let mut wired = false;
+            for input in gate.inputs() {
+                if let Input::Wire(input) = input {
+                    dot.push_str(&format!(
+                        "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                        self.names[input], self.names[wire], label
+                    ));
+                    wired = true;
+                }
+            }
+            if !wired {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\";\n    \"{}\" [shape=plaintext];\n",
+                    label, self.names[wire], label
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl FromStr for Circuit {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let mut circuit = Circuit {
+            names: Vec::new(),
+            wires: HashMap::new(),
+            gates: Vec::new(),
+            feeds: Vec::new(),
+            order: Vec::new(),
+            rank: Vec::new(),
+            values: Vec::new(),
+            overrides: Vec::new(),
+        };
+        for line in input.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            circuit.add_gate(line)?;
+        }
+        if let Some(wire) = (0..circuit.names.len()).find(|&w| circuit.gates[w].is_none()) {
+            return Err(Error::Undriven(circuit.names[wire].clone()));
+        }
+        circuit.sort()?;
+        circuit.values = vec![0; circuit.names.len()];
+        circuit.overrides = vec![None; circuit.names.len()];
+        for rank in 0..circuit.order.len() {
+            circuit.evaluate(circuit.order[rank]);
+        }
+        Ok(circuit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The example from day 7
+    const EXAMPLE: &str = r"
+123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i
+";
+
+    fn values(circuit: &Circuit, wires: &str) -> Vec<u16> {
+        wires
+            .split(' ')
+            .map(|wire| circuit.value(wire).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let circuit: Circuit = EXAMPLE.parse().unwrap();
+        assert_eq!(
+            values(&circuit, "d e f g h i x y"),
+            vec![72, 507, 492, 114, 65412, 65079, 123, 456]
+        );
+        assert!(matches!(circuit.value("a"), Err(Error::UnknownWire(_))));
+    }
+
+    #[test]
+    fn overriding() {
+        let mut circuit: Circuit = EXAMPLE.parse().unwrap();
+        // x and the four gates it feeds
+        assert_eq!(circuit.set_override("x", 0).unwrap(), 5);
+        assert_eq!(values(&circuit, "d e f h x"), vec![0, 456, 0, 65535, 0]);
+        assert_eq!(values(&circuit, "g i"), vec![114, 65079]);
+        assert_eq!(circuit.clear_override("x").unwrap(), 5);
+        assert_eq!(
+            values(&circuit, "d e f h x"),
+            vec![72, 507, 492, 65412, 123]
+        );
+        // Nothing depends on d
+        assert_eq!(circuit.set_override("d", 7).unwrap(), 1);
+        assert_eq!(circuit.value("d").unwrap(), 7);
+        let y = circuit.id("y").unwrap();
+        assert_eq!(circuit.propagate(y), 5);
+        assert_eq!(circuit.value("d").unwrap(), 7);
+        assert!(circuit.set_override("a", 1).is_err());
+    }
+
+    #[test]
+    fn bad_circuits() {
+        let err = "1 -> z\nz AND c -> a\na -> b\nb -> c"
+            .parse::<Circuit>()
+            .unwrap_err();
+        assert!(matches!(err, Error::Cycle(_)));
+        assert_eq!(
+            err.to_string(),
+            "Wires depend on each other: a -> b -> c -> a"
+        );
+        assert!(matches!(
+            "x AND y -> d\n1 -> x".parse::<Circuit>(),
+            Err(Error::Undriven(wire)) if wire == "y"
+        ));
+        assert!(matches!(
+            "1 -> x\n2 -> x".parse::<Circuit>(),
+            Err(Error::Duplicate(wire)) if wire == "x"
+        ));
+        assert!(matches!(
+            "x XOR y -> z".parse::<Circuit>(),
+            Err(Error::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn drawing() {
+        let mut circuit: Circuit = EXAMPLE.parse().unwrap();
+        circuit.set_override("y", 1).unwrap();
+        let dot = circuit.dot();
+        assert!(dot.starts_with("digraph circuit {\n"));
+        assert!(dot.contains("    \"x\" [label=\"x\\n123\"];\n"));
+        assert!(dot.contains("    \"y\" [label=\"y\\n1\", style=filled];\n"));
+        assert!(dot.contains("    \"x\" -> \"d\" [label=\"AND\"];\n"));
+        assert!(dot.contains("    \"x\" -> \"f\" [label=\"LSHIFT 2\"];\n"));
+        assert!(dot.contains("    \"123\" -> \"x\";\n    \"123\" [shape=plaintext];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
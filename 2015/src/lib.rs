@@ -1,4 +1,6 @@
-use std::fs::read_to_string;
+// The days are compiled into this crate too, see `days` below
+extern crate self as aoc2015;
+
 pub use std::io::Result;
 
 pub use lazy_static::lazy_static;
@@ -15,6 +17,21 @@ pub use itertools::Itertools;
 
 pub use either::{Either, Left, Right};
 
+/// 2015 deals in `io::Result`, so this wraps the common `read_input`
 pub fn read_input(day: usize) -> Result<String> {
-    read_to_string(format!("inputs/day{}", day))
+    aoc_common::input::read_input(day)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::NotFound, e.to_string()))
+}
+
+// 2015 specific stuff
+
+pub mod circuit;
+
+/// Days which exist, but which running the whole year should leave alone
+pub const SKIPPED_DAYS: &[(u8, &str)] = &[];
+
+/// Every day of 2015, generated from `src/bin` by `build.rs`
+#[cfg(not(test))]
+pub mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
 }
@@ -0,0 +1,370 @@
+//! Assembunny VM for 2016 AoC (days 12, 23 and 25)
+//!
+//! Programs spend nearly all their time in a couple of loops, so before
+//! running them the VM looks for:
+//!
+//! ```text
+//! inc x / dec y / jnz y -2               x += y, y = 0
+//! cpy s y / <the above> / dec z / jnz z -5     x += s * z, y = 0, z = 0
+//! ```
+//!
+//! (with the `inc` and `dec` either way round) and runs those as single steps
+//! whenever they're entered from the top.  `tgl` can rewrite any instruction,
+//! so every toggle throws the optimised program away and looks again.
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use aoc_common::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reg {
+    A,
+    B,
+    C,
+    D,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    Reg(Reg),
+    Val(i64),
+}
+
+impl FromStr for Arg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        Ok(match s {
+            "a" => Arg::Reg(Reg::A),
+            "b" => Arg::Reg(Reg::B),
+            "c" => Arg::Reg(Reg::C),
+            "d" => Arg::Reg(Reg::D),
+            _ => Arg::Val(s.parse().map_err(|_| format!("Bad argument {:?}", s))?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instr {
+    Cpy(Arg, Arg),
+    Inc(Arg),
+    Dec(Arg),
+    Jnz(Arg, Arg),
+    Tgl(Arg),
+    Out(Arg),
+}
+
+impl Instr {
+    /// What `tgl` turns this instruction into
+    fn toggled(self) -> Instr {
+        match self {
+            Instr::Inc(x) => Instr::Dec(x),
+            Instr::Dec(x) | Instr::Tgl(x) | Instr::Out(x) => Instr::Inc(x),
+            Instr::Jnz(x, y) => Instr::Cpy(x, y),
+            Instr::Cpy(x, y) => Instr::Jnz(x, y),
+        }
+    }
+}
+
+impl FromStr for Instr {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let arg = |n: usize| -> std::result::Result<Arg, String> { words[n].parse() };
+        Ok(match (words.first().copied(), words.len()) {
+            (Some("cpy"), 3) => Instr::Cpy(arg(1)?, arg(2)?),
+            (Some("inc"), 2) => Instr::Inc(arg(1)?),
+            (Some("dec"), 2) => Instr::Dec(arg(1)?),
+            (Some("jnz"), 3) => Instr::Jnz(arg(1)?, arg(2)?),
+            (Some("tgl"), 2) => Instr::Tgl(arg(1)?),
+            (Some("out"), 2) => Instr::Out(arg(1)?),
+            _ => return Err(format!("Bad instruction {:?}", s)),
+        })
+    }
+}
+
+/// Somewhere for `out` to send values
+pub trait Output {
+    /// Take a value, returning false if the program should stop now
+    fn output(&mut self, value: i64) -> bool;
+}
+
+/// Collect everything a program outputs
+impl Output for Vec<i64> {
+    fn output(&mut self, value: i64) -> bool {
+        self.push(value);
+        true
+    }
+}
+
+/// What the VM actually runs at each position of the program
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Plain(Instr),
+    /// `to += counter; counter = 0`, replacing three instructions
+    Add {
+        to: Reg,
+        counter: Reg,
+    },
+    /// `to += src * outer; inner = 0; outer = 0`, replacing six
+    Mul {
+        to: Reg,
+        src: Arg,
+        inner: Reg,
+        outer: Reg,
+    },
+}
+
+/// `inc to / dec counter / jnz counter -2`, either way round
+fn add_at(program: &[Instr], pc: usize) -> Option<(Reg, Reg)> {
+    use Instr::*;
+    let (to, counter) = match *program.get(pc..pc + 3)? {
+        [Inc(Arg::Reg(to)), Dec(Arg::Reg(counter)), Jnz(Arg::Reg(test), Arg::Val(-2))]
+        | [Dec(Arg::Reg(counter)), Inc(Arg::Reg(to)), Jnz(Arg::Reg(test), Arg::Val(-2))]
+            if test == counter =>
+        {
+            (to, counter)
+        }
+        _ => return None,
+    };
+    (to != counter).then_some((to, counter))
+}
+
+/// `cpy src inner / <add to, inner> / dec outer / jnz outer -5`
+fn mul_at(program: &[Instr], pc: usize) -> Option<Op> {
+    use Instr::*;
+    let Cpy(src, Arg::Reg(inner)) = *program.get(pc)? else {
+        return None;
+    };
+    let (to, counter) = add_at(program, pc + 1)?;
+    let [Dec(Arg::Reg(outer)), Jnz(Arg::Reg(test), Arg::Val(-5))] = *program.get(pc + 4..pc + 6)?
+    else {
+        return None;
+    };
+    let distinct = counter == inner && test == outer && outer != to && outer != inner;
+    (distinct && src != Arg::Reg(to) && src != Arg::Reg(inner) && src != Arg::Reg(outer)).then_some(
+        Op::Mul {
+            to,
+            src,
+            inner,
+            outer,
+        },
+    )
+}
+
+fn optimise(program: &[Instr]) -> Vec<Op> {
+    (0..program.len())
+        .map(|pc| {
+            mul_at(program, pc)
+                .or_else(|| add_at(program, pc).map(|(to, counter)| Op::Add { to, counter }))
+                .unwrap_or(Op::Plain(program[pc]))
+        })
+        .collect()
+}
+
+/// The VM itself
+#[derive(Debug, Clone)]
+pub struct VM {
+    program: Vec<Instr>,
+    /// `program` as the VM runs it, rebuilt whenever `tgl` changes it
+    ops: Vec<Op>,
+    optimising: bool,
+    regs: [i64; 4],
+    pc: i64,
+    steps: u64,
+}
+
+impl VM {
+    pub fn new(program: Vec<Instr>) -> VM {
+        let ops = optimise(&program);
+        VM {
+            program,
+            ops,
+            optimising: true,
+            regs: [0; 4],
+            pc: 0,
+            steps: 0,
+        }
+    }
+
+    /// Run every instruction as written, without the loop optimisations
+    pub fn unoptimised(mut self) -> VM {
+        self.optimising = false;
+        self.ops = self.program.iter().copied().map(Op::Plain).collect();
+        self
+    }
+
+    pub fn reg(&self, reg: Reg) -> i64 {
+        self.regs[reg as usize]
+    }
+
+    pub fn set_reg(&mut self, reg: Reg, value: i64) {
+        self.regs[reg as usize] = value;
+    }
+
+    /// How many steps the VM has taken, where an optimised loop is one step
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    fn value(&self, arg: Arg) -> i64 {
+        match arg {
+            Arg::Reg(reg) => self.reg(reg),
+            Arg::Val(v) => v,
+        }
+    }
+
+    /// Writing to a constant is an invalid instruction, and so is skipped
+    fn store(&mut self, arg: Arg, value: i64) {
+        if let Arg::Reg(reg) = arg {
+            self.set_reg(reg, value);
+        }
+    }
+
+    fn toggle(&mut self, target: i64) {
+        if let Ok(target) = usize::try_from(target) {
+            if let Some(instr) = self.program.get_mut(target) {
+                *instr = instr.toggled();
+                self.ops = if self.optimising {
+                    optimise(&self.program)
+                } else {
+                    self.program.iter().copied().map(Op::Plain).collect()
+                };
+            }
+        }
+    }
+
+    /// Run one instruction (or optimised loop), returning the new `pc`
+    fn exec_plain<O: Output>(&mut self, instr: Instr, out: &mut O) -> Option<i64> {
+        match instr {
+            Instr::Cpy(from, to) => self.store(to, self.value(from)),
+            Instr::Inc(x) => self.store(x, self.value(x) + 1),
+            Instr::Dec(x) => self.store(x, self.value(x) - 1),
+            Instr::Jnz(test, ofs) => {
+                if self.value(test) != 0 {
+                    return Some(self.pc + self.value(ofs));
+                }
+            }
+            Instr::Tgl(x) => self.toggle(self.pc + self.value(x)),
+            Instr::Out(x) => {
+                if !out.output(self.value(x)) {
+                    return None;
+                }
+            }
+        }
+        Some(self.pc + 1)
+    }
+
+    fn exec<O: Output>(&mut self, op: Op, out: &mut O) -> Option<i64> {
+        match op {
+            // Loops which wouldn't run the ordinary way round are left to
+            // behave however they do when run as written
+            Op::Add { to, counter } if self.reg(counter) > 0 => {
+                self.set_reg(to, self.reg(to) + self.reg(counter));
+                self.set_reg(counter, 0);
+                Some(self.pc + 3)
+            }
+            Op::Mul {
+                to,
+                src,
+                inner,
+                outer,
+            } if self.value(src) > 0 && self.reg(outer) > 0 => {
+                self.set_reg(to, self.reg(to) + self.value(src) * self.reg(outer));
+                self.set_reg(inner, 0);
+                self.set_reg(outer, 0);
+                Some(self.pc + 6)
+            }
+            Op::Add { .. } | Op::Mul { .. } => self.exec_plain(self.program[self.pc as usize], out),
+            Op::Plain(instr) => self.exec_plain(instr, out),
+        }
+    }
+
+    /// Run until the program ends or `out` asks us to stop
+    pub fn run_with<O: Output>(&mut self, out: &mut O) {
+        while let Some(&op) = usize::try_from(self.pc)
+            .ok()
+            .and_then(|pc| self.ops.get(pc))
+        {
+            self.steps += 1;
+            match self.exec(op, out) {
+                Some(pc) => self.pc = pc,
+                None => break,
+            }
+        }
+    }
+
+    /// Run until the program ends, ignoring anything it outputs
+    pub fn run(&mut self) {
+        self.run_with(&mut Vec::new())
+    }
+}
+
+impl FromStr for VM {
+    type Err = aoc_common::GenError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let program = s
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(Instr::from_str)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(VM::new(program))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn toggling() {
+        // The example from day 23, where `tgl` rewrites the code in front of it
+        let program = "cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a\n";
+        let mut vm = VM::from_str(program).unwrap();
+        vm.run();
+        assert_eq!(vm.reg(Reg::A), 3);
+    }
+
+    #[test]
+    fn loops_are_optimised() {
+        let program = "cpy 5 d\ncpy 7 b\ninc a\ndec b\njnz b -2\ndec d\njnz d -5\n";
+        let mut plain = VM::from_str(program).unwrap().unoptimised();
+        plain.run();
+        let mut fast = VM::from_str(program).unwrap();
+        fast.run();
+        assert_eq!(plain.reg(Reg::A), 35);
+        assert_eq!(fast.reg(Reg::A), 35);
+        assert_eq!(fast.regs, plain.regs);
+        assert_eq!(fast.steps(), 2);
+        assert!(plain.steps() > 100);
+    }
+
+    #[test]
+    fn toggled_loops_are_not() {
+        // Once the `jnz` is toggled into a `cpy` there's no loop left, so
+        // the `inc`/`dec` must run just the once
+        let program = "cpy 3 b\ntgl 3\ninc a\ndec b\njnz b -2\n";
+        let mut vm = VM::from_str(program).unwrap();
+        vm.run();
+        assert_eq!((vm.reg(Reg::A), vm.reg(Reg::B)), (1, 2));
+    }
+
+    #[test]
+    fn output() {
+        struct FirstTwo(Vec<i64>);
+        impl Output for FirstTwo {
+            fn output(&mut self, value: i64) -> bool {
+                self.0.push(value);
+                self.0.len() < 2
+            }
+        }
+        let program = "out a\ninc a\njnz 1 -2\n";
+        let mut sink = FirstTwo(Vec::new());
+        VM::from_str(program).unwrap().run_with(&mut sink);
+        assert_eq!(sink.0, vec![0, 1]);
+        assert!(VM::from_str("jmp 2").is_err());
+    }
+}
@@ -1,7 +1,6 @@
+use aoc_common::input::open_input;
 use std::collections::HashSet;
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
 #[derive(Debug, Copy, Clone)]
@@ -91,8 +90,7 @@ impl Instruction {
 }
 
 fn load_instructions() -> Vec<Instruction> {
-    let infile = File::open("day1.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(1).unwrap();
     let mut ret: Vec<Instruction> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -148,7 +146,7 @@ fn problem2() -> i32 {
     0
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
     println!("Result 2: {}", problem2());
 }
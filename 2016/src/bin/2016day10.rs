@@ -1,12 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+use lazy_static::lazy_static;
 
+use aoc_common::input::open_input;
 use regex::Regex;
 use std::collections::HashMap;
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 
 #[derive(Debug, Clone, Copy)]
 enum Goal {
@@ -178,8 +175,7 @@ impl Instruction {
 }
 
 fn load_state() -> RoomState {
-    let f = File::open("day10.input").unwrap();
-    let reader = BufReader::new(f);
+    let reader = open_input(10).unwrap();
     let mut bots: HashMap<usize, Bot> = HashMap::new();
     let mut bins: HashMap<usize, usize> = HashMap::new();
     let ensure_bot = |bots: &mut HashMap<usize, Bot>, n: usize| {
@@ -238,7 +234,7 @@ fn problem2() -> usize {
     o0 * o1 * o2
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", problem1());
     println!("Problem 2: {}", problem2());
 }
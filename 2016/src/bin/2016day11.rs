@@ -415,7 +415,7 @@ fn problem2() -> usize {
     solver.solve()
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", problem1());
     println!("Problem 2: {}", problem2());
 }
@@ -89,7 +89,7 @@ fn problem2() -> usize {
     count_locs(50)
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
     println!("Result 2: {}", problem2());
 }
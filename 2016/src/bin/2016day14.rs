@@ -163,10 +163,10 @@ fn problem2(label: &str) -> usize {
     }
 }
 
-fn main() {
+pub fn main() {
     let puzzleinput = "ihaygndm";
     //    println!("Test result: {}", problem1("abc"));
-    println!("Result 1 is {}", problem1(&puzzleinput));
+    println!("Result 1: {}", problem1(&puzzleinput));
     //    println!("Test result: {}", problem2("abc"));
-    println!("Result 2 is {}", problem2(&puzzleinput));
+    println!("Result 2: {}", problem2(&puzzleinput));
 }
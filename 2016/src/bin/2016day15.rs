@@ -97,7 +97,7 @@ fn problem2() -> usize {
     n
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", problem1());
     println!("Problem 2: {}", problem2());
 }
@@ -44,13 +44,13 @@ fn checksum(i: &str) -> String {
     }
 }
 
-fn main() {
+pub fn main() {
     println!(
-        "Problem 1 -> {}",
+        "Problem 1: {}",
         checksum(&fill_disc("10111011111001111", 272))
     );
     println!(
-        "Problem 1 -> {}",
+        "Problem 2: {}",
         checksum(&fill_disc("10111011111001111", 35651584))
     );
 }
@@ -94,7 +94,7 @@ fn find_longest_route(passcode: &str) -> usize {
     found.pop().unwrap().len()
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", find_route("lpvhkcbi"));
     println!("Problem 2: {}", find_longest_route("lpvhkcbi"));
 }
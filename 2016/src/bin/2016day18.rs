@@ -41,7 +41,7 @@ fn puzzle1(input: &str, rowcount: usize) -> usize {
 
 static INPUT: &str = ".^^^.^.^^^.^.......^^.^^^^.^^^^..^^^^^.^.^^^..^^.^.^^..^.^..^^...^.^^.^^^...^^.^.^^^..^^^^.....^....";
 
-fn main() {
-    println!("Puzzle 1: {}", puzzle1(INPUT, 40));
-    println!("Puzzle 2: {}", puzzle1(INPUT, 400000));
+pub fn main() {
+    println!("Part 1: {}", puzzle1(INPUT, 40));
+    println!("Part 2: {}", puzzle1(INPUT, 400000));
 }
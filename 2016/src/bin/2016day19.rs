@@ -50,7 +50,7 @@ fn winner2(n_elves: u32) -> u32 {
     elves[0]
 }
 
-fn main() {
+pub fn main() {
     println!("Test: 5 elves, winner: {}", winner1(5));
     println!("Problem 1: {}", winner1(3018458));
     println!("Test: 5 elves, winner: {}", winner2(5));
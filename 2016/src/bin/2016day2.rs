@@ -1,6 +1,5 @@
-use std::fs::File;
+use aoc_common::input::open_input;
 use std::io::prelude::*;
-use std::io::BufReader;
 
 #[derive(Debug, Copy, Clone)]
 enum Instruction {
@@ -152,8 +151,7 @@ impl Instruction {
 }
 
 fn load_instructions() -> Vec<Vec<Instruction>> {
-    let infile = File::open("day2.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(2).unwrap();
     let mut ret: Vec<Vec<Instruction>> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -168,7 +166,7 @@ fn load_instructions() -> Vec<Vec<Instruction>> {
 
 fn problem1() {
     let instructions = load_instructions();
-    print!("Result of problem 1: ");
+    print!("Result 1: ");
     for lvec in instructions.iter() {
         let mut pos = 5;
         for instr in lvec.iter() {
@@ -181,7 +179,7 @@ fn problem1() {
 
 fn problem2() {
     let instructions = load_instructions();
-    print!("Result of problem 2: ");
+    print!("Result 2: ");
     let buttons = "-123456789ABCD";
     for lvec in instructions.iter() {
         let mut pos = 5;
@@ -194,7 +192,7 @@ fn problem2() {
     println!();
 }
 
-fn main() {
+pub fn main() {
     problem1();
     problem2();
 }
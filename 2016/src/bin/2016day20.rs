@@ -1,10 +1,8 @@
-use std::fs::File;
+use aoc_common::input::open_input;
 use std::io::BufRead;
-use std::io::BufReader;
 
 fn load_ranges() -> Vec<(u32, u32)> {
-    let infile = File::open("day20.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(20).unwrap();
     let mut ret: Vec<(u32, u32)> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -76,7 +74,7 @@ fn problem2() -> u32 {
     tot
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", problem1());
     println!("Problem 2: {}", problem2());
 }
@@ -1,12 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+use lazy_static::lazy_static;
 
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
+use aoc_common::input::open_input;
 use regex::Regex;
 
 #[derive(Debug, Clone)]
@@ -200,8 +197,7 @@ impl Instr {
 }
 
 fn load_instructions() -> Vec<Instr> {
-    let infile = File::open("day21.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(21).unwrap();
     let mut ret: Vec<Instr> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -229,7 +225,7 @@ fn problem2(inp: &str) -> String {
     pass
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", problem1("abcdefgh"));
     println!("Problem 2: {}", problem2("fbgdceah"));
 }
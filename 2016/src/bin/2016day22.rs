@@ -1,12 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+use lazy_static::lazy_static;
 
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
+use aoc_common::input::open_input;
 use regex::Regex;
 
 #[derive(Debug)]
@@ -259,8 +256,7 @@ impl Grid {
 }
 
 fn load_grid() -> Grid {
-    let infile = File::open("day22.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(22).unwrap();
     let mut ret = Grid::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -324,7 +320,7 @@ fn problem2() -> usize {
     movecount
 }
 
-fn main() {
+pub fn main() {
     println!("Problem 1: {}", problem1());
     println!("Problem 2: {}", problem2());
 }
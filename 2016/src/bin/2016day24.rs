@@ -1,9 +1,9 @@
 use std::collections::HashSet;
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
+use aoc_common::input::open_input;
+
 #[derive(Debug)]
 struct Maze {
     // true if open, false if wall
@@ -138,9 +138,7 @@ impl Maze {
     }
 }
 
-fn load_maze(pth: &str) -> Maze {
-    let infile = File::open(pth).unwrap();
-    let freader = BufReader::new(&infile);
+fn load_maze<R: BufRead>(freader: R) -> Maze {
     let mut ret: Maze = Maze::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -150,26 +148,28 @@ fn load_maze(pth: &str) -> Maze {
     ret
 }
 
-fn test() {
-    let maze = load_maze("test.input");
-    println!(
-        "test maze shortest route is {} long",
-        maze.shortest_route(false)
-    );
-}
-
 fn problem1() -> usize {
-    let maze = load_maze("day24.input");
+    let maze = load_maze(open_input(24).unwrap());
     maze.shortest_route(false)
 }
 
 fn problem2() -> usize {
-    let maze = load_maze("day24.input");
+    let maze = load_maze(open_input(24).unwrap());
     maze.shortest_route(true)
 }
 
-fn main() {
-    test();
+pub fn main() {
     println!("Problem 1: {}", problem1());
     println!("Problem 2: {}", problem2());
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let maze = load_maze(include_str!("../../inputs/day24.example1").as_bytes());
+        assert_eq!(maze.shortest_route(false), 14);
+    }
+}
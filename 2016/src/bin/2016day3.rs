@@ -1,6 +1,5 @@
-use std::fs::File;
+use aoc_common::input::open_input;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
 #[derive(Debug, Copy, Clone)]
@@ -34,8 +33,7 @@ impl Triangle {
 }
 
 fn load_triangles() -> Vec<Triangle> {
-    let infile = File::open("day3.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(3).unwrap();
     let mut ret: Vec<Triangle> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -51,8 +49,7 @@ fn problem1() -> usize {
 }
 
 fn load_triangles2() -> Vec<Triangle> {
-    let infile = File::open("day3.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(3).unwrap();
     let mut ret: Vec<Triangle> = Vec::new();
     let mut lines: Vec<String> = freader.lines().map(&std::result::Result::unwrap).collect();
     while !lines.is_empty() {
@@ -100,7 +97,7 @@ fn problem2() -> usize {
     triangles.len()
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
     println!("Result 2: {}", problem2());
 }
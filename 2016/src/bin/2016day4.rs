@@ -1,13 +1,10 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+use lazy_static::lazy_static;
 
 use std::collections::HashMap;
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
+use aoc_common::input::open_input;
 use regex::Regex;
 
 #[derive(Debug, Clone)]
@@ -93,8 +90,7 @@ impl Room {
 }
 
 fn load_rooms() -> Vec<Room> {
-    let infile = File::open("day4.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(4).unwrap();
     let mut ret: Vec<Room> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -120,7 +116,7 @@ fn problem2() -> u32 {
     0
 }
 
-fn main() {
+pub fn main() {
     let r = Room::new(("aaaaa-bbb-z-y-x-123[abxyz]").to_string());
     println!("Test room: {:?}", r);
     println!("Test room validity: {}", r.is_valid());
@@ -59,10 +59,10 @@ fn problem2(label: &str) -> String {
     return ret.iter().cloned().collect::<String>();
 }
 
-fn main() {
+pub fn main() {
     let puzzleinput = ("uqwqemis").to_string();
     //    println!("Test result is {}", problem1(&("abc".to_string())));
-    println!("Result 1 is {}", problem1(&puzzleinput));
+    println!("Result 1: {}", problem1(&puzzleinput));
     //    println!("Test result is {}", problem2(&("abc".to_string())));
-    println!("Result 2 is {}", problem2(&puzzleinput));
+    println!("Result 2: {}", problem2(&puzzleinput));
 }
@@ -1,12 +1,10 @@
+use aoc_common::input::open_input;
 use std::collections::HashMap;
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
 fn load_words() -> Vec<String> {
-    let infile = File::open("day6.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(6).unwrap();
     let mut ret: Vec<String> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -55,7 +53,7 @@ fn problem2() -> String {
     ret
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
     println!("Result 2: {}", problem2());
 }
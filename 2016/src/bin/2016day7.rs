@@ -1,6 +1,5 @@
-use std::fs::File;
+use aoc_common::input::open_input;
 use std::io::prelude::*;
-use std::io::BufReader;
 
 #[derive(Debug)]
 struct Address {
@@ -96,8 +95,7 @@ impl Address {
 
 fn load_addresses() -> Vec<Address> {
     let mut ret: Vec<Address> = Vec::new();
-    let f = File::open("day7.input").unwrap();
-    let reader = BufReader::new(f);
+    let reader = open_input(7).unwrap();
     for line_ in reader.lines() {
         let line = line_.unwrap();
         ret.push(Address::new(line));
@@ -117,7 +115,7 @@ fn problem2() -> usize {
     addrs.len()
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
     println!("Result 2: {}", problem2());
 }
@@ -1,12 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
+use lazy_static::lazy_static;
 
-use std::fs::File;
 use std::io::prelude::*;
-use std::io::BufReader;
 use std::vec::Vec;
 
+use aoc_common::input::open_input;
 use regex::Regex;
 
 #[derive(Debug, Clone)]
@@ -113,26 +110,33 @@ impl Screen {
             }
         }
     }
-    fn show(&self) {
+    fn draw(&self) -> String {
+        let mut ret = String::new();
         for row in 0..6 {
             for ch in 0..10 {
                 for pix in 0..5 {
                     if self.get_pix((ch * 5) + pix, row) {
-                        print!("#");
+                        ret.push('#');
                     } else {
-                        print!(" ");
+                        ret.push(' ');
                     }
                 }
-                print!(" ");
+                ret.push(' ');
             }
-            println!();
+            ret.push('\n');
         }
+        ret
+    }
+
+    fn read(&self) -> String {
+        let screen = self.draw();
+        // If the letters can't be read, the screen itself is still an answer
+        aoc_common::ocr::read_grid(&screen).unwrap_or(screen)
     }
 }
 
 fn load_instructions() -> Vec<Instr> {
-    let infile = File::open("day8.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(8).unwrap();
     let mut ret: Vec<Instr> = Vec::new();
     for line_ in freader.lines() {
         let line = line_.unwrap();
@@ -150,16 +154,16 @@ fn problem1() -> usize {
     screen.count()
 }
 
-fn problem2() {
+fn problem2() -> String {
     let instrs = load_instructions();
     let mut screen = Screen::new();
     for instr in &instrs {
         screen.run_instr(instr);
     }
-    screen.show();
+    screen.read()
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
-    problem2();
+    println!("Result 2: {}", problem2());
 }
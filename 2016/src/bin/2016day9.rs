@@ -1,10 +1,8 @@
-use std::fs::File;
+use aoc_common::input::open_input;
 use std::io::prelude::*;
-use std::io::BufReader;
 
 fn load_compressed() -> String {
-    let infile = File::open("day9.input").unwrap();
-    let freader = BufReader::new(&infile);
+    let freader = open_input(9).unwrap();
     freader.lines().next().unwrap().unwrap()
 }
 
@@ -62,7 +60,7 @@ fn problem2() -> usize {
     len_compressed(&compr, true)
 }
 
-fn main() {
+pub fn main() {
     println!("Result 1: {}", problem1());
     println!("Result 2: {}", problem2());
 }
@@ -1 +1,13 @@
-// Nothing here
+// The days are compiled into this crate too, see `days` below
+extern crate self as aoc2016;
+
+pub mod assembunny;
+
+/// Days which exist, but which running the whole year should leave alone
+pub const SKIPPED_DAYS: &[(u8, &str)] = &[];
+
+/// Every day of 2016, generated from `src/bin` by `build.rs`
+#[cfg(not(test))]
+pub mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
@@ -0,0 +1,48 @@
+extern crate aoc_common;
+
+use std::io::prelude::*;
+use std::vec::Vec;
+
+fn load_instructions() -> Vec<u32> {
+    let freader = aoc_common::input::open_input(1).unwrap();
+    let mut ret: Vec<u32> = Vec::new();
+    for line_ in freader.lines() {
+        let line = line_.unwrap();
+        for digit in line.chars() {
+            ret.push(digit.to_digit(10).unwrap());
+        }
+    }
+    ret
+}
+
+fn problem1(input: &[u32]) -> u32 {
+    let mut sum: u32 = 0;
+    for i in 0..input.len() - 1 {
+        if input[i] == input[i + 1] {
+            sum += input[i];
+        }
+    }
+    if input[0] == input[input.len() - 1] {
+        sum += input[0];
+    }
+    sum
+}
+
+fn problem2(input: &[u32]) -> u32 {
+    let mut sum: u32 = 0;
+    let skip = input.len() >> 1;
+    let l = input.len();
+
+    for i in 0..input.len() {
+        if input[i] == input[(i + skip) % l] {
+            sum += input[i];
+        }
+    }
+    sum
+}
+
+pub fn main() {
+    let input = load_instructions();
+    println!("Problem 1: {}", problem1(&input));
+    println!("Problem 2: {}", problem2(&input));
+}
@@ -0,0 +1,35 @@
+extern crate aoc2017duet;
+
+use aoc2017duet::{load_instructions, Inst, Mailbox, SoundCard, VM};
+
+fn problem1(input: &[Inst]) -> i64 {
+    let mut vm = VM::new(input);
+    let mut card = SoundCard::default();
+    vm.run(&mut card);
+    card.recovered.unwrap()
+}
+
+fn problem2(input: &[Inst]) -> usize {
+    let mut vm0 = VM::new(input);
+    let mut vm1 = VM::new(input);
+    vm1.set_reg('p', 1);
+    let mut box0 = Mailbox::default();
+    let mut box1 = Mailbox::default();
+    // Each runs until it's waiting on the other (or done), and once neither
+    // had anything to say to the other they're finished or deadlocked
+    loop {
+        vm0.run(&mut box0);
+        vm1.run(&mut box1);
+        if !Mailbox::exchange(&mut box0, &mut box1) {
+            break;
+        }
+    }
+    box1.sent
+}
+
+pub fn main() {
+    let input = load_instructions(18);
+    println!("Loaded {} instructions", input.len());
+    println!("Problem 1: {}", problem1(&input));
+    println!("Problem 2: {}", problem2(&input));
+}
@@ -0,0 +1,67 @@
+extern crate aoc2017duet;
+extern crate primal;
+
+use aoc2017duet::{load_instructions, Inst, NoChannel, Val, VM};
+
+use primal::Sieve;
+
+fn problem1(input: &[Inst]) -> u64 {
+    let mut vm = VM::new(input);
+    vm.run(&mut NoChannel);
+    vm.count(|inst| matches!(inst, Inst::Mul(..)))
+}
+
+fn problem2(input: &[Inst]) -> usize {
+    // Debug mode runs the same code over a much smaller range, so profile it
+    // to find the shape of the program (see input.annotated).  The hottest
+    // loop tries multiplying pairs of numbers to see if they make b, and the
+    // outermost loop around it steps b up until it reaches c, counting the b
+    // which had factors.  That's counting composites, which we can do
+    // rather quicker.
+    let mut vm = VM::new(input);
+    vm.run(&mut NoChannel);
+    let loops = vm.hot_loops();
+    let outer = loops
+        .iter()
+        .filter(|l| l.contains(&loops[0]))
+        .max_by_key(|l| l.end - l.start)
+        .expect("No loops in program");
+    // The outer loop ends by stepping b and jumping back...
+    let (counter, step) = match input[outer.end - 1] {
+        Inst::Sub(r, Val::Num(n)) => (r, -n),
+        _ => panic!("Unexpected shape for the outer loop"),
+    };
+    // ...unless b has reached c, which it finds with `set g b`, `sub g c`
+    let limit = (outer.start..outer.end)
+        .find_map(|pc| match (input[pc], input[pc + 1]) {
+            (Inst::Set(g, Val::Reg(r)), Inst::Sub(g_, Val::Reg(limit)))
+                if r == counter && g == g_ =>
+            {
+                Some(limit)
+            }
+            _ => None,
+        })
+        .expect("Unexpected shape for the outer loop");
+
+    // So all we need from the real run is where b and c start out
+    let mut vm = VM::new(input);
+    vm.set_reg('a', 1);
+    while vm.pc() != outer.start as i64 {
+        if vm.step(&mut NoChannel).is_some() {
+            panic!("Never reached the outer loop");
+        }
+    }
+    let (first, last) = (vm.reg(counter), vm.reg(limit));
+    let sieve = Sieve::new(last as usize);
+    (first..=last)
+        .step_by(step as usize)
+        .filter(|&b| !sieve.is_prime(b as usize))
+        .count()
+}
+
+pub fn main() {
+    let input = load_instructions(23);
+    println!("Loaded {} instructions", input.len());
+    println!("Problem 1: {}", problem1(&input));
+    println!("Problem 2: {}", problem2(&input));
+}
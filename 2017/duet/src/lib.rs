@@ -0,0 +1,414 @@
+//! The assembly language of 2017 days 18 (Duet) and 23 (the coprocessor)
+//!
+//! Both days run the same sort of program; what differs is what `snd` and
+//! `rcv` do, so those go through a [`Channel`].  Day 18's sound card is a
+//! [`SoundCard`], its two programs talk through a pair of [`Mailbox`]es, and
+//! the coprocessor has nothing connected, [`NoChannel`].
+//!
+//! The VM counts how many times each instruction runs, and
+//! [`VM::hot_loops`] uses that to point out where a program spends its time.
+
+extern crate aoc_common;
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Val {
+    Reg(char),
+    Num(i64),
+}
+
+impl FromStr for Val {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Val, String> {
+        match s.chars().next() {
+            Some(c @ 'a'..='z') if s.len() == 1 => Ok(Val::Reg(c)),
+            _ => s
+                .parse()
+                .map(Val::Num)
+                .map_err(|_| format!("Bad value {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inst {
+    Snd(Val),
+    Set(char, Val),
+    Add(char, Val),
+    Sub(char, Val),
+    Mul(char, Val),
+    Mod(char, Val),
+    Rcv(Val),
+    Jgz(Val, Val),
+    Jnz(Val, Val),
+}
+
+impl FromStr for Inst {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Inst, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let bad = || format!("Unparseable instruction: {:?}", s);
+        let reg = |n: usize| match words[n].parse() {
+            Ok(Val::Reg(r)) => Ok(r),
+            _ => Err(bad()),
+        };
+        let val = |n: usize| words[n].parse::<Val>();
+        Ok(match (words.first().copied(), words.len()) {
+            (Some("snd"), 2) => Inst::Snd(val(1)?),
+            (Some("rcv"), 2) => Inst::Rcv(val(1)?),
+            (Some("set"), 3) => Inst::Set(reg(1)?, val(2)?),
+            (Some("add"), 3) => Inst::Add(reg(1)?, val(2)?),
+            (Some("sub"), 3) => Inst::Sub(reg(1)?, val(2)?),
+            (Some("mul"), 3) => Inst::Mul(reg(1)?, val(2)?),
+            (Some("mod"), 3) => Inst::Mod(reg(1)?, val(2)?),
+            (Some("jgz"), 3) => Inst::Jgz(val(1)?, val(2)?),
+            (Some("jnz"), 3) => Inst::Jnz(val(1)?, val(2)?),
+            _ => return Err(bad()),
+        })
+    }
+}
+
+pub fn parse(program: &str) -> Result<Vec<Inst>, String> {
+    program
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Load the program which is `day`'s input
+pub fn load_instructions(day: usize) -> Vec<Inst> {
+    parse(&aoc_common::input::read_input(day).unwrap()).unwrap()
+}
+
+/// What `rcv` should do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Receive {
+    /// Carry on, leaving the register alone
+    Continue,
+    /// Put the value in the register and carry on
+    Store(i64),
+    /// Wait until there's something to receive, retrying this `rcv`
+    Block,
+    /// Stop the program here
+    Stop,
+}
+
+/// Where `snd` sends to and `rcv` receives from
+pub trait Channel {
+    fn send(&mut self, value: i64);
+
+    /// `rcv X`, where `current` is the value of X
+    fn receive(&mut self, current: i64) -> Receive;
+}
+
+/// Day 18's first reading: `snd` plays a sound and `rcv` recovers the last
+/// one played, unless its operand is zero
+#[derive(Debug, Default)]
+pub struct SoundCard {
+    pub played: Option<i64>,
+    pub recovered: Option<i64>,
+}
+
+impl Channel for SoundCard {
+    fn send(&mut self, value: i64) {
+        self.played = Some(value);
+    }
+
+    fn receive(&mut self, current: i64) -> Receive {
+        if current == 0 {
+            return Receive::Continue;
+        }
+        self.recovered = self.played;
+        Receive::Stop
+    }
+}
+
+/// Day 18's second reading: one end of a pair of queues between programs
+#[derive(Debug, Default)]
+pub struct Mailbox {
+    pub inbox: VecDeque<i64>,
+    /// How many values have been sent so far
+    pub sent: usize,
+    outbox: VecDeque<i64>,
+}
+
+impl Mailbox {
+    /// Move what each side has sent into the other's inbox, returning false
+    /// if nothing was waiting to move
+    pub fn exchange(a: &mut Mailbox, b: &mut Mailbox) -> bool {
+        let moved = !(a.outbox.is_empty() && b.outbox.is_empty());
+        b.inbox.extend(a.outbox.drain(..));
+        a.inbox.extend(b.outbox.drain(..));
+        moved
+    }
+}
+
+impl Channel for Mailbox {
+    fn send(&mut self, value: i64) {
+        self.outbox.push_back(value);
+        self.sent += 1;
+    }
+
+    fn receive(&mut self, _current: i64) -> Receive {
+        match self.inbox.pop_front() {
+            Some(value) => Receive::Store(value),
+            None => Receive::Block,
+        }
+    }
+}
+
+/// Day 23's coprocessor, where nothing is connected
+#[derive(Debug, Default)]
+pub struct NoChannel;
+
+impl Channel for NoChannel {
+    fn send(&mut self, _value: i64) {}
+
+    fn receive(&mut self, _current: i64) -> Receive {
+        Receive::Block
+    }
+}
+
+/// Why a program stopped running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// It jumped out of the program
+    Halted,
+    /// It's waiting at an `rcv`
+    Blocked,
+    /// The channel told it to stop
+    Stopped,
+}
+
+/// A backward jump, and everything it jumps back over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    pub start: usize,
+    /// Where the jump back to `start` is
+    pub end: usize,
+    /// How many times the jump back was taken
+    pub iterations: u64,
+    /// How many instructions were run inside the loop
+    pub executed: u64,
+}
+
+impl Loop {
+    pub fn contains(&self, other: &Loop) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+pub struct VM {
+    prog: Vec<Inst>,
+    regs: [i64; 26],
+    pc: i64,
+    /// How many times each instruction has run
+    counts: Vec<u64>,
+    /// How many times each jump has been taken
+    taken: Vec<u64>,
+}
+
+impl VM {
+    pub fn new(input: &[Inst]) -> VM {
+        VM {
+            prog: input.to_vec(),
+            regs: [0; 26],
+            pc: 0,
+            counts: vec![0; input.len()],
+            taken: vec![0; input.len()],
+        }
+    }
+
+    pub fn reg(&self, r: char) -> i64 {
+        self.regs[(r as u8 - b'a') as usize]
+    }
+
+    pub fn set_reg(&mut self, r: char, value: i64) {
+        self.regs[(r as u8 - b'a') as usize] = value;
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    fn eval_val(&self, val: Val) -> i64 {
+        match val {
+            Val::Reg(r) => self.reg(r),
+            Val::Num(i) => i,
+        }
+    }
+
+    fn update<F: Fn(i64, i64) -> i64>(&mut self, r: char, val: Val, f: F) {
+        let v = f(self.reg(r), self.eval_val(val));
+        self.set_reg(r, v);
+    }
+
+    /// Run one instruction, returning why we can't if we can't
+    pub fn step<C: Channel>(&mut self, channel: &mut C) -> Option<State> {
+        if self.pc < 0 || self.pc >= self.prog.len() as i64 {
+            return Some(State::Halted);
+        }
+        let pc = self.pc as usize;
+        let mut nextpc = self.pc + 1;
+        match self.prog[pc] {
+            Inst::Snd(v) => channel.send(self.eval_val(v)),
+            Inst::Set(r, v) => self.update(r, v, |_, v| v),
+            Inst::Add(r, v) => self.update(r, v, |rv, v| rv + v),
+            Inst::Sub(r, v) => self.update(r, v, |rv, v| rv - v),
+            Inst::Mul(r, v) => self.update(r, v, |rv, v| rv * v),
+            Inst::Mod(r, v) => self.update(r, v, |rv, v| rv % v),
+            Inst::Rcv(v) => match channel.receive(self.eval_val(v)) {
+                Receive::Continue => {}
+                Receive::Store(value) => {
+                    if let Val::Reg(r) = v {
+                        self.set_reg(r, value);
+                    }
+                }
+                Receive::Block => return Some(State::Blocked),
+                Receive::Stop => {
+                    self.counts[pc] += 1;
+                    return Some(State::Stopped);
+                }
+            },
+            Inst::Jgz(cv, ov) | Inst::Jnz(cv, ov) => {
+                let cv = self.eval_val(cv);
+                let jump = match self.prog[pc] {
+                    Inst::Jgz(..) => cv > 0,
+                    _ => cv != 0,
+                };
+                if jump {
+                    nextpc = self.pc + self.eval_val(ov);
+                    self.taken[pc] += 1;
+                }
+            }
+        }
+        self.counts[pc] += 1;
+        self.pc = nextpc;
+        None
+    }
+
+    /// Run until the program halts, blocks or is stopped
+    pub fn run<C: Channel>(&mut self, channel: &mut C) -> State {
+        loop {
+            if let Some(state) = self.step(channel) {
+                return state;
+            }
+        }
+    }
+
+    /// How many times each instruction has run
+    pub fn profile(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// How many times instructions like `inst` have run, such as how many
+    /// `mul`s: `vm.count(|i| matches!(i, Inst::Mul(..)))`
+    pub fn count<F: Fn(&Inst) -> bool>(&self, which: F) -> u64 {
+        self.prog
+            .iter()
+            .zip(&self.counts)
+            .filter(|&(inst, _)| which(inst))
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    /// Every loop in the program (that is, every jump back by a constant
+    /// amount), the ones which went round most often first.  (Ordering by
+    /// `executed` would always put the outermost loop first, as it includes
+    /// everything run in the loops inside it.)
+    pub fn hot_loops(&self) -> Vec<Loop> {
+        let mut loops: Vec<Loop> = self
+            .prog
+            .iter()
+            .enumerate()
+            .filter_map(|(end, inst)| match *inst {
+                Inst::Jgz(_, Val::Num(ofs)) | Inst::Jnz(_, Val::Num(ofs))
+                    if ofs <= 0 && end as i64 + ofs >= 0 =>
+                {
+                    let start = (end as i64 + ofs) as usize;
+                    Some(Loop {
+                        start,
+                        end,
+                        iterations: self.taken[end],
+                        executed: self.counts[start..=end].iter().sum(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        loops.sort_by_key(|l| (std::cmp::Reverse(l.iterations), l.start));
+        loops
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOUNDS: &str =
+        "set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\njgz a -1\nset a 1\njgz a -2\n";
+
+    const PAIRED: &str = "snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d\n";
+
+    #[test]
+    fn sound_card() {
+        let mut vm = VM::new(&parse(SOUNDS).unwrap());
+        let mut card = SoundCard::default();
+        assert_eq!(vm.run(&mut card), State::Stopped);
+        assert_eq!(card.recovered, Some(4));
+        assert!(parse("jmp 1 2").is_err());
+        assert!(parse("set 1 2").is_err());
+    }
+
+    #[test]
+    fn mailboxes() {
+        let prog = parse(PAIRED).unwrap();
+        let mut vms = [VM::new(&prog), VM::new(&prog)];
+        vms[1].set_reg('p', 1);
+        let (mut box0, mut box1) = (Mailbox::default(), Mailbox::default());
+        loop {
+            let states = (vms[0].run(&mut box0), vms[1].run(&mut box1));
+            if !Mailbox::exchange(&mut box0, &mut box1) {
+                assert_eq!(states, (State::Blocked, State::Blocked));
+                break;
+            }
+        }
+        assert_eq!((box0.sent, box1.sent), (3, 3));
+        assert_eq!((vms[0].reg('c'), vms[1].reg('c')), (1, 0));
+    }
+
+    #[test]
+    fn profiling() {
+        // Multiply 4 by 3 the slow way, in a loop inside a loop
+        let prog = parse("set b 4\nset c 3\nadd a 1\nsub c 1\njnz c -2\nsub b 1\njnz b -5\n");
+        let mut vm = VM::new(&prog.unwrap());
+        assert_eq!(vm.run(&mut NoChannel), State::Halted);
+        assert_eq!(vm.reg('a'), 12);
+        assert_eq!(vm.profile(), &[1, 4, 12, 12, 12, 4, 4]);
+        assert_eq!(vm.count(|i| matches!(i, Inst::Add(..))), 12);
+        let loops = vm.hot_loops();
+        assert_eq!(
+            loops,
+            vec![
+                Loop {
+                    start: 2,
+                    end: 4,
+                    iterations: 8,
+                    executed: 36
+                },
+                Loop {
+                    start: 1,
+                    end: 6,
+                    iterations: 3,
+                    executed: 48
+                },
+            ]
+        );
+        assert!(loops[1].contains(&loops[0]));
+    }
+}
@@ -18,7 +18,7 @@ fn part1(input: &[i32]) -> i32 {
     input.iter().sum()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(1)?;
     let input: Result<Vec<i32>> = input.lines().map(|s| Ok(s.parse()?)).collect();
     let input = input?;
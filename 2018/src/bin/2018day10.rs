@@ -106,7 +106,7 @@ impl Grid {
     }
 }
 
-fn find_best(input: &[MessagePoint]) {
+fn find_best(input: &[MessagePoint]) -> (Grid, usize) {
     let mut grid = Grid::new(input);
     let mut bestsize = grid.size();
     let mut seconds = 0;
@@ -124,16 +124,18 @@ fn find_best(input: &[MessagePoint]) {
             break;
         }
     }
-    println!("Best grid is after {} seconds:", seconds);
-    grid.print_grid();
+    (grid, seconds)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Vec<MessagePoint> = input_as_vec(TEST_INPUT)?;
     let input: Vec<MessagePoint> = read_input_as_vec(10)?;
-    println!("Test vector gives:");
-    find_best(&test_input);
-    println!("Problem input gives:");
-    find_best(&input);
+    let (grid, seconds) = find_best(&test_input);
+    println!("Test vector gives, after {} seconds:", seconds);
+    grid.print_grid();
+    let (grid, seconds) = find_best(&input);
+    println!("Part 1:");
+    grid.print_grid();
+    println!("Part 2: {}", seconds);
     Ok(())
 }
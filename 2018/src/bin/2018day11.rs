@@ -171,24 +171,26 @@ fn run_tests2() {
         assert_eq!(grid.score_square(x, y, size), test.4);
     }
 }
-fn part1(input: usize) -> (usize, usize) {
+fn part1(input: usize) -> String {
     let mut grid = FuelGrid::new(300, 300, input);
     grid.set_power_levels();
-    grid.find_best_square(3)
+    let (x, y) = grid.find_best_square(3);
+    format!("{},{}", x, y)
 }
 
-fn part2(input: usize) -> (usize, usize, usize) {
+fn part2(input: usize) -> String {
     let mut grid = FuelGrid::new(300, 300, input);
     grid.set_power_levels();
-    grid.find_best_arbitrary()
+    let (x, y, size) = grid.find_best_arbitrary();
+    format!("{},{},{}", x, y, size)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     test_sat();
     run_tests();
     run_tests2();
     let input: usize = read_input(11)?.parse()?;
-    println!("Part 1: {:?}", part1(input));
-    println!("Part 2: {:?}", part2(input));
+    println!("Part 1: {}", part1(input));
+    println!("Part 2: {}", part2(input));
     Ok(())
 }
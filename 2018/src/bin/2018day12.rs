@@ -145,7 +145,7 @@ fn part2(start: &str, rules: &[Rule]) -> i64 {
     0
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let (test_rawrules, test_startline): (Vec<RawRule>, String) =
         input_as_vec_and_first(TEST_INPUT)?;
     let test_rules: Vec<Rule> = test_rawrules.iter().map(Rule::from_raw).collect();
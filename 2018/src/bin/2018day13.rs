@@ -227,23 +227,23 @@ static TEST_INPUT2: &str = r"/>-<\
   \<->/
 ";
 
-fn part1(input: &TrackGrid) -> Result<(usize, usize)> {
+fn part1(input: &TrackGrid) -> Result<String> {
     // Find when we first splode
     let mut grid = input.clone();
     loop {
-        if let Some(coords) = grid.tick_carts(false)? {
-            break Ok(coords);
+        if let Some((x, y)) = grid.tick_carts(false)? {
+            break Ok(format!("{},{}", x, y));
         }
     }
 }
 
-fn part2(input: &TrackGrid) -> Result<(usize, usize)> {
+fn part2(input: &TrackGrid) -> Result<String> {
     // Find the last cart standing
     let mut grid = input.clone();
     loop {
         grid.tick_carts(true)?;
         if grid.carts.len() == 1 {
-            break Ok((grid.carts[0].x, grid.carts[0].y));
+            break Ok(format!("{},{}", grid.carts[0].x, grid.carts[0].y));
         }
         if grid.carts.is_empty() {
             break Err("No more carts?".into());
@@ -251,19 +251,19 @@ fn part2(input: &TrackGrid) -> Result<(usize, usize)> {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = TrackGrid::from_str(TEST_INPUT)?;
     print!("Loaded test: ");
     test_input.describe();
-    println!("Test 1: {:?}", part1(&test_input)?);
+    println!("Test 1: {}", part1(&test_input)?);
     let test_input = TrackGrid::from_str(TEST_INPUT2)?;
     print!("Loaded test2: ");
     test_input.describe();
-    println!("Test 2: {:?}", part2(&test_input)?);
+    println!("Test 2: {}", part2(&test_input)?);
     let input = TrackGrid::from_str(&read_input(13)?)?;
     print!("Loaded input: ");
     input.describe();
-    println!("Part 1: {:?}", part1(&input)?);
-    println!("Part 2: {:?}", part2(&input)?);
+    println!("Part 1: {}", part1(&input)?);
+    println!("Part 2: {}", part2(&input)?);
     Ok(())
 }
@@ -68,7 +68,7 @@ fn part2(input: &str) -> usize {
 
 static TEST2: &[(&str, usize)] = &[("51589", 9), ("01245", 5), ("92510", 18), ("59414", 2018)];
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     for test in TEST1 {
         assert_eq!(test.1, part1(test.0));
     }
@@ -418,7 +418,7 @@ static TESTS_2: &[(usize, &str)] = &[
     ),
 ];
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     for test in TESTS_1.iter() {
         let cave = Cave::from_str(test.1)?;
         assert_eq!(part1(&cave), test.0);
@@ -1,141 +1,17 @@
 #![allow(non_snake_case)]
 use aoc2018::*;
 
-#[derive(PartialOrd, PartialEq, Ord, Eq, Debug, Copy, Clone, Hash)]
-enum Op {
-    AddR,
-    AddI,
-    MulR,
-    MulI,
-    BanR,
-    BanI,
-    BorR,
-    BorI,
-    SetR,
-    SetI,
-    GtIR,
-    GtRI,
-    GtRR,
-    EqIR,
-    EqRI,
-    EqRR,
-}
-
-use self::Op::*;
-
-impl Op {
-    fn from_raw(v: u8) -> Op {
-        match v {
-            0 => AddR,
-            1 => AddI,
-            2 => MulR,
-            3 => MulI,
-            4 => BanR,
-            5 => BanI,
-            6 => BorR,
-            7 => BorI,
-            8 => SetR,
-            9 => SetI,
-            10 => GtIR,
-            11 => GtRI,
-            12 => GtRR,
-            13 => EqIR,
-            14 => EqRI,
-            15 => EqRR,
-            _ => unreachable!(),
-        }
-    }
-}
-
-struct VM {
-    regs: [i32; 4],
-}
-
-impl VM {
-    fn new() -> VM {
-        VM { regs: [0; 4] }
-    }
-
-    fn exec(&mut self, instr: Op, A: i32, B: i32, C: i32) -> Result<()> {
-        if C < 0 || C > 3 {
-            return Err(format!("C ({}) is out of range", C).into());
-        }
-        let rA = if A >= 0 && A <= 3 {
-            Ok(self.regs[A as usize])
-        } else {
-            Err(format!("A ({}) is out of range", A))
-        };
-        let rB = if B >= 0 && B <= 3 {
-            Ok(self.regs[B as usize])
-        } else {
-            Err(format!("B ({}) is out of range", B))
-        };
-        self.regs[C as usize] = match instr {
-            AddR => rA? + rB?,
-            AddI => rA? + B,
-            MulR => rA? * rB?,
-            MulI => rA? * B,
-            BanR => rA? & rB?,
-            BanI => rA? & B,
-            BorR => rA? | rB?,
-            BorI => rA? | B,
-            SetR => rA?,
-            SetI => A,
-            GtIR => {
-                if A > rB? {
-                    1
-                } else {
-                    0
-                }
-            }
-            GtRI => {
-                if rA? > B {
-                    1
-                } else {
-                    0
-                }
-            }
-            GtRR => {
-                if rA? > rB? {
-                    1
-                } else {
-                    0
-                }
-            }
-            EqIR => {
-                if A == rB? {
-                    1
-                } else {
-                    0
-                }
-            }
-            EqRI => {
-                if rA? == B {
-                    1
-                } else {
-                    0
-                }
-            }
-            EqRR => {
-                if rA? == rB? {
-                    1
-                } else {
-                    0
-                }
-            }
-        };
-        Ok(())
-    }
-}
+use aoc2018::elfcode::Op::*;
+use aoc2018::elfcode::{Instr, Op, VM};
 
 #[derive(ParseByRegex, Debug, Copy, Clone)]
 enum InputLine {
     #[regex = r"^Before: \[(-?\d+), (-?\d+), (-?\d+), (-?\d+)\]$"]
-    Before(i32, i32, i32, i32),
+    Before(i64, i64, i64, i64),
     #[regex = r"^(\d+) (-?\d+) (-?\d+) (-?\d+)$"]
-    Instr(u8, i32, i32, i32),
+    Instr(u8, i64, i64, i64),
     #[regex = r"^After:  \[(-?\d+), (-?\d+), (-?\d+), (-?\d+)\]$"]
-    After(i32, i32, i32, i32),
+    After(i64, i64, i64, i64),
 }
 
 impl InputLine {
@@ -149,7 +25,7 @@ impl InputLine {
         matches!(self, InputLine::After(_, _, _, _))
     }
 
-    fn get_args(&self) -> (i32, i32, i32) {
+    fn get_args(&self) -> (i64, i64, i64) {
         match self {
             InputLine::Instr(_, A, B, C) => (*A, *B, *C),
             _ => unreachable!(),
@@ -163,7 +39,7 @@ impl InputLine {
         }
     }
 
-    fn get_regs(&self) -> [i32; 4] {
+    fn get_regs(&self) -> [i64; 4] {
         match self {
             InputLine::Before(r0, r1, r2, r3) => [*r0, *r1, *r2, *r3],
             InputLine::After(r0, r1, r2, r3) => [*r0, *r1, *r2, *r3],
@@ -188,11 +64,16 @@ fn count_possibilities(input: &[InputLine]) -> Result<Vec<Op>> {
     let mut ret = Vec::new();
     let (A, B, C) = input[1].get_args();
 
-    for op in (0..16).map(Op::from_raw) {
-        let mut vm = VM::new();
-        vm.regs = input[0].get_regs();
-        vm.exec(op, A, B, C)?;
-        if vm.regs == input[2].get_regs() {
+    for op in Op::ALL.iter().copied() {
+        let mut vm = VM::default();
+        vm.regs[..4].copy_from_slice(&input[0].get_regs());
+        vm.exec(Instr {
+            op,
+            a: A,
+            b: B,
+            c: C,
+        })?;
+        if vm.regs[..4] == input[2].get_regs() {
             ret.push(op);
         }
     }
@@ -229,9 +110,9 @@ fn part1(input: &[InputLine]) -> Result<usize> {
     Ok(ret)
 }
 
-fn part2(input: &[InputLine]) -> Result<i32> {
+fn part2(input: &[InputLine]) -> Result<i64> {
     let mut opmap: HashMap<u8, HashSet<Op>> = HashMap::new();
-    let full_opset: HashSet<Op> = (0..16).map(Op::from_raw).collect();
+    let full_opset: HashSet<Op> = Op::ALL.iter().copied().collect();
     for chunk in input.chunks(3) {
         if chunk[0].is_before() {
             let poss = count_possibilities(&chunk)?;
@@ -294,17 +175,22 @@ fn part2(input: &[InputLine]) -> Result<i32> {
         }
     }
 
-    let mut vm = VM::new();
+    let mut vm = VM::default();
     for instr in input[lastafter + 1..].iter() {
         let op = opmap[&instr.get_opnum()];
         let (A, B, C) = instr.get_args();
-        vm.exec(op, A, B, C)?;
+        vm.exec(Instr {
+            op,
+            a: A,
+            b: B,
+            c: C,
+        })?;
     }
 
     Ok(vm.regs[0])
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let raw_input = read_input(16)?;
     let input: Result<Vec<InputLine>> = raw_input
         .lines()
@@ -259,13 +259,15 @@ fn part1_and_2(input: &Landscape) -> (usize, usize) {
     (land.count_wet_cells(), land.count_stillwater_cells())
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Landscape = Landscape::new(&input_as_vec(TEST_INPUT)?);
     if cfg!(debug_assertions) {
         test_input.display();
     }
     let input: Landscape = Landscape::new(&read_input_as_vec(17)?);
     println!("Test (1, 2): {:?}", part1_and_2(&test_input));
-    println!("Real (1, 2): {:?}", part1_and_2(&input));
+    let (part1, part2) = part1_and_2(&input);
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
     Ok(())
 }
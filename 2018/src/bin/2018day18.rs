@@ -229,7 +229,7 @@ fn part2(input: &Area) -> usize {
     area.resource_value()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = Area::from_str(TEST_INPUT)?;
     println!("Test 1: {}", part1(&test_input));
     let input = Area::from_str(&read_input(18)?)?;
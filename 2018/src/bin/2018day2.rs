@@ -44,7 +44,7 @@ fn part2(input: &[&str]) -> Result<String> {
     Err("Unable to find two which have a matching single character".into())
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input1 = vec![
         "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
     ];
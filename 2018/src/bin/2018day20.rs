@@ -79,7 +79,7 @@ static TEST_INPUT: &[(&str, usize)] = &[
     ),
 ];
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     for test in TEST_INPUT {
         assert_eq!(test.1, part1(test.0)?);
     }
@@ -314,7 +314,7 @@ fn part2(input: &Input) -> usize {
     cave.spelunk()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = Input::from_input(TEST_INPUT)?;
 
     {
@@ -99,7 +99,7 @@ fn part2(inputs: &[Nanobot]) -> i32 {
     best_end - 1
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Vec<Nanobot> = input_as_vec(TEST_INPUT)?;
     println!("Test 1: {}", part1(&test_input));
     let test_input: Vec<Nanobot> = input_as_vec(TEST_INPUT2)?;
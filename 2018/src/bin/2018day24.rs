@@ -314,6 +314,14 @@ fn dump_armies(input: &[ArmyGroup]) {
     }
 }
 
+/// How many units the winning army has left
+fn part1(input: &[ArmyGroup]) -> Result<usize> {
+    match run_fight(input, 0) {
+        Some((_, units)) => Ok(units),
+        None => Err("Neither army can win".into()),
+    }
+}
+
 fn part2(input: &[ArmyGroup]) -> usize {
     let mut min_boost = 0;
     let mut max_boost = 1_000_000;
@@ -335,7 +343,7 @@ fn part2(input: &[ArmyGroup]) -> usize {
     lastvalue
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = parse_armies(TEST_INPUT)?;
     if cfg!(debug_assertions) {
         dump_armies(&test_input);
@@ -347,7 +355,7 @@ fn main() -> Result<()> {
     if cfg!(debug_assertions) {
         dump_armies(&input);
     }
-    println!("Part 1: {:?}", run_fight(&input, 0));
-    println!("Part 2: {:?}", part2(&input));
+    println!("Part 1: {}", part1(&input)?);
+    println!("Part 2: {}", part2(&input));
     Ok(())
 }
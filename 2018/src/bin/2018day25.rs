@@ -108,7 +108,7 @@ static TESTS: &[(&str, usize)] = &[
     ),
 ];
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     for test in TESTS {
         let test_input: Vec<Point> = input_as_vec(test.0)?;
         assert_eq!(test.1, part1(&test_input));
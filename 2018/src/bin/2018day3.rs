@@ -71,7 +71,7 @@ fn part2(input: &[Claim]) -> Result<usize> {
     Err("Unable to find a non-overlapping claim!".into())
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Result<Vec<Claim>> = vec!["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]
         .iter()
         .map(Claim::try_from)
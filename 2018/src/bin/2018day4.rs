@@ -151,13 +151,15 @@ fn parts(input: &[Entry]) -> Result<(usize, usize)> {
     Ok((part1, part2))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input = sorted_from_str(TEST_INPUT)?;
     let input = sorted_from_str(read_input(4)?)?;
     println!("Loaded {} instructions from test data", test_input.len());
     println!("Loaded {} instructions from real data", input.len());
 
     println!("Test: {:?}", parts(&test_input)?);
-    println!("Real: {:?}", parts(&input)?);
+    let (part1, part2) = parts(&input)?;
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
     Ok(())
 }
@@ -83,7 +83,7 @@ static TESTS: [(&str, &str); 5] = [
 
 static TESTS2: [(char, usize); 4] = [('a', 6), ('b', 8), ('c', 4), ('d', 6)];
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     for (test_input, test_output) in TESTS.iter() {
         assert_eq!(test_output.len(), part1(test_input));
     }
@@ -155,7 +155,7 @@ fn part2(input: &[Point], maxtot: usize) -> usize {
     grid.count_totals_below(maxtot)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Vec<Point> = input_as_vec(
         r#"
 1, 1
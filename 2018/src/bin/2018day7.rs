@@ -151,12 +151,13 @@ fn part2(input: &[Dependency], worker_count: usize, overhead: usize) -> Result<(
     Ok((ret, time_passed))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Vec<Dependency> = input_as_vec(TEST_INPUT)?;
     let input: Vec<Dependency> = read_input_as_vec(7)?;
     println!("Test 1: {}", part1(&test_input)?);
     println!("Part 1: {}", part1(&input)?);
     println!("Test 2: {:?}", part2(&test_input, 2, 0)?);
-    println!("Part 2: {:?}", part2(&input, 5, 60)?);
+    let (_, time) = part2(&input, 5, 60)?;
+    println!("Part 2: {}", time);
     Ok(())
 }
@@ -49,7 +49,7 @@ fn part2(input: &[usize]) -> Result<usize> {
     Ok(tree.value())
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let test_input: Result<Vec<usize>> = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2"
         .split_whitespace()
         .map(|s| Ok(s.parse()?))
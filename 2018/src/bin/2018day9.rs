@@ -97,7 +97,7 @@ fn part1(setup: &Setup) -> usize {
     game.highest_score()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     for &test1 in TEST_VEC.iter() {
         let setup = Setup {
             players: test1.0,
@@ -0,0 +1,454 @@
+//! Elfcode, the register machine of 2018 days 16, 19 and 21
+//!
+//! A [`Program`] is a list of [`Instr`]uctions and, optionally, the register
+//! which its `#ip` line binds the instruction pointer to.  The [`VM`] runs
+//! programs and can stop at breakpoints (on instructions) and watchpoints (on
+//! registers), and count how often each instruction runs.  To work out what
+//! a program actually computes, [`decompile`] it, or [`explain`] a run of it.
+
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use crate::{GenError, Result};
+
+mod decompile;
+
+pub use self::decompile::{blocks, decompile, Block, Decompiled};
+
+/// How many registers the machine has
+pub const REGISTERS: usize = 6;
+
+#[derive(PartialOrd, PartialEq, Ord, Eq, Debug, Copy, Clone, Hash)]
+pub enum Op {
+    AddR,
+    AddI,
+    MulR,
+    MulI,
+    BanR,
+    BanI,
+    BorR,
+    BorI,
+    SetR,
+    SetI,
+    GtIR,
+    GtRI,
+    GtRR,
+    EqIR,
+    EqRI,
+    EqRR,
+}
+
+use self::Op::*;
+
+impl Op {
+    pub const ALL: [Op; 16] = [
+        AddR, AddI, MulR, MulI, BanR, BanI, BorR, BorI, SetR, SetI, GtIR, GtRI, GtRR, EqIR, EqRI,
+        EqRR,
+    ];
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            AddR => "addr",
+            AddI => "addi",
+            MulR => "mulr",
+            MulI => "muli",
+            BanR => "banr",
+            BanI => "bani",
+            BorR => "borr",
+            BorI => "bori",
+            SetR => "setr",
+            SetI => "seti",
+            GtIR => "gtir",
+            GtRI => "gtri",
+            GtRR => "gtrr",
+            EqIR => "eqir",
+            EqRI => "eqri",
+            EqRR => "eqrr",
+        }
+    }
+
+    /// Whether A names a register rather than being a value
+    fn a_is_reg(self) -> bool {
+        !matches!(self, SetI | GtIR | EqIR)
+    }
+
+    /// Whether B names a register (`set*` ignore B entirely)
+    fn b_is_reg(self) -> bool {
+        matches!(self, AddR | MulR | BanR | BorR | GtIR | GtRR | EqIR | EqRR)
+    }
+
+    fn is_comparison(self) -> bool {
+        matches!(self, GtIR | GtRI | GtRR | EqIR | EqRI | EqRR)
+    }
+}
+
+impl FromStr for Op {
+    type Err = GenError;
+
+    fn from_str(s: &str) -> Result<Op> {
+        Op::ALL
+            .iter()
+            .copied()
+            .find(|op| op.mnemonic() == s)
+            .ok_or_else(|| format!("Unknown opcode {:?}", s).into())
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Hash)]
+pub struct Instr {
+    pub op: Op,
+    pub a: i64,
+    pub b: i64,
+    pub c: i64,
+}
+
+impl Instr {
+    /// Whether running this instruction reads the given register
+    pub fn reads(&self, reg: usize) -> bool {
+        let reg = reg as i64;
+        (self.op.a_is_reg() && self.a == reg) || (self.op.b_is_reg() && self.b == reg)
+    }
+}
+
+impl FromStr for Instr {
+    type Err = GenError;
+
+    fn from_str(s: &str) -> Result<Instr> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        if words.len() != 4 {
+            return Err(format!("Bad instruction {:?}", s).into());
+        }
+        Ok(Instr {
+            op: words[0].parse()?,
+            a: words[1].parse()?,
+            b: words[2].parse()?,
+            c: words[3].parse()?,
+        })
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op.mnemonic(), self.a, self.b, self.c)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Program {
+    /// The register bound to the instruction pointer by `#ip`, if any
+    pub ip: Option<usize>,
+    pub instrs: Vec<Instr>,
+}
+
+impl FromStr for Program {
+    type Err = GenError;
+
+    fn from_str(s: &str) -> Result<Program> {
+        let mut program = Program::default();
+        for line in s.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Some(ip) = line.strip_prefix("#ip ") {
+                let ip = ip.parse()?;
+                if ip >= REGISTERS {
+                    return Err(format!("#ip {} is out of range", ip).into());
+                }
+                program.ip = Some(ip);
+            } else {
+                program.instrs.push(line.parse()?);
+            }
+        }
+        Ok(program)
+    }
+}
+
+/// Why [`VM::run`] stopped
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stop {
+    /// The instruction pointer left the program
+    Halted,
+    /// The next instruction to run is at a breakpoint
+    Breakpoint(usize),
+    /// The last instruction changed a watched register
+    Watchpoint { reg: usize, old: i64, new: i64 },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VM {
+    program: Program,
+    pub regs: [i64; REGISTERS],
+    ip: i64,
+    steps: u64,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+    /// How many times each instruction has run, if we're counting
+    profile: Option<Vec<u64>>,
+}
+
+impl VM {
+    pub fn new(program: Program) -> VM {
+        VM {
+            program,
+            ..VM::default()
+        }
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn ip(&self) -> i64 {
+        self.ip
+    }
+
+    /// How many instructions have been run
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Stop before running the instruction at `pc`
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.remove(&pc);
+    }
+
+    /// Stop whenever `reg` changes
+    pub fn watch(&mut self, reg: usize) -> Result<()> {
+        if reg >= REGISTERS {
+            return Err(format!("There is no register {} to watch", reg).into());
+        }
+        self.watchpoints.insert(reg);
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, reg: usize) {
+        self.watchpoints.remove(&reg);
+    }
+
+    /// Start counting how many times each instruction runs
+    pub fn enable_profiling(&mut self) {
+        self.profile = Some(vec![0; self.program.instrs.len()]);
+    }
+
+    pub fn profile(&self) -> Option<&[u64]> {
+        self.profile.as_deref()
+    }
+
+    /// The `n` most run instructions, most run first
+    pub fn hot_spots(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut counts: Vec<_> = self
+            .profile()
+            .unwrap_or(&[])
+            .iter()
+            .copied()
+            .enumerate()
+            .collect();
+        counts.sort_by_key(|&(pc, count)| (std::cmp::Reverse(count), pc));
+        counts.truncate(n);
+        counts
+    }
+
+    fn reg(&self, which: char, reg: i64) -> Result<i64> {
+        usize::try_from(reg)
+            .ok()
+            .and_then(|reg| self.regs.get(reg))
+            .copied()
+            .ok_or_else(|| format!("{} ({}) is out of range", which, reg).into())
+    }
+
+    /// Run one instruction on the registers, leaving the instruction pointer
+    /// alone
+    pub fn exec(&mut self, instr: Instr) -> Result<()> {
+        let Instr { op, a, b, c } = instr;
+        let c = usize::try_from(c)
+            .ok()
+            .filter(|&c| c < REGISTERS)
+            .ok_or_else(|| format!("C ({}) is out of range", c))?;
+        self.regs[c] = match op {
+            AddR => self.reg('A', a)?.wrapping_add(self.reg('B', b)?),
+            AddI => self.reg('A', a)?.wrapping_add(b),
+            MulR => self.reg('A', a)?.wrapping_mul(self.reg('B', b)?),
+            MulI => self.reg('A', a)?.wrapping_mul(b),
+            BanR => self.reg('A', a)? & self.reg('B', b)?,
+            BanI => self.reg('A', a)? & b,
+            BorR => self.reg('A', a)? | self.reg('B', b)?,
+            BorI => self.reg('A', a)? | b,
+            SetR => self.reg('A', a)?,
+            SetI => a,
+            GtIR => (a > self.reg('B', b)?) as i64,
+            GtRI => (self.reg('A', a)? > b) as i64,
+            GtRR => (self.reg('A', a)? > self.reg('B', b)?) as i64,
+            EqIR => (a == self.reg('B', b)?) as i64,
+            EqRI => (self.reg('A', a)? == b) as i64,
+            EqRR => (self.reg('A', a)? == self.reg('B', b)?) as i64,
+        };
+        Ok(())
+    }
+
+    /// The instruction pointer, if it's still inside the program
+    fn pc(&self) -> Option<usize> {
+        usize::try_from(self.ip)
+            .ok()
+            .filter(|&pc| pc < self.program.instrs.len())
+    }
+
+    /// Run the next instruction, returning false if the program has halted
+    pub fn step(&mut self) -> Result<bool> {
+        let pc = match self.pc() {
+            Some(pc) => pc,
+            None => return Ok(false),
+        };
+        if let Some(profile) = &mut self.profile {
+            profile[pc] += 1;
+        }
+        // The bound register holds the instruction pointer while the
+        // instruction runs, and whatever it holds afterwards is where we are
+        if let Some(ip) = self.program.ip {
+            self.regs[ip] = self.ip;
+        }
+        self.exec(self.program.instrs[pc])?;
+        if let Some(ip) = self.program.ip {
+            self.ip = self.regs[ip];
+        }
+        self.ip += 1;
+        self.steps += 1;
+        Ok(true)
+    }
+
+    /// Run until the program halts or something we're watching for happens.
+    /// At least one instruction is always run, so running again after
+    /// stopping at a breakpoint carries on from it.
+    pub fn run(&mut self) -> Result<Stop> {
+        loop {
+            let before = self.regs;
+            if !self.step()? {
+                return Ok(Stop::Halted);
+            }
+            if let Some(&reg) = self
+                .watchpoints
+                .iter()
+                .find(|&&reg| before[reg] != self.regs[reg])
+            {
+                return Ok(Stop::Watchpoint {
+                    reg,
+                    old: before[reg],
+                    new: self.regs[reg],
+                });
+            }
+            if let Some(pc) = self.pc().filter(|pc| self.breakpoints.contains(pc)) {
+                return Ok(Stop::Breakpoint(pc));
+            }
+        }
+    }
+}
+
+/// What `aoc explain` shows: the decompiled program, followed by where `vm`
+/// spent its time if it has been profiling
+pub fn explain(vm: &VM) -> String {
+    let mut out = decompile(vm.program()).to_string();
+    let hot_spots: Vec<_> = vm
+        .hot_spots(5)
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    if !hot_spots.is_empty() {
+        out.push_str("\nMost run instructions:\n");
+        for (pc, count) in hot_spots {
+            let instr = vm.program.instrs[pc];
+            writeln!(out, "{:>4}: {:<16} {} times", pc, instr.to_string(), count).unwrap();
+        }
+    }
+    out
+}
+
+impl FromStr for VM {
+    type Err = GenError;
+
+    fn from_str(s: &str) -> Result<VM> {
+        Ok(VM::new(s.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The example from day 19
+    const EXAMPLE: &str = r"
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+";
+
+    #[test]
+    fn running() {
+        let mut vm: VM = EXAMPLE.parse().unwrap();
+        assert_eq!(vm.run().unwrap(), Stop::Halted);
+        assert_eq!(vm.regs, [6, 5, 6, 0, 0, 9]);
+        assert_eq!(vm.steps(), 5);
+        assert_eq!(vm.program().instrs[3].to_string(), "addr 1 2 3");
+        assert!("#ip 6\n".parse::<Program>().is_err());
+        assert!("addr 1 2\n".parse::<Program>().is_err());
+
+        // The sample from day 16, where three opcodes could be `9`
+        let matches: Vec<Op> = Op::ALL
+            .iter()
+            .copied()
+            .filter(|&op| {
+                let mut vm = VM::default();
+                vm.regs[..4].copy_from_slice(&[3, 2, 1, 1]);
+                vm.exec(Instr {
+                    op,
+                    a: 2,
+                    b: 1,
+                    c: 2,
+                })
+                .unwrap();
+                vm.regs[..4] == [3, 2, 2, 1]
+            })
+            .collect();
+        assert_eq!(matches, vec![AddI, MulR, SetI]);
+        assert!(VM::default().exec("addr 9 0 1".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn stopping() {
+        let mut vm: VM = EXAMPLE.parse().unwrap();
+        vm.add_breakpoint(6);
+        assert!(vm.watch(REGISTERS).is_err());
+        vm.watch(1).unwrap();
+        assert_eq!(
+            vm.run().unwrap(),
+            Stop::Watchpoint {
+                reg: 1,
+                old: 0,
+                new: 5
+            }
+        );
+        vm.unwatch(1);
+        assert_eq!(vm.run().unwrap(), Stop::Breakpoint(6));
+        assert_eq!(vm.regs[1], 5);
+        assert_eq!(vm.run().unwrap(), Stop::Halted);
+    }
+
+    #[test]
+    fn profiling() {
+        let mut vm: VM = EXAMPLE.parse().unwrap();
+        vm.enable_profiling();
+        vm.run().unwrap();
+        assert_eq!(vm.profile().unwrap(), &[1, 1, 1, 0, 1, 0, 1]);
+        assert_eq!(vm.hot_spots(2), vec![(0, 1), (1, 1)]);
+        let explained = explain(&vm);
+        assert!(explained.starts_with(&decompile(vm.program()).to_string()));
+        assert!(explained.ends_with("   6: seti 9 0 5       1 times\n"));
+        assert!(!explained.contains("   3: addr"));
+    }
+}
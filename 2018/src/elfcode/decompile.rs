@@ -0,0 +1,685 @@
+//! Lifting Elfcode into something readable
+//!
+//! Each instruction becomes an assignment, except that writes to the bound
+//! instruction pointer become jumps.  Constant jumps are easy, and the idiom
+//!
+//! ```text
+//! eqrr 4 1 4     (or any other comparison)
+//! addr 4 2 2     (where 2 is the instruction pointer)
+//! ```
+//!
+//! is a conditional skip over the next instruction.  The comparison's result
+//! is dropped if nothing reads it afterwards.  Backward jumps then make loops
+//! and forward conditional jumps make `if`s, leaving `goto` for whatever
+//! doesn't fit.  Every line keeps the address it came from, so the output
+//! can be lined up with the program (and breakpoints set from it).
+
+use std::collections::{BTreeSet, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::{Instr, Op, Program};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Reg(usize),
+    Const(i64),
+    Bin(BinOp, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Mul,
+    And,
+    Or,
+    Gt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl BinOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Mul => "*",
+            BinOp::And => "&",
+            BinOp::Or => "|",
+            BinOp::Gt => ">",
+            BinOp::Le => "<=",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+        }
+    }
+}
+
+impl Expr {
+    fn bin(op: BinOp, lhs: Expr, rhs: Expr) -> Expr {
+        use self::Expr::Const;
+        match (op, lhs, rhs) {
+            (_, Const(a), Const(b)) => Const(match op {
+                BinOp::Add => a.wrapping_add(b),
+                BinOp::Mul => a.wrapping_mul(b),
+                BinOp::And => a & b,
+                BinOp::Or => a | b,
+                BinOp::Gt => (a > b) as i64,
+                BinOp::Le => (a <= b) as i64,
+                BinOp::Eq => (a == b) as i64,
+                BinOp::Ne => (a != b) as i64,
+            }),
+            // Keep the sums which jumps turn into down to a single constant
+            (BinOp::Add, Expr::Bin(BinOp::Add, x, y), Const(b)) => match (*x, *y) {
+                (Const(a), y) | (y, Const(a)) => Expr::bin(BinOp::Add, Const(a + b), y),
+                (x, y) => Expr::Bin(
+                    BinOp::Add,
+                    Box::new(Expr::Bin(BinOp::Add, Box::new(x), Box::new(y))),
+                    Box::new(Const(b)),
+                ),
+            },
+            (op, lhs, rhs) => Expr::Bin(op, Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    fn negate(self) -> Expr {
+        match self {
+            Expr::Const(v) => Expr::Const((v == 0) as i64),
+            Expr::Bin(BinOp::Gt, a, b) => Expr::Bin(BinOp::Le, a, b),
+            Expr::Bin(BinOp::Le, a, b) => Expr::Bin(BinOp::Gt, a, b),
+            Expr::Bin(BinOp::Eq, a, b) => Expr::Bin(BinOp::Ne, a, b),
+            Expr::Bin(BinOp::Ne, a, b) => Expr::Bin(BinOp::Eq, a, b),
+            e => Expr::Bin(BinOp::Eq, Box::new(e), Box::new(Expr::Const(0))),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn operand(f: &mut fmt::Formatter<'_>, e: &Expr) -> fmt::Result {
+            match e {
+                Expr::Bin(..) => write!(f, "({})", e),
+                _ => write!(f, "{}", e),
+            }
+        }
+        match self {
+            Expr::Reg(r) => write!(f, "r{}", r),
+            Expr::Const(v) => write!(f, "{}", v),
+            Expr::Bin(op, a, b) => {
+                operand(f, a)?;
+                write!(f, " {} ", op.symbol())?;
+                operand(f, b)
+            }
+        }
+    }
+}
+
+/// Where control goes after each instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Flow {
+    Next,
+    /// To a known address, which may be outside the program
+    Jump(usize),
+    /// Skip the next instruction if the comparison just before was true
+    Skip,
+    /// Anywhere at all
+    Computed(Expr),
+}
+
+#[derive(Debug, Clone)]
+enum Stmt {
+    Set(usize, Expr),
+    Jump(usize),
+    /// Jump if the condition is true, otherwise carry on
+    Branch(Expr, usize),
+    Computed(Expr),
+}
+
+struct Lifter<'a> {
+    program: &'a Program,
+    flows: Vec<Flow>,
+}
+
+impl<'a> Lifter<'a> {
+    fn new(program: &'a Program) -> Lifter<'a> {
+        let mut lifter = Lifter {
+            program,
+            flows: Vec::new(),
+        };
+        lifter.flows = (0..program.instrs.len())
+            .map(|pc| lifter.flow(pc))
+            .collect();
+        // A skip which is itself jumped to can't be read along with the
+        // comparison before it
+        let targets: HashSet<usize> = lifter
+            .flows
+            .iter()
+            .filter_map(|flow| match flow {
+                Flow::Jump(target) => Some(*target),
+                _ => None,
+            })
+            .collect();
+        for pc in 0..lifter.flows.len() {
+            if lifter.flows[pc] == Flow::Skip && targets.contains(&pc) {
+                let instr = program.instrs[pc];
+                let next = Expr::bin(BinOp::Add, lifter.value(pc, instr), Expr::Const(1));
+                lifter.flows[pc] = Flow::Computed(next);
+            }
+        }
+        lifter
+    }
+
+    fn len(&self) -> usize {
+        self.program.instrs.len()
+    }
+
+    fn is_ip(&self, reg: i64) -> bool {
+        self.program.ip.map(|ip| ip as i64) == Some(reg)
+    }
+
+    /// What reading register `reg` gives while running the instruction at `pc`
+    fn read(&self, pc: usize, reg: i64) -> Expr {
+        if self.is_ip(reg) {
+            Expr::Const(pc as i64)
+        } else {
+            Expr::Reg(reg as usize)
+        }
+    }
+
+    /// The value the instruction at `pc` writes
+    fn value(&self, pc: usize, instr: Instr) -> Expr {
+        use super::Op::*;
+        let a = if instr.op.a_is_reg() {
+            self.read(pc, instr.a)
+        } else {
+            Expr::Const(instr.a)
+        };
+        let b = if instr.op.b_is_reg() {
+            self.read(pc, instr.b)
+        } else {
+            Expr::Const(instr.b)
+        };
+        match instr.op {
+            AddR | AddI => Expr::bin(BinOp::Add, a, b),
+            MulR | MulI => Expr::bin(BinOp::Mul, a, b),
+            BanR | BanI => Expr::bin(BinOp::And, a, b),
+            BorR | BorI => Expr::bin(BinOp::Or, a, b),
+            SetR | SetI => a,
+            GtIR | GtRI | GtRR => Expr::bin(BinOp::Gt, a, b),
+            EqIR | EqRI | EqRR => Expr::bin(BinOp::Eq, a, b),
+        }
+    }
+
+    /// The comparison just before `pc` which it could be skipping on
+    fn skip_flag(&self, pc: usize) -> Option<usize> {
+        let instr = self.program.instrs[pc];
+        let before = self.program.instrs.get(pc.checked_sub(1)?)?;
+        let flag = if instr.op == Op::AddR && self.is_ip(instr.a) {
+            instr.b
+        } else if instr.op == Op::AddR && self.is_ip(instr.b) {
+            instr.a
+        } else {
+            return None;
+        };
+        (before.op.is_comparison() && before.c == flag && !self.is_ip(flag))
+            .then_some(flag as usize)
+    }
+
+    fn flow(&self, pc: usize) -> Flow {
+        let instr = self.program.instrs[pc];
+        if !self.is_ip(instr.c) {
+            return Flow::Next;
+        }
+        if self.skip_flag(pc).is_some() {
+            return Flow::Skip;
+        }
+        match Expr::bin(BinOp::Add, self.value(pc, instr), Expr::Const(1)) {
+            // Negative addresses are as far outside the program as any
+            Expr::Const(target) => Flow::Jump(usize::try_from(target).unwrap_or(usize::MAX)),
+            next => Flow::Computed(next),
+        }
+    }
+
+    /// Where control can go after `pc`, if we know
+    fn successors(&self, pc: usize) -> Option<Vec<usize>> {
+        match &self.flows[pc] {
+            Flow::Next => Some(vec![pc + 1]),
+            Flow::Jump(target) => Some(vec![*target]),
+            Flow::Skip => Some(vec![pc + 1, pc + 2]),
+            Flow::Computed(_) => None,
+        }
+    }
+
+    /// Whether anything might read `reg` after control reaches any of `from`
+    fn is_live(&self, from: &[usize], reg: usize) -> bool {
+        let mut seen = HashSet::new();
+        let mut todo = from.to_vec();
+        while let Some(pc) = todo.pop() {
+            if pc >= self.len() || !seen.insert(pc) {
+                continue;
+            }
+            let instr = self.program.instrs[pc];
+            if instr.reads(reg) {
+                return true;
+            }
+            if instr.c == reg as i64 {
+                continue;
+            }
+            match self.successors(pc) {
+                Some(next) => todo.extend(next),
+                None => return true,
+            }
+        }
+        false
+    }
+
+    /// The program as statements, each with the address it came from
+    fn lift(&self) -> Vec<(usize, Stmt)> {
+        let mut stmts = Vec::new();
+        for (pc, instr) in self.program.instrs.iter().copied().enumerate() {
+            match &self.flows[pc] {
+                Flow::Next => stmts.push((pc, Stmt::Set(instr.c as usize, self.value(pc, instr)))),
+                Flow::Jump(target) => stmts.push((pc, Stmt::Jump(*target))),
+                Flow::Computed(next) => stmts.push((pc, Stmt::Computed(next.clone()))),
+                Flow::Skip => {
+                    // Which replaces the comparison just lifted
+                    let flag = self.skip_flag(pc).expect("Skips follow comparisons");
+                    if self.is_live(&[pc + 1, pc + 2], flag) {
+                        stmts.push((pc, Stmt::Branch(Expr::Reg(flag), pc + 2)));
+                    } else {
+                        let (at, cmp) = stmts.pop().expect("Skips follow comparisons");
+                        let cmp = match cmp {
+                            Stmt::Set(_, cmp) => cmp,
+                            _ => unreachable!(),
+                        };
+                        stmts.push((at, Stmt::Branch(cmp, pc + 2)));
+                    }
+                }
+            }
+        }
+        stmts
+    }
+}
+
+/// A straight run of instructions `start..end`, entered only at `start`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub start: usize,
+    pub end: usize,
+    /// Where control can go afterwards, with addresses past the end of the
+    /// program meaning it halts.  Empty if the block ends in a computed jump.
+    pub exits: Vec<usize>,
+}
+
+/// Split a program into its basic blocks
+pub fn blocks(program: &Program) -> Vec<Block> {
+    let lifter = Lifter::new(program);
+    let len = lifter.len();
+    let mut leaders = BTreeSet::new();
+    leaders.insert(0);
+    for pc in 0..len {
+        if lifter.flows[pc] != Flow::Next {
+            leaders.insert(pc + 1);
+            leaders.extend(lifter.successors(pc).unwrap_or_default());
+        }
+    }
+    let leaders: Vec<usize> = leaders.into_iter().filter(|&pc| pc < len).collect();
+    leaders
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = leaders.get(n + 1).copied().unwrap_or(len);
+            Block {
+                start,
+                end,
+                exits: lifter.successors(end - 1).unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Set(usize, usize, Expr),
+    Computed(usize, Expr),
+    Halt(usize),
+    Break(usize),
+    Continue(usize),
+    Goto(usize, usize),
+    If {
+        pc: usize,
+        cond: Expr,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    /// `do { .. } while cond` if there is a condition
+    Loop {
+        pc: usize,
+        body: Vec<Node>,
+        cond: Option<(usize, Expr)>,
+    },
+}
+
+/// The loop being structured, and so what `break` and `continue` mean
+#[derive(Debug, Copy, Clone)]
+struct Enclosing {
+    head: usize,
+    exit: usize,
+}
+
+struct Structurer {
+    stmts: Vec<(usize, Stmt)>,
+    len: usize,
+    /// `(from, to)` indices into `stmts` of jumps which go backwards
+    back_edges: Vec<(usize, usize)>,
+}
+
+impl Structurer {
+    fn new(program: &Program) -> Structurer {
+        let mut structurer = Structurer {
+            stmts: Lifter::new(program).lift(),
+            len: program.instrs.len(),
+            back_edges: Vec::new(),
+        };
+        structurer.back_edges = (0..structurer.stmts.len())
+            .filter_map(|from| {
+                let target = structurer.target(from)?;
+                let to = structurer.index_of(target);
+                (target < structurer.len && to <= from).then_some((from, to))
+            })
+            .collect();
+        structurer
+    }
+
+    fn target(&self, index: usize) -> Option<usize> {
+        match self.stmts[index].1 {
+            Stmt::Jump(target) | Stmt::Branch(_, target) => Some(target),
+            _ => None,
+        }
+    }
+
+    /// The statement lifted from the instruction at `pc`
+    fn index_of(&self, pc: usize) -> usize {
+        self.stmts.partition_point(|&(at, _)| at < pc)
+    }
+
+    /// The address after the statement at `index`
+    fn pc_after(&self, index: usize) -> usize {
+        self.stmts.get(index + 1).map_or(self.len, |&(pc, _)| pc)
+    }
+
+    fn jump(&self, pc: usize, target: usize, enclosing: Option<Enclosing>) -> Node {
+        match enclosing {
+            _ if target >= self.len => Node::Halt(pc),
+            Some(e) if e.exit == target => Node::Break(pc),
+            Some(e) if e.head == target => Node::Continue(pc),
+            _ => Node::Goto(pc, target),
+        }
+    }
+
+    /// Structure `stmts[lo..hi]`, where `looping` is the index of a loop
+    /// header which we're already structuring the body of
+    fn structure(
+        &self,
+        lo: usize,
+        hi: usize,
+        enclosing: Option<Enclosing>,
+        looping: Option<usize>,
+    ) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut i = lo;
+        while i < hi {
+            let pc = self.stmts[i].0;
+            let latch = self
+                .back_edges
+                .iter()
+                .filter(|&&(from, to)| to == i && from < hi)
+                .map(|&(from, _)| from)
+                .max();
+            if let Some(latch) = latch.filter(|_| looping != Some(i)) {
+                let inner = Enclosing {
+                    head: pc,
+                    exit: self.pc_after(latch),
+                };
+                let body = self.structure(i, latch, Some(inner), Some(i));
+                let cond = match &self.stmts[latch] {
+                    (at, Stmt::Branch(cond, _)) => Some((*at, cond.clone())),
+                    _ => None,
+                };
+                nodes.push(Node::Loop { pc, body, cond });
+                i = latch + 1;
+                continue;
+            }
+            match &self.stmts[i].1 {
+                Stmt::Set(reg, value) => nodes.push(Node::Set(pc, *reg, value.clone())),
+                Stmt::Computed(next) => nodes.push(Node::Computed(pc, next.clone())),
+                Stmt::Jump(target) => nodes.push(self.jump(pc, *target, enclosing)),
+                Stmt::Branch(cond, target) => {
+                    let to = self.index_of(*target);
+                    if *target < self.len && to > i && to <= hi {
+                        // Skipping forwards over some code, which may end by
+                        // jumping over an `else`
+                        let mut then_end = to;
+                        let mut next = to;
+                        let mut otherwise = Vec::new();
+                        if to > i + 1 {
+                            if let Stmt::Jump(over) = self.stmts[to - 1].1 {
+                                let past = self.index_of(over);
+                                if over < self.len && past > to && past <= hi {
+                                    then_end = to - 1;
+                                    otherwise = self.structure(to, past, enclosing, None);
+                                    next = past;
+                                }
+                            }
+                        }
+                        let then = self.structure(i + 1, then_end, enclosing, None);
+                        nodes.push(if then.is_empty() {
+                            Node::If {
+                                pc,
+                                cond: cond.clone(),
+                                then: otherwise,
+                                otherwise: Vec::new(),
+                            }
+                        } else {
+                            Node::If {
+                                pc,
+                                cond: cond.clone().negate(),
+                                then,
+                                otherwise,
+                            }
+                        });
+                        i = next;
+                        continue;
+                    }
+                    nodes.push(Node::If {
+                        pc,
+                        cond: cond.clone(),
+                        then: vec![self.jump(pc, *target, enclosing)],
+                        otherwise: Vec::new(),
+                    });
+                }
+            }
+            i += 1;
+        }
+        nodes
+    }
+}
+
+/// A program lifted into structured pseudo-code, ready to print
+#[derive(Debug, Clone)]
+pub struct Decompiled {
+    nodes: Vec<Node>,
+    width: usize,
+}
+
+pub fn decompile(program: &Program) -> Decompiled {
+    let structurer = Structurer::new(program);
+    Decompiled {
+        nodes: structurer.structure(0, structurer.stmts.len(), None, None),
+        width: program.instrs.len().max(1).to_string().len(),
+    }
+}
+
+impl Decompiled {
+    fn write(&self, f: &mut fmt::Formatter<'_>, nodes: &[Node], depth: usize) -> fmt::Result {
+        let line = |f: &mut fmt::Formatter<'_>, pc: Option<usize>, text: String| {
+            match pc {
+                Some(pc) => write!(f, "{:>w$}: ", pc, w = self.width)?,
+                None => write!(f, "{:w$}  ", "", w = self.width)?,
+            }
+            writeln!(f, "{:indent$}{}", "", text, indent = depth * 4)
+        };
+        for node in nodes {
+            match node {
+                Node::Set(pc, reg, value) => line(f, Some(*pc), format!("r{} = {}", reg, value))?,
+                Node::Computed(pc, next) => line(f, Some(*pc), format!("goto {}", next))?,
+                Node::Halt(pc) => line(f, Some(*pc), "halt".into())?,
+                Node::Break(pc) => line(f, Some(*pc), "break".into())?,
+                Node::Continue(pc) => line(f, Some(*pc), "continue".into())?,
+                Node::Goto(pc, target) => line(f, Some(*pc), format!("goto {}", target))?,
+                Node::If {
+                    pc,
+                    cond,
+                    then,
+                    otherwise,
+                } => {
+                    line(f, Some(*pc), format!("if {} {{", cond))?;
+                    self.write(f, then, depth + 1)?;
+                    if !otherwise.is_empty() {
+                        line(f, None, "} else {".into())?;
+                        self.write(f, otherwise, depth + 1)?;
+                    }
+                    line(f, None, "}".into())?;
+                }
+                Node::Loop { pc, body, cond } => {
+                    match cond {
+                        Some(_) => line(f, Some(*pc), "do {".into())?,
+                        None => line(f, Some(*pc), "loop {".into())?,
+                    }
+                    self.write(f, body, depth + 1)?;
+                    match cond {
+                        Some((at, cond)) => line(f, Some(*at), format!("}} while {}", cond))?,
+                        None => line(f, None, "}".into())?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Decompiled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &self.nodes, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The start of day 21's program, which checks that `bani` works
+    const BANI_CHECK: &str = r"
+#ip 1
+seti 123 0 3
+bani 3 456 3
+eqri 3 72 3
+addr 3 1 1
+seti 0 0 1
+seti 0 9 3
+";
+
+    /// Adds up the factors of r1, the heart of day 19's program
+    const FACTORS: &str = r"
+#ip 2
+seti 1 1 5
+seti 1 1 3
+mulr 5 3 4
+eqrr 4 1 4
+addr 4 2 2
+addi 2 1 2
+addr 5 0 0
+addi 3 1 3
+gtrr 3 1 4
+addr 2 4 2
+seti 1 8 2
+addi 5 1 5
+gtrr 5 1 4
+addr 4 2 2
+seti 0 5 2
+mulr 2 2 2
+";
+
+    fn program(text: &str) -> Program {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn basic_blocks() {
+        let blocks = blocks(&program(BANI_CHECK));
+        let spans: Vec<_> = blocks
+            .iter()
+            .map(|b| (b.start, b.end, b.exits.clone()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 1, vec![1]),
+                (1, 4, vec![4, 5]),
+                (4, 5, vec![1]),
+                (5, 6, vec![6])
+            ]
+        );
+    }
+
+    #[test]
+    fn loops_and_conditions() {
+        // The comparison is kept, because the loop reads r3 again
+        assert_eq!(
+            decompile(&program(BANI_CHECK)).to_string(),
+            "\
+0: r3 = 123
+1: loop {
+1:     r3 = r3 & 456
+2:     r3 = r3 == 72
+3:     if r3 {
+3:         break
+       }
+   }
+5: r3 = 0
+"
+        );
+        assert_eq!(
+            decompile(&program(FACTORS)).to_string(),
+            " \
+0: r5 = 1
+ 1: loop {
+ 1:     r3 = 1
+ 2:     loop {
+ 2:         r4 = r5 * r3
+ 3:         if r4 == r1 {
+ 6:             r0 = r5 + r0
+            }
+ 7:         r3 = r3 + 1
+ 8:         if r3 > r1 {
+ 8:             break
+            }
+        }
+11:     r5 = r5 + 1
+12:     if r5 > r1 {
+12:         break
+        }
+    }
+15: halt
+"
+        );
+    }
+
+    #[test]
+    fn computed_jumps() {
+        let decompiled = decompile(&program("#ip 0\naddr 0 1 0\nseti 0 0 2\nseti 99 0 0\n"));
+        assert_eq!(
+            decompiled.to_string(),
+            "0: goto 1 + r1\n1: r2 = 0\n2: halt\n"
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use std::fs::read_to_string;
+// The days are compiled into this crate too, see `days` below
+extern crate self as aoc2018;
 
-pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+pub use aoc_common::prelude::*;
 
 pub use lazy_static::lazy_static;
 pub use regex::Regex;
@@ -21,45 +22,15 @@ pub use std::collections::VecDeque;
 
 pub use twoway;
 
-pub fn read_input(day: usize) -> Result<String> {
-    Ok(read_to_string(format!("inputs/day{}", day))?)
-}
+/// Days which exist, but which running the whole year should leave alone
+pub const SKIPPED_DAYS: &[(u8, &str)] = &[];
 
-pub fn read_input_as_vec<T: ParseByRegex>(day: usize) -> Result<Vec<T>> {
-    let plain = read_input(day)?;
-    Ok(input_as_vec(plain)?)
+/// Every day of 2018, generated from `src/bin` by `build.rs`
+#[cfg(not(test))]
+pub mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
 }
 
-pub fn input_as_vec<T: ParseByRegex, S: AsRef<str>>(plain: S) -> Result<Vec<T>> {
-    let mapped: Result<Vec<T>> = plain
-        .as_ref()
-        .trim()
-        .lines()
-        .map(ParseByRegex::parse_by_regex)
-        .collect();
-    Ok(mapped?)
-}
+// 2018 specific stuff
 
-pub fn input_as_vec_and_first<T: ParseByRegex, S: AsRef<str>>(
-    plain: S,
-) -> Result<(Vec<T>, String)> {
-    let mut lines = plain.as_ref().trim().lines();
-    let first = lines.next();
-    let mapped: Result<Vec<T>> = lines
-        .filter(|s| !s.trim().is_empty()) // In case there are any blanks after the first line
-        .map(ParseByRegex::parse_by_regex)
-        .collect();
-    if first.is_none() {
-        Err("No lines at all?".into())
-    } else {
-        Ok((
-            mapped?,
-            first.expect("Something went wrong").trim().to_owned(),
-        ))
-    }
-}
-
-pub fn read_input_as_vec_and_first<T: ParseByRegex>(day: usize) -> Result<(Vec<T>, String)> {
-    let plain = read_input(day)?;
-    Ok(input_as_vec_and_first(plain)?)
-}
+pub mod elfcode;
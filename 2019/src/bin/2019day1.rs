@@ -41,7 +41,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Result<Vec<usize>> = read_input_as_vec(1);
     let input = input?;
     println!("Part 1: {}", part1(&input));
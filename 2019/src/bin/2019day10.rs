@@ -86,7 +86,8 @@ impl AsteroidField {
     }
 }
 
-fn part1(input: &AsteroidField) -> ((i32, i32), usize) {
+/// Where the station should go, and how many asteroids it can see from there
+fn best_station(input: &AsteroidField) -> ((i32, i32), usize) {
     let mut counts: Vec<_> = input.seeing_counts().into_iter().collect();
     counts.sort_by_cached_key(|(_, n)| *n);
     counts[counts.len() - 1]
@@ -183,7 +184,7 @@ mod test {
             let input = input.replace(' ', "");
             let input = AsteroidField::parse_string(&input).expect("Unable to parse");
             eprintln!("{:?}", input);
-            let ((x_, y_), count_) = part1(&input);
+            let ((x_, y_), count_) = best_station(&input);
             assert_eq!(*x, x_);
             assert_eq!(*y, y_);
             assert_eq!(*count, count_);
@@ -234,15 +235,19 @@ mod test {
     }
 }
 
-fn part2(input: &AsteroidField, roid: (i32, i32)) -> (i32, i32) {
-    let order = input.vaporisation_order(roid);
-    order[199]
+fn part1(input: &AsteroidField) -> usize {
+    best_station(input).1
 }
 
-fn main() -> Result<()> {
+fn part2(input: &AsteroidField) -> i32 {
+    let (station, _) = best_station(input);
+    let (x, y) = input.vaporisation_order(station)[199];
+    x * 100 + y
+}
+
+pub fn main() -> Result<()> {
     let input = AsteroidField::parse_string(&read_input(10)?)?;
-    let p1 = part1(&input);
-    println!("Part 1: {:?}", p1);
-    println!("Part 2: {:?}", part2(&input, p1.0));
+    println!("Part 1: {}", part1(&input));
+    println!("Part 2: {}", part2(&input));
     Ok(())
 }
@@ -103,14 +103,15 @@ fn part2(input: &intcode::VM) -> Result<String> {
     let mut bot = PaintingBot::new(input.clone());
     bot.paint_position(0, 0, true);
     bot.run_to_completion()?;
-    Ok(bot.display_map().join("\n"))
+    let hull = bot.display_map().join("\n");
+    Ok(ocr::read_grid(&hull).unwrap_or(hull))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(11)?;
     let input = input.trim();
     let input = intcode::VM::from_str(input)?;
     println!("Part 1: {}", part1(&input)?);
-    println!("Part 2:\n{}", part2(&input)?);
+    println!("Part 2: {}", part2(&input)?);
     Ok(())
 }
@@ -136,7 +136,7 @@ fn part2(input: &[Moon]) -> usize {
     lcm(lcm(xcount, ycount), zcount)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Position> = read_input_as_vec(12)?;
     let input: Vec<Moon> = input.into_iter().map(Moon::new).collect();
 
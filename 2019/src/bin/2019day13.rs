@@ -61,8 +61,8 @@ fn part2(input: &intcode::VM) -> Result<i64> {
             WaitingOnInput => {
                 let joystick = match padx.cmp(&ballx) {
                     Ordering::Equal => 0,
-                    Ordering::Less => -1,
-                    Ordering::Greater => 1,
+                    Ordering::Less => 1,
+                    Ordering::Greater => -1,
                 };
                 next_input = Some(joystick);
             }
@@ -71,7 +71,7 @@ fn part2(input: &intcode::VM) -> Result<i64> {
     Ok(score)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(13)?;
     let input = intcode::VM::from_str(&input)?;
 
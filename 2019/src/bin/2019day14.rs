@@ -218,7 +218,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(14)?;
     let input = load_data(&input)?;
     println!("Part 1: {}", part1(&input)?);
@@ -118,8 +118,8 @@ impl RepairBot {
         Ok(kind)
     }
 
-    fn explore(&mut self, find_all: bool) -> Result<usize> {
-        // We will explore until we find the oxygen generator
+    fn explore(&mut self) -> Result<()> {
+        // We will explore until we've been everywhere
         let mut chain = Vec::new();
         let mut curdir = Direction::North;
         loop {
@@ -132,7 +132,7 @@ impl RepairBot {
                 if chain.is_empty() && curdir == Direction::North {
                     // We've made it back to the start and the starting direction
                     // so we're done exploring
-                    break Ok(0);
+                    break Ok(());
                 }
                 if !chain.is_empty() && curdir == chain[chain.len() - 1] {
                     // We're backing up
@@ -161,16 +161,11 @@ impl RepairBot {
                     }
                 }
                 CellKind::Oxygen => {
-                    // We've found the oxygen.  We know we *can* reach it
-                    // by the route chain we have
+                    // We've found the oxygen, but treat it as empty so
+                    // we carry on exploring
                     self.oxygen = self.pos;
-                    if !find_all {
-                        break Ok(chain.len() + 1);
-                    } else {
-                        // Treat this as empty
-                        chain.push(curdir.backwards());
-                        curdir = curdir.backwards().next_direction();
-                    }
+                    chain.push(curdir.backwards());
+                    curdir = curdir.backwards().next_direction();
                 }
                 CellKind::Empty => {
                     // We walked forward one block
@@ -216,21 +211,52 @@ impl RepairBot {
     }
 }
 
+/// Search outwards from the start, a step at a time, so the first route to
+/// the oxygen system is the shortest.  Rather than walking the droid back and
+/// forth we snapshot its brain at each place it reaches, and its state hash
+/// tells us when it has been somewhere before.
 fn part1(input: &intcode::VM) -> Result<usize> {
-    let mut bot = RepairBot::new(input.clone());
-
-    bot.explore(false)
+    let mut brain = input.clone();
+    let mut seen = HashSet::new();
+    seen.insert(brain.state_hash());
+    let mut queue = VecDeque::new();
+    queue.push_back((brain.snapshot(), 0));
+    while let Some((snapshot, steps)) = queue.pop_front() {
+        for dir in [
+            Direction::North,
+            Direction::South,
+            Direction::West,
+            Direction::East,
+        ] {
+            brain.restore(&snapshot);
+            let kind: CellKind = match brain.interpreter_step(Some(dir.to_num()))? {
+                intcode::VMState::GaveOutput(o) => o.into(),
+                eh => return Err(format!("Unexpected brain state: {:?}", eh).into()),
+            };
+            match kind {
+                CellKind::Unknown => return Err("Unknown cell kind returned?".into()),
+                CellKind::Wall => {}
+                CellKind::Oxygen => return Ok(steps + 1),
+                CellKind::Empty => {
+                    if seen.insert(brain.state_hash()) {
+                        queue.push_back((brain.snapshot(), steps + 1));
+                    }
+                }
+            }
+        }
+    }
+    Err("Never found the oxygen system".into())
 }
 
 fn part2(input: &intcode::VM) -> Result<usize> {
     let mut bot = RepairBot::new(input.clone());
-    assert_eq!(bot.explore(true)?, 0);
+    bot.explore()?;
     //println!("Found oxygen generator at {:?}", bot.oxygen);
     // Okay the area is fully explored
     Ok(bot.spread_oxygen())
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(15)?;
     let input = intcode::VM::from_str(&input)?;
 
@@ -141,7 +141,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(16)?;
 
     println!("Part 1: {}", part1(&input));
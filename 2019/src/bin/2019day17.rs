@@ -1,3 +1,4 @@
+use aoc2019::intcode::ascii;
 use aoc2019::*;
 
 struct Scaffold {
@@ -27,16 +28,16 @@ impl fmt::Display for Instruction {
 }
 
 impl Scaffold {
-    fn load(mut vm: intcode::VM) -> Result<Self> {
+    fn load(vm: intcode::VM) -> Result<Self> {
         let mut width = std::usize::MAX; // Start huge in case bot is on line 1
-        let mut asciimap = Vec::new();
         let mut botpos = (0, 0);
         let mut botfacing = Facing::Up;
 
-        vm.full_interpret(&[], &mut asciimap)?;
+        let mut camera = ascii::Ascii::new(vm);
+        camera.run()?;
 
         let mut map = Vec::new();
-        for ch in asciimap.into_iter().map(|v| (v as u8) as char) {
+        for ch in camera.take_output().chars() {
             match ch {
                 '\n' => {
                     if width == std::usize::MAX {
@@ -198,18 +199,18 @@ fn part2(input: &intcode::VM) -> Result<usize> {
     //println!("C: {}", seq_c);
     let mut newbot = input.clone();
     newbot.poke(0, 2)?;
-    let botin: Vec<_> = format!("{}\n{}\n{}\n{}\nn\n", prog, seq_a, seq_b, seq_c)
-        .bytes()
-        .map(|b| b as i64)
-        .collect();
-    let mut botout = Vec::new();
-    newbot.full_interpret(&botin, &mut botout)?;
-    //println!("Output: {:?}", botout);
-
-    Ok(botout[botout.len() - 1] as usize)
+    let mut newbot = ascii::Ascii::new(newbot);
+    let botin = [prog.as_str(), &seq_a, &seq_b, &seq_c, "n"];
+    newbot.run_with(&mut ascii::lines(botin.iter().copied()))?;
+    //println!("Output: {}", newbot);
+
+    match newbot.values().last() {
+        Some(&dust) => Ok(dust as usize),
+        None => Err(format!("Vacuum robot failed:\n{}", newbot).into()),
+    }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(17)?;
     let input = intcode::VM::from_str(&input)?;
 
@@ -429,7 +429,7 @@ fn part2(input: &str) -> Result<usize> {
     input.collect_keys()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(18)?;
     println!("Part 1: {}", part1(&input)?);
     println!("Part 2: {}", part2(&input)?);
@@ -132,9 +132,9 @@ fn part2(input: &intcode::VM) -> Result<usize> {
     Ok(base_y + (base_x * 10_000))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(19)?;
-    let input = intcode::VM::from_str(&input)?;
+    let input = intcode::VM::from_str(&input)?.predecoded();
 
     println!("Part 1: {}", part1(&input)?);
     println!("Part 2: {}", part2(&input)?);
@@ -45,7 +45,7 @@ fn part2(code: &intcode::VM) -> i64 {
     unreachable!()
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(2)?;
     let input = input.trim();
     let input = intcode::VM::from_str(input)?;
@@ -335,7 +335,7 @@ fn part2(input: &Maze) -> usize {
     input.shortest_route(true)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(20)?;
     let input = Maze::from_str(&input)?;
 
@@ -1,17 +1,20 @@
+use aoc2019::intcode::ascii;
 use aoc2019::*;
 
 static PROG1: &str = "OR A T\nAND B T\nAND C T\nNOT T J\nAND D J\nWALK\n";
 static PROG2: &str = "NOT H J\nOR  C J\nAND B J\nAND A J\nNOT J J\nAND D J\nRUN\n";
 
 fn part12(input: &intcode::VM, prog: &str) -> Result<i64> {
-    let mut vm = input.clone();
-    let mut output = Vec::new();
-    let input: Vec<_> = prog.as_bytes().iter().map(|v| *v as i64).collect();
-    vm.full_interpret(&input, &mut output)?;
-    Ok(output[output.len() - 1])
+    let mut droid = ascii::Ascii::new(input.clone());
+    droid.run_with(&mut ascii::lines(prog.lines()))?;
+    match droid.values().last() {
+        Some(&damage) => Ok(damage),
+        // The droid fell into space, and here's the animation
+        None => Err(format!("Springdroid failed:\n{}", droid).into()),
+    }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(21)?;
     let input = intcode::VM::from_str(&input)?;
 
@@ -215,7 +215,7 @@ fn part2(input: &[ShuffOp]) -> i128 {
         .fold(2020, |n, op| op.project_cardpos_backward(n, P2DECKSIZE))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<ShuffOp> = read_input_as_vec(22)?;
 
     println!("Part 1: {}", part1(&input));
@@ -219,7 +219,7 @@ fn part2(input: u32) -> u32 {
     bugs
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(24)?;
     let input = grid_to_u32(&input);
 
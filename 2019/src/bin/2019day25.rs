@@ -1,100 +1,324 @@
+use aoc2019::intcode::ascii;
 use aoc2019::*;
+use aoc_common::options;
 
-#[allow(dead_code)]
-fn gather_output(vm: &mut intcode::VM) -> Result<Vec<String>> {
-    let mut out = String::new();
-    while let intcode::VMState::GaveOutput(c) = vm.interpreter_step(None)? {
-        out.push(c as u8 as char);
+use std::collections::BTreeMap;
+
+/// Items which end the game if you pick them up (or in the case of the
+/// infinite loop, never let it carry on)
+static FATAL_ITEMS: &[&str] = &[
+    "escape pod",
+    "giant electromagnet",
+    "infinite loop",
+    "molten lava",
+    "photons",
+];
+
+#[derive(Debug, Default)]
+struct Room {
+    name: String,
+    /// Where each door leads, once we've been through it
+    doors: BTreeMap<Direction, Option<String>>,
+    items: Vec<String>,
+}
+
+/// Every room described in some output, in the order they were.  Usually
+/// there's only one, but being thrown out of somewhere describes the room
+/// we were thrown out of and then the one we landed in.
+fn parse_rooms(output: &str) -> Result<Vec<Room>> {
+    enum List {
+        Nothing,
+        Doors,
+        Items,
     }
-    Ok(out.lines().map(String::from).collect())
+    let mut rooms: Vec<Room> = Vec::new();
+    let mut list = List::Nothing;
+    for line in output.lines() {
+        if let Some(name) = line
+            .strip_prefix("== ")
+            .and_then(|line| line.strip_suffix(" =="))
+        {
+            rooms.push(Room {
+                name: name.to_string(),
+                ..Room::default()
+            });
+            list = List::Nothing;
+        } else if line == "Doors here lead:" {
+            list = List::Doors;
+        } else if line == "Items here:" {
+            list = List::Items;
+        } else if let (Some(entry), Some(room)) = (line.strip_prefix("- "), rooms.last_mut()) {
+            match list {
+                List::Doors => {
+                    room.doors.insert(Direction::from_str(entry)?, None);
+                }
+                List::Items => room.items.push(entry.to_string()),
+                List::Nothing => {}
+            }
+        } else {
+            list = List::Nothing;
+        }
+    }
+    Ok(rooms)
+}
+
+/// The `n`th number in a Gray code, where each differs from the last in
+/// only one bit
+fn gray_code(n: usize) -> usize {
+    n ^ (n >> 1)
 }
 
-#[allow(dead_code)]
-fn parse_room(vm: &mut intcode::VM) -> Result<(String, Vec<Direction>, Vec<String>)> {
-    // We run this VM until it prompts for input, and then analyse its output
-    let mut output = gather_output(vm)?;
-    assert!(output.len() >= 5);
-    // The first line of a room output is the room's name
-    let name = loop {
-        let line = output.remove(0);
-        if line.starts_with("== ") {
-            break line;
+struct Explorer {
+    droid: ascii::Ascii,
+    rooms: BTreeMap<String, Room>,
+    here: String,
+    inventory: Vec<String>,
+    /// The room we get thrown out of for weighing the wrong amount, and the
+    /// door from where we get thrown back to
+    checkpoint: Option<(String, Direction)>,
+}
+
+impl Explorer {
+    fn new(vm: intcode::VM) -> Result<Self> {
+        let mut droid = ascii::Ascii::new(vm);
+        droid.run()?;
+        let room = parse_rooms(&droid.take_output())?
+            .pop()
+            .ok_or("No starting room")?;
+        let here = room.name.clone();
+        let mut rooms = BTreeMap::new();
+        rooms.insert(here.clone(), room);
+        Ok(Self {
+            droid,
+            rooms,
+            here,
+            inventory: Vec::new(),
+            checkpoint: None,
+        })
+    }
+
+    fn command(&mut self, command: &str) -> Result<String> {
+        self.droid.send_line(command)?;
+        self.droid.run()?;
+        Ok(self.droid.take_output())
+    }
+
+    /// Take everything safe here, then explore everywhere through every
+    /// door we haven't been through, coming back here afterwards
+    fn explore(&mut self) -> Result<()> {
+        let here = self.here.clone();
+        for item in self.rooms[&here].items.clone() {
+            if FATAL_ITEMS.contains(&item.as_str()) {
+                continue;
+            }
+            let output = self.command(&format!("take {}", item))?;
+            if self.droid.vm().state() == intcode::VMState::Halted {
+                return Err(format!("Taking the {} was fatal:\n{}", item, output).into());
+            }
+            self.inventory.push(item);
         }
-    };
-    assert!(name.starts_with("== "));
-    assert!(name.ends_with(" =="));
-    let name = name[3..name.len() - 3].to_string();
-    // Now let's hunt for the door list
-    while !output.is_empty() {
-        let line = output.remove(0);
-        if line == "Doors here lead:" {
-            break;
+        let unexplored: Vec<_> = self.rooms[&here]
+            .doors
+            .iter()
+            .filter(|(_, dest)| dest.is_none())
+            .map(|(&dir, _)| dir)
+            .collect();
+        for dir in unexplored {
+            let mut rooms = parse_rooms(&self.command(&dir.to_string())?)?.into_iter();
+            let room = rooms.next().ok_or("Went nowhere")?;
+            let there = room.name.clone();
+            self.rooms
+                .get_mut(&here)
+                .unwrap()
+                .doors
+                .insert(dir, Some(there.clone()));
+            let visited = self.rooms.contains_key(&there);
+            let room = self.rooms.entry(there.clone()).or_insert(room);
+            room.doors.insert(dir.opposite(), Some(here.clone()));
+            if rooms.next().is_some() {
+                // Thrown back out
+                self.checkpoint = Some((here.clone(), dir));
+                continue;
+            }
+            if !visited {
+                self.here = there;
+                self.explore()?;
+            }
+            self.command(&dir.opposite().to_string())?;
+            self.here = here.clone();
         }
+        Ok(())
     }
-    let mut doors = Vec::new();
-    while !output.is_empty() {
-        let line = output.remove(0);
-        if let Some(end) = line.strip_prefix("- ") {
-            let dir = Direction::from_str(end)?;
-            doors.push(dir);
-        } else {
-            break; // Finished doors
+
+    /// Walk to `dest` the shortest way
+    fn walk_to(&mut self, dest: &str) -> Result<()> {
+        let mut came_from: HashMap<String, (String, Direction)> = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(self.here.clone());
+        while let Some(room) = queue.pop_front() {
+            if room == dest {
+                break;
+            }
+            for (&dir, next) in &self.rooms[&room].doors {
+                if let Some(next) = next {
+                    if *next != self.here && !came_from.contains_key(next) {
+                        came_from.insert(next.clone(), (room.clone(), dir));
+                        queue.push_back(next.clone());
+                    }
+                }
+            }
         }
-    }
-    let mut items = Vec::new();
-    // Hunt for the items line
-    while !output.is_empty() {
-        let line = output.remove(0);
-        if line == "Command?" {
-            break;
+        let mut route = Vec::new();
+        let mut room = dest.to_string();
+        while room != self.here {
+            let (prev, dir) = came_from.remove(&room).ok_or("No way there")?;
+            route.push(dir);
+            room = prev;
         }
-        if line == "Items here:" {
-            while !output.is_empty() {
-                let line = output.remove(0);
-                if let Some(end) = line.strip_prefix("- ") {
-                    items.push(end.to_string());
-                } else {
-                    break;
+        for dir in route.into_iter().rev() {
+            self.command(&dir.to_string())?;
+        }
+        self.here = dest.to_string();
+        Ok(())
+    }
+
+    /// Try every combination of what we're carrying on the pressure plate,
+    /// swapping one item at a time, until we weigh the right amount
+    fn through_checkpoint(&mut self) -> Result<String> {
+        let (checkpoint, dir) = self.checkpoint.clone().ok_or("No checkpoint found")?;
+        self.walk_to(&checkpoint)?;
+        // Starting with everything, which is what we're carrying
+        let everything = (1 << self.inventory.len()) - 1;
+        let mut held = everything;
+        for n in 0..=everything {
+            let want = !gray_code(n) & everything;
+            for (bit, item) in self.inventory.clone().iter().enumerate() {
+                let mask = 1 << bit;
+                if (held ^ want) & mask != 0 {
+                    let verb = if want & mask == 0 { "drop" } else { "take" };
+                    self.command(&format!("{} {}", verb, item))?;
                 }
             }
+            held = want;
+            let output = self.command(&dir.to_string())?;
+            if let Some(typing) = output.split("typing ").nth(1) {
+                let code = typing.split_whitespace().next().unwrap_or_default();
+                return Ok(code.to_string());
+            }
+        }
+        Err("Nothing we could carry got us through".into())
+    }
+
+    /// The map, as a graphviz graph
+    fn dot(&self) -> String {
+        let mut dot = String::from("graph ship {\n");
+        for room in self.rooms.values() {
+            let mut label = room.name.clone();
+            for item in &room.items {
+                label.push_str(&format!("\\n({})", item));
+            }
+            dot.push_str(&format!("    \"{}\" [label=\"{}\"];\n", room.name, label));
+        }
+        for room in self.rooms.values() {
+            for (dir, dest) in &room.doors {
+                let dest = match dest {
+                    // Each door once, from its northern or western end
+                    Some(dest) if matches!(dir, Direction::South | Direction::East) => dest,
+                    _ => continue,
+                };
+                dot.push_str(&format!(
+                    "    \"{}\" -- \"{}\" [taillabel=\"{}\", headlabel=\"{}\"];\n",
+                    room.name,
+                    dest,
+                    dir,
+                    dir.opposite()
+                ));
+            }
         }
+        dot.push_str("}\n");
+        dot
     }
-    // And give it all back
-    Ok((name, doors, items))
 }
 
-//#[derive(Debug, Hash, PartialEq, Eq)]
-//struct BotPos {
-//    room: String,
-//    items: HashSet<String>,
-//}
+fn part1(input: &intcode::VM) -> Result<(String, String)> {
+    let mut explorer = Explorer::new(input.clone())?;
+    explorer.explore()?;
+    let code = explorer.through_checkpoint()?;
+    Ok((code, explorer.dot()))
+}
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(25)?;
-    let mut input = intcode::VM::from_str(&input)?;
-
-    //if std::env::args().nth(1) == Some("interactive".to_string()) {
-    input.run_ascii_machine()?;
-    //    return Ok(());
-    //}
-
-    //    let (first_room, first_doors, first_items) = parse_room(&mut input)?;
-    //
-    //    let bots: HashMap<BotPos, intcode::VM> = HashMap::new();
-    //
-    //    bots.insert(
-    //        BotPos {
-    //            room: first_room.clone(),
-    //            items: HashSet::new(),
-    //        },
-    //        input.clone(),
-    //    );
-    //
-    //    loop {
-    //        // We are in a room, there are potentially items, and potentially
-    //        // exits.  First thing we do is decide if we're going to try picking
-    //        // up any of the items
-    //    }
+    let input = intcode::VM::from_str(&input)?;
+
+    if cfg!(feature = "interactive") {
+        // Play through any script from `aoc run --script` before handing
+        // over to the terminal
+        let mut droid = ascii::Ascii::new(input).echoed();
+        if let Some(script) = options::script() {
+            droid.run_with(&mut ascii::script(script)?)?;
+        }
+        droid.run_with(&mut ascii::Terminal)?;
+    } else {
+        if options::script().is_some() {
+            return Err("Playing a script needs the interactive feature".into());
+        }
+        let (code, map) = part1(&input)?;
+        println!("Part 1: {}", code);
+        if let Some(path) = options::take_dot_output() {
+            std::fs::write(path, map)?;
+        }
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gray_codes() {
+        for n in 1..256 {
+            assert_eq!((gray_code(n) ^ gray_code(n - 1)).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn ejected() {
+        static OUTPUT: &str = "
+
+
+== Pressure-Sensitive Floor ==
+Analyzing...
+
+Doors here lead:
+- south
+
+A loud, robotic voice says \"Alert! Droids on this ship are heavier than the detected value!\" and you are ejected back to the checkpoint.
+
+
+
+== Security Checkpoint ==
+In the next room, a pressure-sensitive floor will verify your identity.
+
+Doors here lead:
+- north
+- south
+
+Items here:
+- mug
+
+Command?
+";
+        let rooms = parse_rooms(OUTPUT).unwrap();
+        assert_eq!(rooms.len(), 2);
+        assert_eq!(rooms[0].name, "Pressure-Sensitive Floor");
+        assert_eq!(rooms[1].name, "Security Checkpoint");
+        assert_eq!(
+            rooms[1].doors.keys().copied().collect::<Vec<_>>(),
+            [Direction::North, Direction::South]
+        );
+        assert_eq!(rooms[1].items, ["mug"]);
+    }
+}
@@ -135,7 +135,7 @@ fn part2(wires: &[Wire]) -> usize {
     wires[0].stepwise_overlap_distance(&wires[1])
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Vec<WireStep>> = read_input_as_lists(3)?;
     let input: Vec<Wire> = input.into_iter().map(Wire::from_steps).collect();
     println!("Part 1: {}", part1(&input));
@@ -105,7 +105,7 @@ fn part2(first: &str, second: &str) -> usize {
     loop_check_codes(first, second, passcode_valid_2)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(4)?;
     let (first, second) = input.split_at(input.find('-').unwrap());
     let second = &second[1..];
@@ -114,6 +114,6 @@ fn main() -> Result<()> {
         println!("second: {}", second);
     }
     println!("Part 1: {}", part1(first, second));
-    println!("Part 1: {}", part2(first, second));
+    println!("Part 2: {}", part2(first, second));
     Ok(())
 }
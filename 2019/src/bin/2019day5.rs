@@ -112,7 +112,7 @@ fn part2(vm: &intcode::VM) -> Result<i64> {
     Ok(output[output.len() - 1])
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(5)?;
     let input = input.trim();
     let test_vm = intcode::VM::from_str(input)?;
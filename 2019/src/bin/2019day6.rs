@@ -53,7 +53,7 @@ fn part2(orbits: &[Orbit]) -> usize {
     0
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Orbit> = read_input_as_vec(6)?;
 
     println!("Part 1: {}", part1(&input));
@@ -1,57 +1,22 @@
+use aoc2019::intcode::network::{Network, Topology};
 use aoc2019::*;
 
-fn run_amplifier(model: &intcode::VM, phase: i64, input: i64) -> Result<i64> {
-    let mut vm = model.clone();
-    let input = [phase, input];
-    let mut output = Vec::new();
-    vm.full_interpret(&input, &mut output)?;
-    if output.len() != 1 {
-        Err(format!("Output was {:?}", output).into())
-    } else {
-        Ok(output[0])
+fn run_sequence(model: &intcode::VM, sequence: &[i64], topology: Topology) -> Result<i64> {
+    let mut amplifiers = Network::new(model, sequence.len(), topology);
+    for (amplifier, phase) in sequence.iter().enumerate() {
+        amplifiers.send(amplifier, &[*phase]);
     }
+    amplifiers.send(0, &[0]);
+    let signals = amplifiers.run_to_halt()?;
+    Ok(*signals.last().ok_or("No signal came out")?)
 }
 
 fn run_sequence1(model: &intcode::VM, sequence: &[i64]) -> Result<i64> {
-    let mut signal = 0;
-    for phase in sequence.iter().copied() {
-        signal = run_amplifier(model, phase, signal)?;
-    }
-    Ok(signal)
+    run_sequence(model, sequence, Topology::Pipeline)
 }
 
 fn run_sequence2(model: &intcode::VM, sequence: &[i64]) -> Result<i64> {
-    let mut feedback = 0;
-    let vms: Result<Vec<intcode::VM>> = sequence
-        .iter()
-        .map(|phase| {
-            let mut vm = model.clone();
-            let vmstate = vm.interpreter_step(None)?;
-            assert_eq!(vmstate, intcode::VMState::WaitingOnInput);
-            let vmstate = vm.interpreter_step(Some(*phase))?;
-            assert_eq!(vmstate, intcode::VMState::WaitingOnInput);
-            Ok(vm)
-        })
-        .collect();
-    let mut vms = vms?;
-    'outer: loop {
-        for vm in vms.iter_mut() {
-            let mut vmstate = vm.interpreter_step(None)?;
-            'inner: loop {
-                use intcode::VMState::*;
-                match vmstate {
-                    Runnable => panic!("Why did it return to us runnable?"),
-                    Halted => break 'outer,
-                    GaveOutput(v) => {
-                        feedback = v;
-                        break 'inner;
-                    }
-                    WaitingOnInput => vmstate = vm.interpreter_step(Some(feedback))?,
-                }
-            }
-        }
-    }
-    Ok(feedback)
+    run_sequence(model, sequence, Topology::Ring)
 }
 
 fn best_sequence<F>(model: &intcode::VM, start_seq: &[i64], score: F) -> Result<Vec<i64>>
@@ -145,7 +110,7 @@ fn part2(model: &intcode::VM) -> Result<i64> {
     run_sequence2(model, &seq)
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(7)?;
     let input = intcode::VM::from_str(&input)?;
 
@@ -42,14 +42,15 @@ fn part2(input: &str) -> Result<String> {
         .chunks_exact(25)
         .map(|row| std::str::from_utf8(row).unwrap())
         .collect();
-    Ok(rows.join("\n").replace('0', " ").replace('1', "X"))
+    let image = rows.join("\n").replace('0', " ").replace('1', "X");
+    Ok(ocr::read_grid(&image).unwrap_or(image))
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(8)?;
     let input = input.trim();
 
     println!("Part 1: {}", part1(input)?);
-    println!("Part 2:\n{}", part2(input)?);
+    println!("Part 2: {}", part2(input)?);
     Ok(())
 }
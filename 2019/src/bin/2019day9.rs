@@ -36,7 +36,7 @@ fn run_step(input: &intcode::VM, part: i64) -> Result<i64> {
     Ok(output[0])
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(9)?;
     let input = input.trim();
     let input = intcode::VM::from_str(input)?;
@@ -0,0 +1,52 @@
+//! Debug an Intcode program interactively
+//!
+//! ```text
+//! 2019intcode (DAY | FILE) [SCRIPT]
+//! ```
+//!
+//! The program is either a day's input or a file, which can be an assembly
+//! listing (see `intcode::asm`) as well as a plain Intcode program.  Commands
+//! in SCRIPT are run before any are read from stdin.
+
+use std::fs::File;
+use std::io::{stdin, stdout, BufReader};
+
+use aoc2019::intcode::debugger::Debugger;
+use aoc2019::intcode::{asm, VM};
+use aoc2019::*;
+
+const USAGE: &str = "usage: 2019intcode (DAY | FILE) [SCRIPT]";
+
+fn load(program: &str) -> Result<VM> {
+    let source = match program.parse() {
+        Ok(day) => read_input(day)?,
+        Err(_) => std::fs::read_to_string(program)?,
+    };
+    let source = source.trim();
+    let is_intcode = source
+        .split(',')
+        .all(|value| value.trim().parse::<i64>().is_ok());
+    Ok(if is_intcode {
+        VM::from_str(source)?
+    } else {
+        VM::from(asm::assemble(source)?)
+    })
+}
+
+pub fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (program, script) = match args.as_slice() {
+        [program] => (program, None),
+        [program, script] => (program, Some(script)),
+        _ => return Err(USAGE.into()),
+    };
+    let mut debugger = Debugger::new(load(program)?);
+    let mut out = stdout();
+    if let Some(script) = script {
+        if !debugger.repl(BufReader::new(File::open(script)?), &mut out, true)? {
+            return Ok(());
+        }
+    }
+    debugger.repl(stdin().lock(), &mut out, false)?;
+    Ok(())
+}
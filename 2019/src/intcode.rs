@@ -1,6 +1,27 @@
 //! Intcode VM for 2019 AoC
 //!
+//! See [`asm`] for turning programs into text and back again,
+//! [`debugger`] for picking them apart as they run, [`network`] for wiring
+//! several together, and [`ascii`] for talking to them in text.
 //!
+//! Searches which explore a program's behaviour want lots of copies of a VM,
+//! so RAM is kept in pages which are shared between a VM and its clones and
+//! [`Snapshot`]s until one of them writes to a page.  A VM can also keep a
+//! journal of what each step changed, to step back through, and
+//! [`VM::state_hash`] lets searches spot states they've already been in.
+//!
+//! Decoding an instruction means picking its opcode value apart every time
+//! it runs, so [`VM::predecoded`] VMs keep each address's opcode decoded,
+//! in pages alongside the RAM's, until something writes over it.
+
+pub mod ascii;
+pub mod asm;
+pub mod debugger;
+pub mod network;
+
+use std::convert::TryFrom;
+use std::hash::Hasher;
+use std::rc::Rc;
 
 /// Errors this stuff can return
 #[derive(Debug)]
@@ -12,6 +33,10 @@ pub enum Error {
     UnknownAddressingMode(i64),
     NoMoreInput(i64),
     IOError(std::io::Error),
+    /// An assembly listing which didn't assemble, and on which line
+    AsmError(usize, String),
+    /// A network of VMs all waiting on each other
+    Deadlocked,
 }
 
 impl std::fmt::Display for Error {
@@ -31,7 +56,7 @@ impl std::convert::From<std::io::Error> for Error {
 /// A VM Result
 pub type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OpCode {
     Add = 1,
     Mul = 2,
@@ -47,6 +72,45 @@ pub enum OpCode {
 }
 
 impl OpCode {
+    pub const ALL: [OpCode; 10] = [
+        Self::Add,
+        Self::Mul,
+        Self::Input,
+        Self::Output,
+        Self::JumpIfTrue,
+        Self::JumpIfFalse,
+        Self::LessThan,
+        Self::Equals,
+        Self::SetRelativeBase,
+        Self::Terminate,
+    ];
+
+    /// How many parameters follow the opcode
+    pub fn params(self) -> usize {
+        match self {
+            Self::Add | Self::Mul | Self::LessThan | Self::Equals => 3,
+            Self::JumpIfTrue | Self::JumpIfFalse => 2,
+            Self::Input | Self::Output | Self::SetRelativeBase => 1,
+            Self::Terminate => 0,
+        }
+    }
+
+    /// The name [`asm`] gives the opcode
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Mul => "mul",
+            Self::Input => "in",
+            Self::Output => "out",
+            Self::JumpIfTrue => "jnz",
+            Self::JumpIfFalse => "jz",
+            Self::LessThan => "lt",
+            Self::Equals => "eq",
+            Self::SetRelativeBase => "arb",
+            Self::Terminate => "hlt",
+        }
+    }
+
     fn convert(from: i64) -> Result<Self> {
         if cfg!(debug_assertions) && from < 1 {
             return Err(Error::BadOpCode(from));
@@ -77,50 +141,326 @@ pub enum VMState {
     Halted,
 }
 
+const PAGE_SIZE: usize = 256;
+
+type Page = Rc<[i64; PAGE_SIZE]>;
+
+fn paged(ram: &[i64]) -> Vec<Page> {
+    ram.chunks(PAGE_SIZE)
+        .map(|chunk| {
+            let mut page = [0; PAGE_SIZE];
+            page[..chunk.len()].copy_from_slice(chunk);
+            Rc::new(page)
+        })
+        .collect()
+}
+
+/// FNV-1a, which unlike `std`'s hashers promises the same answer every run
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3);
+        }
+    }
+}
+
+fn state_hash(ram: &[Page], pc: i64, relative_base: i64, state: VMState) -> u64 {
+    let mut hash = Fnv(0xcbf2_9ce4_8422_2325);
+    let state = match state {
+        VMState::Runnable => [0, 0],
+        VMState::WaitingOnInput => [1, 0],
+        VMState::GaveOutput(value) => [2, value],
+        VMState::Halted => [3, 0],
+    };
+    // Memory nobody has written to is zero, however much of it there is
+    let used = ram
+        .iter()
+        .rposition(|page| page.iter().any(|&v| v != 0))
+        .map_or(0, |last| {
+            last * PAGE_SIZE + ram[last].iter().rposition(|&v| v != 0).unwrap() + 1
+        });
+    let values = ram.iter().flat_map(|page| page.iter()).take(used);
+    for value in [pc, relative_base, state[0], state[1]].iter().chain(values) {
+        hash.write(&value.to_le_bytes());
+    }
+    hash.finish()
+}
+
+/// An opcode as [`VM::predecoded`] keeps it
+#[derive(Debug, Clone, Copy)]
+struct Decoded {
+    /// What it was decoded from, which if it's no longer what's in RAM means
+    /// the program has written over it
+    opval: i64,
+    op: OpCode,
+    modes: [asm::Mode; 3],
+}
+
+impl Decoded {
+    /// Unless it isn't a valid opcode (which is then left to the interpreter
+    /// to complain about, if it's run)
+    fn new(opval: i64) -> Option<Self> {
+        if opval < 1 {
+            return None;
+        }
+        let op = OpCode::convert(opval).ok()?;
+        let mut decoded = Decoded {
+            opval,
+            op,
+            modes: [asm::Mode::Position; 3],
+        };
+        let mut modes = opval / 100;
+        for mode in decoded.modes.iter_mut().take(op.params()) {
+            *mode = match modes % 10 {
+                0 => asm::Mode::Position,
+                1 => asm::Mode::Immediate,
+                2 => asm::Mode::Relative,
+                _ => return None,
+            };
+            modes /= 10;
+        }
+        Some(decoded)
+    }
+}
+
+type CachePage = Rc<[Option<Decoded>; PAGE_SIZE]>;
+
+/// What a step changed, so that it can be undone
+#[derive(Debug, Clone, Copy)]
+struct Undo {
+    pc: i64,
+    relative_base: i64,
+    state: VMState,
+    /// The address written to, and what was there before
+    write: Option<(i64, i64)>,
+}
+
+/// A VM's state at some point, to go back to with [`VM::restore`]
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    ram: Vec<Page>,
+    cache: Option<Vec<CachePage>>,
+    len: usize,
+    pc: i64,
+    curstate: VMState,
+    relative_base: i64,
+}
+
+impl Snapshot {
+    pub fn state_hash(&self) -> u64 {
+        state_hash(&self.ram, self.pc, self.relative_base, self.curstate)
+    }
+}
+
 /// The VM itself
 #[derive(Debug, Clone)]
 pub struct VM {
-    ram: Vec<i64>,
+    ram: Vec<Page>,
+    /// How much of `ram` has been used
+    len: usize,
     pc: i64,
     curstate: VMState,
     relative_base: i64,
+    journal: Option<Vec<Undo>>,
+    /// Decoded instructions, for a predecoded VM
+    cache: Option<Vec<CachePage>>,
+    /// The opcode being run, if it came from `cache`
+    current: Option<Decoded>,
 }
 
 impl VM {
+    /// The VM's memory, as far as the program has used it
+    pub fn ram(&self) -> Vec<i64> {
+        self.ram
+            .iter()
+            .flat_map(|page| page.iter().copied())
+            .take(self.len)
+            .collect()
+    }
+
+    /// The instruction at `addr`, if there's a valid one there
+    pub fn instruction_at(&self, addr: i64) -> Option<asm::Instruction> {
+        if addr < 0 {
+            return None;
+        }
+        let values = (addr..addr + 4)
+            .map(|addr| self.peek(addr))
+            .collect::<Result<Vec<_>>>()
+            .ok()?;
+        asm::decode(&values, 0)
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    pub fn state(&self) -> VMState {
+        self.curstate
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn set_relative_base(&mut self, value: i64) {
+        self.relative_base = value;
+    }
+
     #[inline]
     pub fn peek(&self, addr: i64) -> Result<i64> {
         if cfg!(debug_assertions) && addr < 0 {
             return Err(Error::BadAddress(addr));
         }
         let addr = addr as usize;
-        Ok(if addr < self.ram.len() {
-            self.ram[addr]
-        } else {
-            0
-        })
+        Ok(self
+            .ram
+            .get(addr / PAGE_SIZE)
+            .map_or(0, |page| page[addr % PAGE_SIZE]))
     }
 
     #[inline]
     pub fn poke(&mut self, addr: i64, value: i64) -> Result<()> {
-        if cfg!(debug_assertions) {
-            if addr < 0 {
-                Err(Error::BadAddress(addr))
-            } else {
-                let addr = addr as usize;
-                while addr >= self.ram.len() {
-                    self.ram.resize(addr + 1024, 0);
+        if cfg!(debug_assertions) && addr < 0 {
+            return Err(Error::BadAddress(addr));
+        }
+        let addr = addr as usize;
+        let page = addr / PAGE_SIZE;
+        if page >= self.ram.len() {
+            // New pages all share one page of zeros until they're written
+            self.ram.resize(page + 1, Rc::new([0; PAGE_SIZE]));
+        }
+        Rc::make_mut(&mut self.ram[page])[addr % PAGE_SIZE] = value;
+        self.len = self.len.max(addr + 1);
+        Ok(())
+    }
+
+    /// Decode each opcode once rather than every time it runs, which is
+    /// quicker for programs which loop a lot.  Opcodes which are written
+    /// over are decoded again, so self-modifying code still works.
+    pub fn predecoded(mut self) -> Self {
+        let cache = self
+            .ram
+            .iter()
+            .map(|page| {
+                let mut entries = [None; PAGE_SIZE];
+                for (entry, &opval) in entries.iter_mut().zip(page.iter()) {
+                    *entry = Decoded::new(opval);
                 }
-                self.ram[addr] = value;
-                Ok(())
+                Rc::new(entries)
+            })
+            .collect();
+        self.cache = Some(cache);
+        self
+    }
+
+    /// The decoded opcode at `addr`, decoding it again if it's been written
+    /// over
+    fn cached(&mut self, addr: i64) -> Option<Decoded> {
+        // Without a cache there's nothing to look up, so don't read memory
+        let cache = self.cache.as_ref()?;
+        let index = usize::try_from(addr).ok()?;
+        let (page, slot) = (index / PAGE_SIZE, index % PAGE_SIZE);
+        let cached = cache.get(page)?[slot];
+        let opval = self.peek(addr).ok()?;
+        match cached {
+            Some(decoded) if decoded.opval == opval => Some(decoded),
+            _ => {
+                let decoded = Decoded::new(opval)?;
+                Rc::make_mut(&mut self.cache.as_mut()?[page])[slot] = Some(decoded);
+                Some(decoded)
             }
-        } else {
-            let addr = addr as usize;
-            while addr >= self.ram.len() {
-                self.ram.resize(addr + 1024, 0);
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            ram: self.ram.clone(),
+            cache: self.cache.clone(),
+            len: self.len,
+            pc: self.pc,
+            curstate: self.curstate,
+            relative_base: self.relative_base,
+        }
+    }
+
+    /// Go back to `snapshot`, which forgets the journal
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.ram = snapshot.ram.clone();
+        self.cache = snapshot.cache.clone();
+        self.len = snapshot.len;
+        self.pc = snapshot.pc;
+        self.curstate = snapshot.curstate;
+        self.relative_base = snapshot.relative_base;
+        if let Some(journal) = &mut self.journal {
+            journal.clear();
+        }
+    }
+
+    /// A hash of everything which decides what the VM does next, which is
+    /// the same from run to run
+    pub fn state_hash(&self) -> u64 {
+        state_hash(&self.ram, self.pc, self.relative_base, self.curstate)
+    }
+
+    /// Start keeping a journal of steps, so that they can be stepped back
+    /// over.  Steps run before this can't be.
+    pub fn enable_journal(&mut self) {
+        self.journal.get_or_insert_with(Vec::new);
+    }
+
+    /// How many steps there are to step back over
+    pub fn journal_len(&self) -> usize {
+        self.journal.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Undo the last step, returning false if there's nothing to undo
+    pub fn step_back(&mut self) -> Result<bool> {
+        let undo = match self.journal.as_mut().and_then(Vec::pop) {
+            Some(undo) => undo,
+            None => return Ok(false),
+        };
+        if let Some((addr, value)) = undo.write {
+            self.poke(addr, value)?;
+        }
+        self.pc = undo.pc;
+        self.relative_base = undo.relative_base;
+        self.curstate = undo.state;
+        Ok(true)
+    }
+
+    /// Undo up to `steps` steps, returning how many were undone
+    pub fn rewind(&mut self, steps: usize) -> Result<usize> {
+        for undone in 0..steps {
+            if !self.step_back()? {
+                return Ok(undone);
             }
-            self.ram[addr] = value;
-            Ok(())
         }
+        Ok(steps)
+    }
+
+    fn undo_for(&self, op: OpCode, has_input: bool) -> Result<Undo> {
+        let target = match op {
+            OpCode::Add | OpCode::Mul | OpCode::LessThan | OpCode::Equals => {
+                Some(self.addr_for(2)?)
+            }
+            OpCode::Input if has_input => Some(self.addr_for(0)?),
+            _ => None,
+        };
+        Ok(Undo {
+            pc: self.pc,
+            relative_base: self.relative_base,
+            state: self.curstate,
+            write: match target {
+                Some(addr) => Some((addr, self.peek(addr)?)),
+                None => None,
+            },
+        })
     }
 
     fn opcode(&self) -> Result<OpCode> {
@@ -129,6 +469,13 @@ impl VM {
     }
 
     fn addr_for(&self, operand: i64) -> Result<i64> {
+        if let Some(decoded) = &self.current {
+            return match decoded.modes[operand as usize] {
+                asm::Mode::Position => self.peek(self.pc + 1 + operand),
+                asm::Mode::Immediate => Ok(self.pc + 1 + operand),
+                asm::Mode::Relative => Ok(self.peek(self.pc + 1 + operand)? + self.relative_base),
+            };
+        }
         let opval = self.peek(self.pc)?;
         let divisor = 10i64.pow((operand + 2) as u32);
         let shifted = opval / divisor;
@@ -143,13 +490,15 @@ impl VM {
 
     #[cfg(feature = "debug_intcode")]
     fn debug_instr(&self, args: i64) -> Result<()> {
+        let instr = self
+            .instruction_at(self.pc)
+            .ok_or_else(|| Error::BadOpCode(self.peek(self.pc).unwrap_or_default()))?;
         print!(
-            "RB={} PC={} OpVal={} ",
+            "RB={} PC={} {:<32} Args=",
             self.relative_base,
             self.pc,
-            self.peek(self.pc)?
+            instr.to_string()
         );
-        print!("OpCode={:?} Args=", self.opcode()?);
         for arg in 0..args {
             let argval = self.peek(self.pc + 1 + arg)?;
             let argaddr = self.addr_for(arg)?;
@@ -245,55 +594,65 @@ impl VM {
         Ok(self.pc + 2)
     }
 
-    pub fn interpreter_step(&mut self, mut input: Option<i64>) -> Result<VMState> {
-        'outer: loop {
-            match self.curstate {
-                VMState::Runnable => {
-                    let new_pc = match self.opcode()? {
-                        OpCode::Add => self.run_add(),
-                        OpCode::Mul => self.run_mul(),
-                        OpCode::Input => {
-                            self.curstate = VMState::WaitingOnInput;
-                            if input.is_some() {
-                                continue 'outer;
-                            }
-                            Ok(self.pc)
-                        }
-                        OpCode::Output => {
-                            let (pc, out) = self.run_output()?;
-                            self.curstate = VMState::GaveOutput(out);
-                            Ok(pc)
-                        }
-                        OpCode::JumpIfTrue => self.run_jump_if_true(),
-                        OpCode::JumpIfFalse => self.run_jump_if_false(),
-                        OpCode::LessThan => self.run_less_than(),
-                        OpCode::Equals => self.run_equals(),
-                        OpCode::SetRelativeBase => self.run_relative_base(),
-                        OpCode::Terminate => {
-                            self.curstate = VMState::Halted;
-                            Ok(self.pc)
-                        }
-                    }?;
-                    self.pc = new_pc;
-                }
-                VMState::WaitingOnInput => {
-                    assert_eq!(self.opcode()?, OpCode::Input);
-                    if let Some(input) = input.take() {
-                        self.pc = self.run_input(input)?;
-                        self.curstate = VMState::Runnable;
-                    } else {
-                        // Do nothing, we want input, none was given
-                    }
-                }
-                VMState::GaveOutput(_) => {
-                    self.curstate = VMState::Runnable;
-                }
-                VMState::Halted => {
-                    // Do nothing
+    /// Run a single instruction, returning the state it leaves the VM in.
+    /// `input` is taken if the instruction was waiting for it.
+    pub fn step(&mut self, input: &mut Option<i64>) -> Result<VMState> {
+        if self.curstate == VMState::Halted {
+            return Ok(self.curstate);
+        }
+        self.current = self.cached(self.pc);
+        let op = match self.current {
+            Some(decoded) => decoded.op,
+            None => self.opcode()?,
+        };
+        let undo = match self.journal {
+            Some(_) => Some(self.undo_for(op, input.is_some())?),
+            None => None,
+        };
+        self.curstate = VMState::Runnable;
+        let new_pc = match op {
+            OpCode::Add => self.run_add(),
+            OpCode::Mul => self.run_mul(),
+            OpCode::Input => match input.take() {
+                Some(input) => self.run_input(input),
+                None => {
+                    // Do nothing, we want input, none was given
+                    self.curstate = VMState::WaitingOnInput;
+                    Ok(self.pc)
                 }
+            },
+            OpCode::Output => {
+                let (pc, out) = self.run_output()?;
+                self.curstate = VMState::GaveOutput(out);
+                Ok(pc)
+            }
+            OpCode::JumpIfTrue => self.run_jump_if_true(),
+            OpCode::JumpIfFalse => self.run_jump_if_false(),
+            OpCode::LessThan => self.run_less_than(),
+            OpCode::Equals => self.run_equals(),
+            OpCode::SetRelativeBase => self.run_relative_base(),
+            OpCode::Terminate => {
+                self.curstate = VMState::Halted;
+                Ok(self.pc)
             }
-            if self.curstate != VMState::Runnable {
-                break Ok(self.curstate);
+        }?;
+        self.current = None;
+        self.pc = new_pc;
+        // Going on waiting for input changes nothing worth stepping back over
+        if let (Some(journal), Some(undo)) = (&mut self.journal, undo) {
+            if undo.state != VMState::WaitingOnInput || self.curstate != VMState::WaitingOnInput {
+                journal.push(undo);
+            }
+        }
+        Ok(self.curstate)
+    }
+
+    /// Run until the VM gives output, wants input it wasn't given, or halts
+    pub fn interpreter_step(&mut self, mut input: Option<i64>) -> Result<VMState> {
+        loop {
+            let state = self.step(&mut input)?;
+            if state != VMState::Runnable {
+                break Ok(state);
             }
         }
     }
@@ -325,51 +684,169 @@ impl VM {
         self.full_interpret(&[], &mut Vec::new())
     }
 
-    /// ASCII machines run until they output a non-ASCII value
-    /// at which point that is saved and they run to termination
+    /// Talk to an ASCII machine at the terminal until it halts, returning
+    /// the last non-ASCII value it output (or 0 if none)
     pub fn run_ascii_machine(&mut self) -> Result<i64> {
-        use std::io::{Read, Write};
-        let mut ret = 0;
-        let mut input = None;
-        loop {
-            match self.interpreter_step(input.take())? {
-                VMState::Halted => break,
-                VMState::Runnable => continue,
-                VMState::GaveOutput(o) => {
-                    if o > 0 && o < 255 {
-                        std::io::stdout().write_all(&[o as u8])?;
-                        if o == b'\n' as i64 {
-                            std::io::stdout().flush()?;
-                        }
-                    } else {
-                        ret = o
-                    }
-                }
-                VMState::WaitingOnInput => {
-                    if input.is_none() {
-                        let mut ch: [u8; 1] = [0];
-                        std::io::stdin().read_exact(&mut ch)?;
-                        input = Some(ch[0] as i64);
-                    }
-                }
-            }
-        }
-
+        let mut machine = ascii::Ascii::new(self.clone()).echoed();
+        machine.run_with(&mut ascii::Terminal)?;
+        let ret = machine.values().last().copied().unwrap_or(0);
+        *self = machine.into_vm();
         Ok(ret)
     }
 }
 
+impl From<Vec<i64>> for VM {
+    fn from(ram: Vec<i64>) -> Self {
+        Self {
+            ram: paged(&ram),
+            len: ram.len(),
+            pc: 0,
+            curstate: VMState::Runnable,
+            relative_base: 0,
+            journal: None,
+            cache: None,
+            current: None,
+        }
+    }
+}
+
 impl std::str::FromStr for VM {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
         let ram: Vec<i64> =
             super::line_as_list(s).map_err(|e| Error::ParseError(format!("{}", e)))?;
-        Ok(Self {
-            ram,
-            pc: 0,
-            curstate: VMState::Runnable,
-            relative_base: 0,
-        })
+        Ok(Self::from(ram))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Add up the input until it's zero, writing each total to `total`
+    const SUM: &str = "
+top:    in [n]
+        jz [n], done
+        add [n], [total], [total]
+        jz 0, top
+done:   hlt
+n:      data 0
+total:  data 0
+";
+
+    fn sum() -> VM {
+        VM::from(asm::assemble(SUM).unwrap())
+    }
+
+    #[test]
+    fn snapshots() {
+        let mut vm = sum();
+        vm.interpreter_step(Some(5)).unwrap();
+        let snapshot = vm.snapshot();
+        let mut copy = vm.clone();
+        vm.interpreter_step(Some(6)).unwrap();
+        assert_eq!(vm.peek(14).unwrap(), 11);
+        // Writing to a shared page copies it for the writer only
+        assert_eq!(copy.peek(14).unwrap(), 5);
+        assert!(Rc::ptr_eq(&copy.ram[0], &snapshot.ram[0]));
+        copy.poke(14, 7).unwrap();
+        assert!(!Rc::ptr_eq(&copy.ram[0], &snapshot.ram[0]));
+        vm.restore(&snapshot);
+        assert_eq!(vm.peek(14).unwrap(), 5);
+        assert_eq!(vm.state_hash(), snapshot.state_hash());
+        let mut output = Vec::new();
+        vm.full_interpret(&[1, 2, 0], &mut output).unwrap();
+        assert_eq!(vm.peek(14).unwrap(), 8);
+    }
+
+    #[test]
+    fn rewinding() {
+        let mut vm = sum();
+        let start = vm.state_hash();
+        vm.enable_journal();
+        vm.full_interpret(&[3, 4, 0], &mut Vec::new()).unwrap();
+        assert_eq!(vm.state(), VMState::Halted);
+        assert_eq!(vm.peek(14).unwrap(), 7);
+        // Back over the halt, the jump to it, reading the 0, and waiting
+        // for it
+        assert_eq!(vm.rewind(4).unwrap(), 4);
+        assert_eq!(
+            (vm.pc(), vm.peek(13).unwrap(), vm.peek(14).unwrap()),
+            (0, 4, 7)
+        );
+        assert_eq!(vm.state(), VMState::Runnable);
+        // and the jump back to the top, and adding the 4
+        assert_eq!(vm.rewind(2).unwrap(), 2);
+        assert_eq!((vm.pc(), vm.peek(14).unwrap()), (5, 3));
+        let left = vm.journal_len();
+        assert_eq!(vm.rewind(100).unwrap(), left);
+        assert!(!vm.step_back().unwrap());
+        assert_eq!(vm.state_hash(), start);
+    }
+
+    #[test]
+    fn hashing() {
+        // The same total by different routes is the same state
+        let (mut a, mut b) = (sum(), sum());
+        a.full_interpret(&[2, 2], &mut Vec::new()).unwrap_err();
+        b.full_interpret(&[3, 1], &mut Vec::new()).unwrap_err();
+        assert_ne!(a.ram(), b.ram());
+        a.poke(13, 1).unwrap();
+        assert_eq!(a.state_hash(), b.state_hash());
+        // Unused memory is zero whether or not it's been touched
+        b.poke(5000, 0).unwrap();
+        assert_eq!(a.state_hash(), b.state_hash());
+        b.poke(5000, 1).unwrap();
+        assert_ne!(a.state_hash(), b.state_hash());
+        // The hash mustn't change between runs (or versions of Rust)
+        assert_eq!(sum().state_hash(), 0x2586_0209_81de_7db4);
+    }
+
+    #[test]
+    fn predecoding() {
+        // Run an add, then turn it into a mul and change its immediate
+        // (at 2), and run it again
+        let program = asm::assemble(
+            "
+target: add [a], 3, [a]
+        eq [target], 1002, [t]
+        jnz [t], done
+        add [target], 1, [target]
+        add [2], 4, [2]
+        jz 0, target
+done:   out [a]
+        hlt
+a:      data 5
+t:      data 0
+",
+        )
+        .unwrap();
+        for vm in &[VM::from(program.clone()), VM::from(program).predecoded()] {
+            let mut vm = vm.clone();
+            let mut output = Vec::new();
+            vm.full_interpret(&[], &mut output).unwrap();
+            assert_eq!(output, [56]);
+        }
+    }
+
+    #[test]
+    fn predecoded_inputs() {
+        // Days 5 and 9 check every instruction and mode between them
+        for &(day, input) in &[(5, 1), (5, 5), (9, 1), (9, 2)] {
+            let path = aoc_common::locate::default_input_path(2019, day).unwrap();
+            let program: Vec<i64> = std::fs::read_to_string(path)
+                .unwrap()
+                .trim()
+                .split(',')
+                .map(|v| v.parse().unwrap())
+                .collect();
+            let (mut plain, mut fast) = (VM::from(program.clone()), VM::from(program).predecoded());
+            let (mut plain_out, mut fast_out) = (Vec::new(), Vec::new());
+            plain.full_interpret(&[input], &mut plain_out).unwrap();
+            fast.full_interpret(&[input], &mut fast_out).unwrap();
+            assert_eq!(plain_out, fast_out, "day {} with {}", day, input);
+            assert_eq!(plain.state_hash(), fast.state_hash());
+        }
     }
 }
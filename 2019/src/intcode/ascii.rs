@@ -0,0 +1,318 @@
+//! Talking to Intcode programs in ASCII
+//!
+//! Days 17, 21 and 25 run programs which print lines of text, read lines of
+//! commands, and sometimes output a value too big to be ASCII which is the
+//! actual answer.  An [`Ascii`] adapter runs a VM a line at a time, feeding
+//! it lines from a [`LineSource`] (an iterator, a script file, or the
+//! terminal), keeps a [`Line`] by line transcript of the conversation, and
+//! collects the non-ASCII values separately.
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use super::{Result, VMState, VM};
+
+/// Somewhere lines of input come from
+pub trait LineSource {
+    /// The next line, without its newline, or `None` if there are no more
+    fn next_line(&mut self) -> Result<Option<String>>;
+}
+
+/// Lines from an iterator, see [`lines`]
+#[derive(Debug, Clone)]
+pub struct Lines<I>(I);
+
+impl<I, S> LineSource for Lines<I>
+where
+    I: Iterator<Item = S>,
+    S: Into<String>,
+{
+    fn next_line(&mut self) -> Result<Option<String>> {
+        Ok(self.0.next().map(Into::into))
+    }
+}
+
+/// Input from anything which gives lines
+pub fn lines<I, S>(lines: I) -> Lines<I::IntoIter>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    Lines(lines.into_iter())
+}
+
+/// Input from a script file, one line per line, skipping blank lines and
+/// `#` comments
+pub fn script<P: AsRef<Path>>(path: P) -> Result<Lines<std::vec::IntoIter<String>>> {
+    let script = std::fs::read_to_string(path)?;
+    let script: Vec<_> = script
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !(line.trim().is_empty() || line.trim_start().starts_with('#')))
+        .map(String::from)
+        .collect();
+    Ok(lines(script))
+}
+
+/// Input typed at the terminal, until end of file
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Terminal;
+
+impl LineSource for Terminal {
+    fn next_line(&mut self) -> Result<Option<String>> {
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(&['\r', '\n'][..]).to_string()))
+    }
+}
+
+/// One line of a transcript
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    /// The program printed this.  If it didn't end in a newline (a prompt,
+    /// say) it's because the program then asked for input, or halted.
+    Output(String),
+    /// And was given this
+    Input(String),
+}
+
+/// Why [`Ascii::run`] stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// The program wants a line of input which it hasn't been given
+    WaitingOnInput,
+    Halted,
+}
+
+/// An ASCII conversation with a VM
+#[derive(Debug, Clone)]
+pub struct Ascii {
+    vm: VM,
+    input: VecDeque<i64>,
+    /// The line being printed
+    partial: String,
+    /// Output since [`Ascii::take_output`] was last called
+    output: String,
+    transcript: Vec<Line>,
+    values: Vec<i64>,
+    echo: bool,
+}
+
+impl Ascii {
+    pub fn new(vm: VM) -> Self {
+        Self {
+            vm,
+            input: VecDeque::new(),
+            partial: String::new(),
+            output: String::new(),
+            transcript: Vec::new(),
+            values: Vec::new(),
+            echo: false,
+        }
+    }
+
+    /// Print the conversation to stdout as it happens, as if it were being
+    /// had at the terminal
+    pub fn echoed(mut self) -> Self {
+        self.echo = true;
+        self
+    }
+
+    pub fn vm(&self) -> &VM {
+        &self.vm
+    }
+
+    pub fn vm_mut(&mut self) -> &mut VM {
+        &mut self.vm
+    }
+
+    pub fn into_vm(self) -> VM {
+        self.vm
+    }
+
+    /// Everything said so far
+    pub fn transcript(&self) -> &[Line] {
+        &self.transcript
+    }
+
+    /// The values output which weren't ASCII, in order
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// The text output since this was last called
+    pub fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+
+    fn echo(&self, text: &str) -> Result<()> {
+        if self.echo {
+            let mut stdout = std::io::stdout();
+            stdout.write_all(text.as_bytes())?;
+            stdout.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Queue up a line of input, a newline is added
+    pub fn send_line(&mut self, line: &str) -> Result<()> {
+        let line = line.trim_end_matches('\n');
+        self.echo(line)?;
+        self.echo("\n")?;
+        self.transcript.push(Line::Input(line.to_string()));
+        self.input
+            .extend(line.bytes().chain(Some(b'\n')).map(i64::from));
+        Ok(())
+    }
+
+    fn end_line(&mut self) {
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            self.transcript.push(Line::Output(line));
+        }
+    }
+
+    /// Run until the program halts, or wants input which hasn't been queued
+    pub fn run(&mut self) -> Result<Stop> {
+        let mut input = None;
+        loop {
+            match self.vm.interpreter_step(input.take())? {
+                VMState::Runnable => {}
+                VMState::GaveOutput(value) => match u8::try_from(value) {
+                    Ok(ch) if ch.is_ascii() => {
+                        let ch = char::from(ch);
+                        self.output.push(ch);
+                        self.echo(ch.encode_utf8(&mut [0; 4]))?;
+                        if ch == '\n' {
+                            let line = std::mem::take(&mut self.partial);
+                            self.transcript.push(Line::Output(line));
+                        } else {
+                            self.partial.push(ch);
+                        }
+                    }
+                    _ => self.values.push(value),
+                },
+                VMState::WaitingOnInput => match self.input.pop_front() {
+                    Some(value) => input = Some(value),
+                    None => {
+                        self.end_line();
+                        break Ok(Stop::WaitingOnInput);
+                    }
+                },
+                VMState::Halted => {
+                    self.end_line();
+                    break Ok(Stop::Halted);
+                }
+            }
+        }
+    }
+
+    /// Run, giving the program lines from `source` whenever it wants them,
+    /// until it halts or `source` runs dry
+    pub fn run_with(&mut self, source: &mut dyn LineSource) -> Result<Stop> {
+        loop {
+            if self.run()? == Stop::Halted {
+                break Ok(Stop::Halted);
+            }
+            match source.next_line()? {
+                Some(line) => self.send_line(&line)?,
+                None => break Ok(Stop::WaitingOnInput),
+            }
+        }
+    }
+}
+
+/// A transcript as it'd have looked at the terminal
+impl fmt::Display for Ascii {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.transcript {
+            match line {
+                Line::Output(line) | Line::Input(line) => writeln!(f, "{}", line)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm;
+
+    /// Ask for a name, say hello, and output how long it was
+    const HELLO: &str = "
+        out 78
+        out 97
+        out 109
+        out 101
+        out 63
+        out 10
+read:   in [ch]
+        eq [ch], 10, [t]
+        jnz [t], done
+        add [len], 1, [len]
+        jz 0, read
+done:   out 72
+        out 105
+        out 10
+        mul [len], 1000, [len]
+        out [len]
+        hlt
+ch:     data 0
+t:      data 0
+len:    data 0
+";
+
+    #[test]
+    fn conversation() {
+        let vm = VM::from(asm::assemble(HELLO).unwrap());
+        let mut ascii = Ascii::new(vm.clone());
+        assert_eq!(ascii.run().unwrap(), Stop::WaitingOnInput);
+        assert_eq!(ascii.take_output(), "Name?\n");
+        assert_eq!(
+            ascii.run_with(&mut lines(vec!["Intcode"])).unwrap(),
+            Stop::Halted
+        );
+        assert_eq!(ascii.take_output(), "Hi\n");
+        assert_eq!(ascii.values(), [7000]);
+        assert_eq!(
+            ascii.transcript(),
+            [
+                Line::Output("Name?".into()),
+                Line::Input("Intcode".into()),
+                Line::Output("Hi".into())
+            ]
+        );
+        assert_eq!(ascii.to_string(), "Name?\nIntcode\nHi\n");
+        // Running out of input leaves it waiting
+        let mut ascii = Ascii::new(vm);
+        let mut nothing = lines(Vec::<String>::new());
+        assert_eq!(ascii.run_with(&mut nothing).unwrap(), Stop::WaitingOnInput);
+    }
+
+    #[test]
+    fn springdroid() {
+        let path = aoc_common::locate::default_input_path(2019, 21).unwrap();
+        let input = std::fs::read_to_string(path).unwrap();
+        let vm: VM = input.parse().unwrap();
+        let mut ascii = Ascii::new(vm);
+        let walk = ["OR A T", "AND B T", "AND C T", "NOT T J", "AND D J", "WALK"];
+        assert_eq!(
+            ascii.run_with(&mut lines(walk.iter().copied())).unwrap(),
+            Stop::Halted
+        );
+        assert_eq!(ascii.values(), [19_349_530]);
+        assert_eq!(
+            ascii.transcript()[..2],
+            [
+                Line::Output("Input instructions:".into()),
+                Line::Input("OR A T".into())
+            ][..]
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! Intcode assembly
+//!
+//! A listing has one instruction per line, its mnemonic (see
+//! [`OpCode::mnemonic`]) followed by its parameters, separated by commas and
+//! written according to their mode:
+//!
+//! ```text
+//! 12        immediate
+//! [12]      position
+//! [rb+12]   relative ([rb-12] and [rb] also work)
+//! ```
+//!
+//! Lines may start with `label:`, which can then be used anywhere a number
+//! can, and `data` puts its values into the program as they are.  Anything
+//! after a `;` is a comment.  For example, counting down from the input:
+//!
+//! ```text
+//!         in [n]
+//! loop:   out [n]
+//!         add [n], -1, [n]
+//!         jnz [n], loop
+//!         hlt
+//! n:      data 0
+//! ```
+//!
+//! Programs don't say which parts of them are code, so the disassembler
+//! reads everything which decodes as an instruction as one, and the rest as
+//! data.  Data which happens to look like instructions comes out as
+//! instructions, but either way it assembles back to the same program.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::{Error, OpCode, Result};
+
+/// How many values go on each line of `data`
+const DATA_PER_LINE: usize = 8;
+/// The column the address comments start at in a listing
+const COMMENT_COLUMN: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Position = 0,
+    Immediate = 1,
+    Relative = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub mode: Mode,
+    pub value: i64,
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.mode {
+            Mode::Immediate => write!(f, "{}", self.value),
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Relative if self.value == 0 => write!(f, "[rb]"),
+            Mode::Relative if self.value < 0 => write!(f, "[rb{}]", self.value),
+            Mode::Relative => write!(f, "[rb+{}]", self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub op: OpCode,
+    pub params: Vec<Param>,
+}
+
+impl Instruction {
+    /// How many values the instruction takes up
+    pub fn size(&self) -> usize {
+        1 + self.params.len()
+    }
+
+    pub fn encode(&self) -> Vec<i64> {
+        let modes = self
+            .params
+            .iter()
+            .rev()
+            .fold(0, |modes, param| modes * 10 + param.mode as i64);
+        std::iter::once(modes * 100 + self.op as i64)
+            .chain(self.params.iter().map(|param| param.value))
+            .collect()
+    }
+
+    /// Where the instruction jumps to, if that doesn't depend on memory
+    pub fn jump_target(&self) -> Option<i64> {
+        match (self.op, self.params.get(1)) {
+            (OpCode::JumpIfTrue, Some(param)) | (OpCode::JumpIfFalse, Some(param))
+                if param.mode == Mode::Immediate =>
+            {
+                Some(param.value)
+            }
+            _ => None,
+        }
+    }
+
+    fn render(&self, labels: &HashSet<i64>) -> String {
+        let params = self
+            .params
+            .iter()
+            .enumerate()
+            .map(|(n, param)| match self.jump_target() {
+                Some(target) if n == 1 && labels.contains(&target) => label(target),
+                _ => param.to_string(),
+            });
+        std::iter::once(self.op.mnemonic().to_string())
+            .chain(std::iter::once(params.collect::<Vec<_>>().join(", ")))
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(&HashSet::new()))
+    }
+}
+
+/// The instruction at `addr`, if there's a valid one there.  Opcodes with
+/// modes for parameters they don't have aren't valid, so that every
+/// instruction encodes back to exactly what it was decoded from.
+pub fn decode(ram: &[i64], addr: usize) -> Option<Instruction> {
+    let opval = *ram.get(addr)?;
+    if opval < 1 {
+        return None;
+    }
+    let op = OpCode::convert(opval).ok()?;
+    let mut modes = opval / 100;
+    let params = (0..op.params())
+        .map(|n| {
+            let mode = match modes % 10 {
+                0 => Mode::Position,
+                1 => Mode::Immediate,
+                2 => Mode::Relative,
+                _ => return None,
+            };
+            modes /= 10;
+            Some(Param {
+                mode,
+                value: *ram.get(addr + 1 + n)?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    (modes == 0).then_some(Instruction { op, params })
+}
+
+fn label(addr: i64) -> String {
+    format!("l{}", addr)
+}
+
+enum Item {
+    Instr(Instruction),
+    Data(i64),
+}
+
+/// Turn a program into a listing which [`assemble`] turns back into it
+pub fn disassemble(ram: &[i64]) -> String {
+    let mut items = Vec::new();
+    let mut addr = 0;
+    while addr < ram.len() {
+        match decode(ram, addr) {
+            Some(instr) => {
+                let size = instr.size();
+                items.push((addr, Item::Instr(instr)));
+                addr += size;
+            }
+            None => {
+                items.push((addr, Item::Data(ram[addr])));
+                addr += 1;
+            }
+        }
+    }
+    // Jumps into the middle of an instruction keep their number
+    let starts: HashSet<i64> = items.iter().map(|&(addr, _)| addr as i64).collect();
+    let labels: HashSet<i64> = items
+        .iter()
+        .filter_map(|(_, item)| match item {
+            Item::Instr(instr) => instr.jump_target(),
+            Item::Data(_) => None,
+        })
+        .filter(|target| starts.contains(target))
+        .collect();
+
+    // Each instruction gets a line, data is gathered up
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut data_run = 0;
+    for (addr, item) in &items {
+        let labelled = labels.contains(&(*addr as i64));
+        let body = match item {
+            Item::Instr(instr) => {
+                data_run = 0;
+                instr.render(&labels)
+            }
+            Item::Data(value) => {
+                if data_run > 0 && data_run < DATA_PER_LINE && !labelled {
+                    data_run += 1;
+                    lines.last_mut().unwrap().1 += &format!(", {}", value);
+                    continue;
+                }
+                data_run = 1;
+                format!("data {}", value)
+            }
+        };
+        let name = if labelled {
+            label(*addr as i64) + ":"
+        } else {
+            String::new()
+        };
+        lines.push((*addr, format!("{:<7} {}", name, body)));
+    }
+    lines
+        .into_iter()
+        .map(|(addr, line)| format!("{:<width$} ; {}\n", line, addr, width = COMMENT_COLUMN - 1))
+        .collect()
+}
+
+/// What a label or a number is
+fn value(s: &str, labels: &HashMap<&str, i64>) -> std::result::Result<i64, String> {
+    s.parse().or_else(|_| {
+        labels
+            .get(s)
+            .copied()
+            .ok_or_else(|| format!("Unknown label {:?}", s))
+    })
+}
+
+fn param(s: &str, labels: &HashMap<&str, i64>) -> std::result::Result<Param, String> {
+    let inner = match s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        Some(inner) => inner.split_whitespace().collect::<String>(),
+        None => {
+            return Ok(Param {
+                mode: Mode::Immediate,
+                value: value(s, labels)?,
+            })
+        }
+    };
+    let (mode, value) = if inner == "rb" {
+        (Mode::Relative, 0)
+    } else if let Some(ofs) = inner.strip_prefix("rb+") {
+        (Mode::Relative, value(ofs, labels)?)
+    } else if let Some(ofs) = inner.strip_prefix("rb-") {
+        (Mode::Relative, -value(ofs, labels)?)
+    } else {
+        (Mode::Position, value(&inner, labels)?)
+    };
+    Ok(Param { mode, value })
+}
+
+fn is_label(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && s != "rb"
+}
+
+/// Turn a listing (see above) into a program
+pub fn assemble(listing: &str) -> Result<Vec<i64>> {
+    // Find every label's address first, so that labels can be used before
+    // they're defined
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut addr = 0;
+    for (n, line) in listing.lines().enumerate() {
+        let err = |msg| Error::AsmError(n + 1, msg);
+        let mut line = line.split(';').next().unwrap_or_default().trim();
+        while let Some((name, rest)) = line.split_once(':') {
+            let name = name.trim();
+            if !is_label(name) {
+                return Err(err(format!("Bad label {:?}", name)));
+            }
+            if labels.insert(name, addr).is_some() {
+                return Err(err(format!("Label {:?} defined twice", name)));
+            }
+            line = rest.trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let (mnemonic, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args: Vec<&str> = match rest.trim() {
+            "" => Vec::new(),
+            rest => rest.split(',').map(str::trim).collect(),
+        };
+        let op = if mnemonic == "data" {
+            None
+        } else {
+            let op = OpCode::ALL
+                .iter()
+                .copied()
+                .find(|op| op.mnemonic() == mnemonic)
+                .ok_or_else(|| err(format!("Unknown instruction {:?}", mnemonic)))?;
+            if args.len() != op.params() {
+                return Err(err(format!(
+                    "{} takes {} parameters, not {}",
+                    mnemonic,
+                    op.params(),
+                    args.len()
+                )));
+            }
+            Some(op)
+        };
+        addr += args.len() as i64 + i64::from(op.is_some());
+        statements.push((n + 1, op, args));
+    }
+
+    let mut ram = Vec::new();
+    for (line, op, args) in statements {
+        let err = |msg| Error::AsmError(line, msg);
+        match op {
+            Some(op) => {
+                let params = args
+                    .iter()
+                    .map(|arg| param(arg, &labels))
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(err)?;
+                ram.extend(Instruction { op, params }.encode());
+            }
+            None => {
+                for arg in args {
+                    ram.push(value(arg, &labels).map_err(err)?);
+                }
+            }
+        }
+    }
+    Ok(ram)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::VM;
+
+    const COUNTDOWN: &str = "
+        in [n]
+loop:   out [n]
+        add [n], -1, [n]
+        jnz [n], loop   ; until it's zero
+        hlt
+n:      data 0
+";
+
+    #[test]
+    fn assembling() {
+        let ram = assemble(COUNTDOWN).unwrap();
+        assert_eq!(ram, [3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, 0]);
+        let mut output = Vec::new();
+        VM::from(ram).full_interpret(&[3], &mut output).unwrap();
+        assert_eq!(output, [3, 2, 1]);
+
+        let line = |res: Result<Vec<i64>>| match res {
+            Err(Error::AsmError(line, _)) => line,
+            other => panic!("Expected an error, got {:?}", other),
+        };
+        assert_eq!(line(assemble("hlt\njmp 4")), 2);
+        assert_eq!(line(assemble("add 1, 2")), 1);
+        assert_eq!(line(assemble("x: hlt\nx: hlt")), 2);
+        assert_eq!(line(assemble("hlt\n\njz 0, nowhere")), 3);
+        assert_eq!(line(assemble("out [rb*2]")), 1);
+    }
+
+    #[test]
+    fn disassembling() {
+        let ram = [109, -3, 21101, 1, 2, 0, 1006, 5, 11, 204, -1, 99, 0, 7];
+        let listing = disassemble(&ram);
+        let lines: Vec<&str> = listing.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines,
+            [
+                "        arb -3                          ; 0",
+                "        add 1, 2, [rb]                  ; 2",
+                "        jz [5], l11                     ; 6",
+                "        out [rb-1]                      ; 9",
+                "l11:    hlt                             ; 11",
+                "        data 0, 7                       ; 12",
+            ]
+        );
+        assert_eq!(assemble(&listing).unwrap(), ram);
+    }
+
+    #[test]
+    fn round_trip_inputs() {
+        // Every day which runs an Intcode program
+        for day in &[2, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25] {
+            let path = aoc_common::locate::default_input_path(2019, *day).unwrap();
+            let input = std::fs::read_to_string(path).unwrap();
+            let ram: Vec<i64> = input
+                .trim()
+                .split(',')
+                .map(|v| v.parse().unwrap())
+                .collect();
+            let listing = disassemble(&ram);
+            assert_eq!(assemble(&listing).unwrap(), ram, "day {}", day);
+        }
+    }
+}
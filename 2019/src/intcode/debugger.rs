@@ -0,0 +1,540 @@
+//! An interactive debugger for Intcode programs
+//!
+//! The debugger reads commands a line at a time (see [`HELP`]), so it can be
+//! driven by hand or by a script of commands.  Anything the program reads
+//! comes from a queue which commands fill up in advance, so programs which
+//! want a lot of input (such as day 25's adventure) can be fed it in one go.
+//!
+//! The VM keeps a journal while being debugged, so that `back` can step back
+//! over instructions.  Input they read goes back on the queue, but anything
+//! they output has already been shown.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io::{BufRead, Write};
+
+use super::{Error, Result, VMState, VM};
+
+pub const HELP: &str = "\
+step [N]           run N instructions (default 1)
+back [N]           step back over N instructions (default 1)
+continue           run until a breakpoint, watchpoint, or the program needs input or halts
+output             as continue, but stop after the next output too
+break [ADDR]       set a breakpoint at ADDR, or list them
+delete ADDR        remove the breakpoint at ADDR
+watch [ADDR]       stop whenever the value at ADDR changes, or list watchpoints
+unwatch ADDR       stop watching ADDR
+peek ADDR [N]      show N values from ADDR (default 1)
+poke ADDR VALUE    change the value at ADDR
+rb [VALUE]         show or change the relative base
+dis [ADDR] [N]     disassemble N instructions from ADDR (default 10 from pc)
+input N...         queue numbers as input
+type TEXT          queue TEXT and a newline as ASCII input
+ascii [on|off]     show output as text where it's ASCII (the default) or as numbers
+status             show where the program is up to
+help               show this
+quit";
+
+/// Why running the program stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// We're about to run the instruction at this address
+    Breakpoint(i64),
+    Watchpoint {
+        addr: i64,
+        old: i64,
+        new: i64,
+    },
+    Output(i64),
+    /// The program wants input and the queue is empty
+    WaitingOnInput,
+    Halted,
+    /// We ran as many instructions as we were asked to
+    Stepped,
+}
+
+/// What the debugger needs to step back over an entry in the VM's journal
+#[derive(Debug, Clone, Copy)]
+struct Stepped {
+    /// The input the instruction read, to be read again
+    input: Option<i64>,
+    /// Whether it was counted in `steps`
+    counted: bool,
+}
+
+pub struct Debugger {
+    vm: VM,
+    breakpoints: BTreeSet<i64>,
+    /// The last value seen at each watched address
+    watches: BTreeMap<i64, i64>,
+    input: VecDeque<i64>,
+    ascii: bool,
+    /// Whether ASCII output has left us partway along a line
+    mid_line: bool,
+    steps: u64,
+    /// One entry for each entry in the VM's journal
+    history: Vec<Stepped>,
+}
+
+impl Debugger {
+    pub fn new(mut vm: VM) -> Self {
+        vm.enable_journal();
+        Self {
+            vm,
+            breakpoints: BTreeSet::new(),
+            watches: BTreeMap::new(),
+            input: VecDeque::new(),
+            ascii: true,
+            mid_line: false,
+            steps: 0,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn vm(&self) -> &VM {
+        &self.vm
+    }
+
+    pub fn vm_mut(&mut self) -> &mut VM {
+        &mut self.vm
+    }
+
+    pub fn add_breakpoint(&mut self, addr: i64) {
+        self.breakpoints.insert(addr);
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: i64) -> bool {
+        self.breakpoints.remove(&addr)
+    }
+
+    pub fn watch(&mut self, addr: i64) -> Result<()> {
+        let value = self.vm.peek(addr)?;
+        self.watches.insert(addr, value);
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, addr: i64) -> bool {
+        self.watches.remove(&addr).is_some()
+    }
+
+    pub fn queue_input<I: IntoIterator<Item = i64>>(&mut self, values: I) {
+        self.input.extend(values);
+    }
+
+    pub fn queue_ascii(&mut self, text: &str) {
+        self.queue_input(text.bytes().map(i64::from));
+    }
+
+    /// How many instructions have run
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    fn show_output<W: Write>(&mut self, value: i64, out: &mut W) -> Result<()> {
+        match value {
+            0..=127 if self.ascii => {
+                write!(out, "{}", value as u8 as char)?;
+                self.mid_line = value != i64::from(b'\n');
+            }
+            _ => {
+                self.end_line(out)?;
+                writeln!(out, "output: {}", value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_line<W: Write>(&mut self, out: &mut W) -> Result<()> {
+        if self.mid_line {
+            writeln!(out)?;
+            self.mid_line = false;
+        }
+        Ok(())
+    }
+
+    /// Run one instruction, showing anything it outputs, and say if that
+    /// was somewhere to stop
+    fn step_once<W: Write>(&mut self, out: &mut W) -> Result<Option<Stop>> {
+        let offered = self.input.pop_front();
+        let mut input = offered;
+        let journalled = self.vm.journal_len();
+        let state = self.vm.step(&mut input)?;
+        if let Some(unused) = input {
+            self.input.push_front(unused);
+        }
+        let counted = !matches!(state, VMState::WaitingOnInput | VMState::Halted);
+        if self.vm.journal_len() > journalled {
+            self.history.push(Stepped {
+                input: if input.is_none() { offered } else { None },
+                counted,
+            });
+        }
+        let stop = match state {
+            VMState::Runnable => None,
+            VMState::WaitingOnInput => return Ok(Some(Stop::WaitingOnInput)),
+            VMState::Halted => return Ok(Some(Stop::Halted)),
+            VMState::GaveOutput(value) => {
+                self.show_output(value, out)?;
+                Some(Stop::Output(value))
+            }
+        };
+        self.steps += 1;
+        for (&addr, old) in self.watches.iter_mut() {
+            let new = self.vm.peek(addr)?;
+            if new != *old {
+                let stop = Stop::Watchpoint {
+                    addr,
+                    old: *old,
+                    new,
+                };
+                *old = new;
+                return Ok(Some(stop));
+            }
+        }
+        Ok(stop)
+    }
+
+    /// Run `count` instructions, or fewer if the program stops or changes a
+    /// watched value
+    pub fn step<W: Write>(&mut self, count: u64, out: &mut W) -> Result<Stop> {
+        for _ in 0..count {
+            match self.step_once(out)? {
+                None | Some(Stop::Output(_)) => {}
+                Some(stop) => return Ok(stop),
+            }
+        }
+        Ok(Stop::Stepped)
+    }
+
+    /// Step back over up to `count` instructions, returning how many were
+    /// stepped back over
+    pub fn back(&mut self, count: usize) -> Result<usize> {
+        let undone = self.vm.rewind(count)?;
+        let first = self.history.len().saturating_sub(undone);
+        for stepped in self.history.drain(first..).rev() {
+            if let Some(value) = stepped.input {
+                self.input.push_front(value);
+            }
+            if stepped.counted {
+                self.steps -= 1;
+            }
+        }
+        // Going back isn't a change worth stopping for
+        for (&addr, old) in self.watches.iter_mut() {
+            *old = self.vm.peek(addr)?;
+        }
+        Ok(undone)
+    }
+
+    /// Run until something stops us, which with `to_output` includes the
+    /// program outputting anything
+    pub fn run<W: Write>(&mut self, to_output: bool, out: &mut W) -> Result<Stop> {
+        loop {
+            match self.step_once(out)? {
+                None => {}
+                Some(Stop::Output(_)) if !to_output => {}
+                Some(stop) => return Ok(stop),
+            }
+            if self.breakpoints.contains(&self.vm.pc()) {
+                return Ok(Stop::Breakpoint(self.vm.pc()));
+            }
+        }
+    }
+
+    fn show_instruction<W: Write>(&self, addr: i64, out: &mut W) -> Result<usize> {
+        let marker = if addr == self.vm.pc() { "=>" } else { "  " };
+        match self.vm.instruction_at(addr) {
+            Some(instr) => {
+                writeln!(out, "{} {:>5}: {}", marker, addr, instr)?;
+                Ok(instr.size())
+            }
+            None => {
+                writeln!(out, "{} {:>5}: data {}", marker, addr, self.vm.peek(addr)?)?;
+                Ok(1)
+            }
+        }
+    }
+
+    fn show_stop<W: Write>(&mut self, stop: Stop, out: &mut W) -> Result<()> {
+        self.end_line(out)?;
+        match stop {
+            Stop::Breakpoint(addr) => writeln!(out, "breakpoint at {}", addr)?,
+            Stop::Watchpoint { addr, old, new } => {
+                writeln!(out, "[{}] changed from {} to {}", addr, old, new)?
+            }
+            Stop::Output(_) | Stop::Stepped => {}
+            Stop::WaitingOnInput => writeln!(out, "waiting for input")?,
+            Stop::Halted => {
+                writeln!(out, "halted after {} steps", self.steps)?;
+                return Ok(());
+            }
+        }
+        self.show_instruction(self.vm.pc(), out)?;
+        Ok(())
+    }
+
+    /// Carry out one command, returning false when it's time to quit
+    pub fn command<W: Write>(&mut self, line: &str, out: &mut W) -> Result<bool> {
+        let (cmd, rest) = line
+            .trim()
+            .split_once(char::is_whitespace)
+            .unwrap_or((line.trim(), ""));
+        let rest = rest.trim();
+        let args = rest
+            .split_whitespace()
+            .map(|arg| {
+                arg.parse::<i64>()
+                    .map_err(|_| Error::ParseError(format!("Bad number {:?}", arg)))
+            })
+            .collect::<Result<Vec<_>>>();
+        match (cmd, args) {
+            ("", _) => {}
+            ("quit" | "q", _) => return Ok(false),
+            ("help" | "h", _) => writeln!(out, "{}", HELP)?,
+            ("type" | "t", _) => {
+                self.queue_ascii(rest);
+                self.queue_ascii("\n");
+            }
+            ("ascii", _) => match rest {
+                "" | "on" => self.ascii = true,
+                "off" => self.ascii = false,
+                _ => writeln!(out, "ascii on or ascii off?")?,
+            },
+            (_, Err(e)) => writeln!(out, "{}", e)?,
+            ("step" | "s", Ok(args)) if args.len() <= 1 => {
+                let count = args.first().copied().unwrap_or(1).max(0) as u64;
+                let stop = self.step(count, out)?;
+                self.show_stop(stop, out)?;
+            }
+            ("back", Ok(args)) if args.len() <= 1 => {
+                let count = args.first().copied().unwrap_or(1).max(0) as usize;
+                let undone = self.back(count)?;
+                if undone < count {
+                    writeln!(out, "only {} steps to go back over", undone)?;
+                }
+                self.show_instruction(self.vm.pc(), out)?;
+            }
+            ("continue" | "c", Ok(args)) if args.is_empty() => {
+                let stop = self.run(false, out)?;
+                self.show_stop(stop, out)?;
+            }
+            ("output" | "o", Ok(args)) if args.is_empty() => {
+                let stop = self.run(true, out)?;
+                self.show_stop(stop, out)?;
+            }
+            ("break" | "b", Ok(args)) => {
+                if args.is_empty() {
+                    for addr in &self.breakpoints {
+                        self.show_instruction(*addr, out)?;
+                    }
+                }
+                for addr in args {
+                    self.add_breakpoint(addr);
+                }
+            }
+            ("delete" | "d", Ok(args)) => {
+                for addr in args {
+                    if !self.remove_breakpoint(addr) {
+                        writeln!(out, "no breakpoint at {}", addr)?;
+                    }
+                }
+            }
+            ("watch" | "w", Ok(args)) => {
+                if args.is_empty() {
+                    for (addr, value) in &self.watches {
+                        writeln!(out, "[{}] = {}", addr, value)?;
+                    }
+                }
+                for addr in args {
+                    self.watch(addr)?;
+                }
+            }
+            ("unwatch", Ok(args)) => {
+                for addr in args {
+                    if !self.unwatch(addr) {
+                        writeln!(out, "not watching {}", addr)?;
+                    }
+                }
+            }
+            ("peek" | "p", Ok(args)) if (1..=2).contains(&args.len()) => {
+                let count = args.get(1).copied().unwrap_or(1);
+                let values = (args[0]..args[0] + count)
+                    .map(|addr| self.vm.peek(addr).map(|v| v.to_string()))
+                    .collect::<Result<Vec<_>>>()?;
+                writeln!(out, "[{}] {}", args[0], values.join(", "))?;
+            }
+            ("poke", Ok(args)) if args.len() == 2 => {
+                self.vm.poke(args[0], args[1])?;
+                // Changing a watched value ourselves isn't worth stopping for
+                if let Some(old) = self.watches.get_mut(&args[0]) {
+                    *old = args[1];
+                }
+            }
+            ("rb", Ok(args)) if args.len() <= 1 => match args.first() {
+                Some(&value) => self.vm.set_relative_base(value),
+                None => writeln!(out, "rb = {}", self.vm.relative_base())?,
+            },
+            ("dis", Ok(args)) if args.len() <= 2 => {
+                let mut addr = args.first().copied().unwrap_or_else(|| self.vm.pc());
+                for _ in 0..args.get(1).copied().unwrap_or(10) {
+                    addr += self.show_instruction(addr, out)? as i64;
+                }
+            }
+            ("input" | "i", Ok(args)) => self.queue_input(args),
+            ("status", Ok(args)) if args.is_empty() => {
+                writeln!(
+                    out,
+                    "pc = {}, rb = {}, {:?} after {} steps, {} values of input queued",
+                    self.vm.pc(),
+                    self.vm.relative_base(),
+                    self.vm.state(),
+                    self.steps,
+                    self.input.len()
+                )?;
+                self.show_instruction(self.vm.pc(), out)?;
+            }
+            _ => writeln!(out, "Unknown command {:?}, try help", line.trim())?,
+        }
+        Ok(true)
+    }
+
+    /// Read and carry out commands until told to quit or there are none left,
+    /// returning false if told to quit.  With `echo` each command is shown
+    /// after its prompt, for when they come from a script.
+    pub fn repl<R: BufRead, W: Write>(
+        &mut self,
+        input: R,
+        out: &mut W,
+        echo: bool,
+    ) -> Result<bool> {
+        write!(out, "(intcode) ")?;
+        out.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            if echo {
+                writeln!(out, "{}", line)?;
+            }
+            // A bad address is worth saying so, not giving up over
+            match self.command(&line, out) {
+                Ok(true) => {}
+                Ok(false) => return Ok(false),
+                Err(Error::IOError(e)) => return Err(Error::IOError(e)),
+                Err(e) => writeln!(out, "{}", e)?,
+            }
+            write!(out, "(intcode) ")?;
+            out.flush()?;
+        }
+        writeln!(out)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm;
+
+    fn debugger(listing: &str) -> Debugger {
+        Debugger::new(VM::from(asm::assemble(listing).unwrap()))
+    }
+
+    fn run(debugger: &mut Debugger, commands: &str) -> String {
+        let mut out = Vec::new();
+        for line in commands.lines() {
+            assert!(debugger.command(line, &mut out).unwrap());
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Echo input until it's zero, counting how many values there were
+    const ECHO: &str = "
+top:    in [n]
+        jz [n], done
+        out [n]
+        add [count], 1, [count]
+        jz 0, top
+done:   hlt
+n:      data 0
+count:  data 0
+";
+
+    #[test]
+    fn stepping_and_breaking() {
+        let mut dbg = debugger(ECHO);
+        assert_eq!(
+            run(&mut dbg, "ascii off\ninput 5 6\nstep 3"),
+            "output: 5\n=>     7: add [16], 1, [16]\n"
+        );
+        assert_eq!(dbg.steps(), 3);
+        // Stop at the top of the loop, having echoed 6
+        run(&mut dbg, "break 0");
+        assert_eq!(
+            dbg.run(false, &mut Vec::new()).unwrap(),
+            Stop::Breakpoint(0)
+        );
+        assert_eq!(
+            dbg.run(false, &mut Vec::new()).unwrap(),
+            Stop::Breakpoint(0)
+        );
+        assert_eq!(dbg.vm().peek(16).unwrap(), 2);
+        // With nothing left to read it waits
+        assert_eq!(
+            dbg.run(true, &mut Vec::new()).unwrap(),
+            Stop::WaitingOnInput
+        );
+        run(&mut dbg, "delete 0\ninput 0");
+        assert_eq!(dbg.run(false, &mut Vec::new()).unwrap(), Stop::Halted);
+        assert!(!dbg.command("quit", &mut Vec::new()).unwrap());
+    }
+
+    #[test]
+    fn stepping_back() {
+        let mut dbg = debugger(ECHO);
+        assert_eq!(
+            run(&mut dbg, "ascii off\ninput 5\nstep 4"),
+            "output: 5\n=>    11: jz 0, 0\n"
+        );
+        // Back to before the count went up
+        assert_eq!(run(&mut dbg, "back"), "=>     7: add [16], 1, [16]\n");
+        assert_eq!((dbg.steps(), dbg.vm().peek(16).unwrap()), (3, 0));
+        // and to the start, which puts the 5 back to be read again
+        assert_eq!(
+            run(&mut dbg, "back 10"),
+            "only 3 steps to go back over\n=>     0: in [15]\n"
+        );
+        assert_eq!((dbg.steps(), dbg.vm().peek(15).unwrap()), (0, 0));
+        assert_eq!(
+            run(&mut dbg, "step 3"),
+            "output: 5\n=>     7: add [16], 1, [16]\n"
+        );
+    }
+
+    #[test]
+    fn watching_and_poking() {
+        let mut dbg = debugger(ECHO);
+        dbg.queue_ascii("hi");
+        let out = run(&mut dbg, "watch 16\ncontinue");
+        assert_eq!(out, "h\n[16] changed from 0 to 1\n=>    11: jz 0, 0\n");
+        assert_eq!(run(&mut dbg, "output"), "i\n=>     7: add [16], 1, [16]\n");
+        // Changing it ourselves doesn't count
+        assert_eq!(
+            run(&mut dbg, "poke 16 10\npeek 15 2\nrb 7\nrb"),
+            "[15] 105, 10\nrb = 7\n"
+        );
+        assert_eq!(
+            dbg.run(false, &mut Vec::new()).unwrap(),
+            Stop::Watchpoint {
+                addr: 16,
+                old: 10,
+                new: 11
+            }
+        );
+        assert_eq!(
+            run(&mut dbg, "bogus"),
+            "Unknown command \"bogus\", try help\n"
+        );
+        assert_eq!(
+            run(&mut dbg, "peek x"),
+            "ParseError(\"Bad number \\\"x\\\"\")\n"
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Networks of Intcode VMs
+//!
+//! Day 7 wires amplifiers together in a line and then a loop, and day 23 has
+//! computers sending each other packets.  A [`Network`] runs its nodes in
+//! turn, always in the same order, each until it wants input it hasn't got,
+//! and passes on what they output according to its [`Topology`].  Anything
+//! which leaves the network, and the network going quiet, comes back to the
+//! caller as an [`Event`] to deal with.
+
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+use super::{Error, Result, VMState, VM};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// Each node's output goes to the next, and the last node's out of the
+    /// network
+    Pipeline,
+    /// As a pipeline, but the last node's output goes back to the first too
+    Ring,
+    /// Nodes output packets of an address and two values, and read -1 when
+    /// there's nothing waiting for them
+    Packets,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet {
+    pub dest: i64,
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The last node of a pipeline or ring output this
+    Output(i64),
+    /// A packet was sent to an address with no node
+    Packet(Packet),
+    /// Nothing has moved for a while, see [`Network::set_idle_rounds`].  In
+    /// a pipeline or ring that means nothing ever will.
+    Idle,
+    /// Every node has halted
+    Halted,
+}
+
+#[derive(Debug)]
+struct Node {
+    vm: VM,
+    inbox: VecDeque<i64>,
+    /// Part of a packet
+    outbox: Vec<i64>,
+}
+
+#[derive(Debug)]
+pub struct Network {
+    nodes: Vec<Node>,
+    topology: Topology,
+    events: VecDeque<Event>,
+    idle_rounds: usize,
+    quiet_rounds: usize,
+}
+
+impl Network {
+    /// A network of `count` copies of `model`
+    pub fn new(model: &VM, count: usize, topology: Topology) -> Self {
+        let nodes = (0..count)
+            .map(|_| Node {
+                vm: model.clone(),
+                inbox: VecDeque::new(),
+                outbox: Vec::new(),
+            })
+            .collect();
+        Self {
+            nodes,
+            topology,
+            events: VecDeque::new(),
+            idle_rounds: 1,
+            quiet_rounds: 0,
+        }
+    }
+
+    pub fn vm(&self, node: usize) -> &VM {
+        &self.nodes[node].vm
+    }
+
+    /// Queue up input for a node
+    pub fn send(&mut self, node: usize, values: &[i64]) {
+        self.nodes[node].inbox.extend(values);
+    }
+
+    /// Send a packet as if a node had, to a node or out of the network
+    pub fn deliver(&mut self, packet: Packet) {
+        match usize::try_from(packet.dest)
+            .ok()
+            .and_then(|dest| self.nodes.get_mut(dest))
+        {
+            Some(node) => node.inbox.extend(&[packet.x, packet.y]),
+            None => self.events.push_back(Event::Packet(packet)),
+        }
+    }
+
+    /// How many times in a row every node has to find nothing to do before
+    /// the network counts as idle (1 to start with).  Nodes which do some
+    /// work between finding nothing to read and looking again might want
+    /// this to be more.
+    pub fn set_idle_rounds(&mut self, rounds: usize) {
+        self.idle_rounds = rounds.max(1);
+    }
+
+    fn route(&mut self, from: usize, value: i64) {
+        match self.topology {
+            Topology::Pipeline | Topology::Ring => match self.nodes.get_mut(from + 1) {
+                Some(next) => next.inbox.push_back(value),
+                None => {
+                    self.events.push_back(Event::Output(value));
+                    if self.topology == Topology::Ring {
+                        self.nodes[0].inbox.push_back(value);
+                    }
+                }
+            },
+            Topology::Packets => {
+                let outbox = &mut self.nodes[from].outbox;
+                outbox.push(value);
+                if let [dest, x, y] = outbox[..] {
+                    outbox.clear();
+                    self.deliver(Packet { dest, x, y });
+                }
+            }
+        }
+    }
+
+    /// Run a node until it wants input it hasn't got, or halts, returning
+    /// whether it read or wrote anything.  In a packet network it gets one
+    /// -1 to read before that.
+    fn run_node(&mut self, n: usize) -> Result<bool> {
+        let mut moved = false;
+        let mut polled = self.topology != Topology::Packets;
+        loop {
+            let node = &mut self.nodes[n];
+            let mut input = node.inbox.pop_front();
+            let real = input.is_some();
+            if !(real || polled) {
+                input = Some(-1);
+            }
+            let state = node.vm.step(&mut input)?;
+            match input {
+                Some(unused) if real => node.inbox.push_front(unused),
+                Some(_) => {}
+                None if real => moved = true,
+                None => polled = true,
+            }
+            match state {
+                VMState::Runnable => {}
+                VMState::WaitingOnInput | VMState::Halted => break Ok(moved),
+                VMState::GaveOutput(value) => {
+                    moved = true;
+                    self.route(n, value);
+                }
+            }
+        }
+    }
+
+    /// Run the network until something happens
+    pub fn next_event(&mut self) -> Result<Event> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+            if self
+                .nodes
+                .iter()
+                .all(|node| node.vm.state() == VMState::Halted)
+            {
+                return Ok(Event::Halted);
+            }
+            let mut moved = false;
+            for n in 0..self.nodes.len() {
+                moved |= self.run_node(n)?;
+            }
+            self.quiet_rounds = if moved { 0 } else { self.quiet_rounds + 1 };
+            if self.quiet_rounds == self.idle_rounds {
+                self.quiet_rounds = 0;
+                return Ok(Event::Idle);
+            }
+        }
+    }
+
+    /// Run until every node halts, returning everything which came out of
+    /// the network (packets as their address and values)
+    pub fn run_to_halt(&mut self) -> Result<Vec<i64>> {
+        let mut outputs = Vec::new();
+        loop {
+            match self.next_event()? {
+                Event::Output(value) => outputs.push(value),
+                Event::Packet(packet) => outputs.extend(&[packet.dest, packet.x, packet.y]),
+                Event::Idle => return Err(Error::Deadlocked),
+                Event::Halted => return Ok(outputs),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::intcode::asm;
+
+    fn vm(listing: &str) -> VM {
+        VM::from(asm::assemble(listing).unwrap())
+    }
+
+    /// Pass on one more than it's given, until that's at least 10
+    const COUNTER: &str = "
+top:    in [v]
+        add [v], 1, [v]
+        out [v]
+        lt [v], 10, [t]
+        jnz [t], top
+        hlt
+v:      data 0
+t:      data 0
+";
+
+    #[test]
+    fn pipeline_and_ring() {
+        let mut ring = Network::new(&vm(COUNTER), 3, Topology::Ring);
+        ring.send(0, &[0]);
+        assert_eq!(ring.run_to_halt().unwrap(), [3, 6, 9, 12]);
+        // In a line the last node waits forever for more
+        let mut line = Network::new(&vm(COUNTER), 3, Topology::Pipeline);
+        line.send(0, &[0]);
+        assert_eq!(line.next_event().unwrap(), Event::Output(3));
+        assert_eq!(line.next_event().unwrap(), Event::Idle);
+        assert!(matches!(line.run_to_halt(), Err(Error::Deadlocked)));
+    }
+
+    /// Read an address, then pass every packet on to the next address up,
+    /// adding the address to its y
+    const FORWARDER: &str = "
+        in [addr]
+wait:   in [x]
+        eq [x], -1, [t]
+        jnz [t], wait
+        in [y]
+        add [addr], 1, [dest]
+        add [y], [addr], [y]
+        out [dest]
+        out [x]
+        out [y]
+        jz 0, wait
+addr:   data 0
+x:      data 0
+y:      data 0
+t:      data 0
+dest:   data 0
+";
+
+    #[test]
+    fn packets() {
+        let mut net = Network::new(&vm(FORWARDER), 3, Topology::Packets);
+        for addr in 0..3 {
+            net.send(addr, &[addr as i64]);
+        }
+        assert_eq!(net.next_event().unwrap(), Event::Idle);
+        net.deliver(Packet {
+            dest: 1,
+            x: 7,
+            y: 10,
+        });
+        let packet = Packet {
+            dest: 3,
+            x: 7,
+            y: 13,
+        };
+        assert_eq!(net.next_event().unwrap(), Event::Packet(packet));
+        assert_eq!(net.next_event().unwrap(), Event::Idle);
+        net.set_idle_rounds(3);
+        net.deliver(Packet {
+            dest: 0,
+            x: 1,
+            y: 0,
+        });
+        let packet = Packet {
+            dest: 3,
+            x: 1,
+            y: 3,
+        };
+        assert_eq!(net.next_event().unwrap(), Event::Packet(packet));
+        assert_eq!(net.next_event().unwrap(), Event::Idle);
+    }
+}
@@ -1,7 +1,7 @@
-use std::fs::read_to_string;
+// The days are compiled into this crate too, see `days` below
+extern crate self as aoc2019;
 
-pub type StdResult<T, E> = std::result::Result<T, E>;
-pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+pub use aoc_common::prelude::*;
 
 pub use lazy_static::lazy_static;
 pub use regex::Regex;
@@ -30,68 +30,13 @@ pub use itertools::*;
 
 pub use modinverse::*;
 
-pub fn read_input(day: usize) -> Result<String> {
-    color_backtrace::install();
-    Ok(read_to_string(format!("inputs/day{}", day))?)
-}
-
-pub fn read_input_as_vec<T: ParseByRegex>(day: usize) -> Result<Vec<T>> {
-    let plain = read_input(day)?;
-    Ok(input_as_vec(plain)?)
-}
+/// Days which exist, but which running the whole year should leave alone
+pub const SKIPPED_DAYS: &[(u8, &str)] = &[];
 
-pub fn input_as_vec<T: ParseByRegex, S: AsRef<str>>(plain: S) -> Result<Vec<T>> {
-    let mapped: Result<Vec<T>> = plain
-        .as_ref()
-        .trim()
-        .lines()
-        .map(ParseByRegex::parse_by_regex)
-        .collect();
-    Ok(mapped?)
-}
-
-pub fn input_as_vec_and_first<T: ParseByRegex, S: AsRef<str>>(
-    plain: S,
-) -> Result<(Vec<T>, String)> {
-    let mut lines = plain.as_ref().trim().lines();
-    let first = lines.next();
-    let mapped: Result<Vec<T>> = lines
-        .filter(|s| !s.trim().is_empty()) // In case there are any blanks after the first line
-        .map(ParseByRegex::parse_by_regex)
-        .collect();
-    if first.is_none() {
-        Err("No lines at all?".into())
-    } else {
-        Ok((
-            mapped?,
-            first.expect("Something went wrong").trim().to_owned(),
-        ))
-    }
-}
-
-pub fn read_input_as_vec_and_first<T: ParseByRegex>(day: usize) -> Result<(Vec<T>, String)> {
-    let plain = read_input(day)?;
-    Ok(input_as_vec_and_first(plain)?)
-}
-
-pub fn line_as_list<T: ParseByRegex, S: AsRef<str>>(line: S) -> Result<Vec<T>> {
-    let mapped: Result<Vec<T>> = line
-        .as_ref()
-        .trim()
-        .split(',')
-        .map(ParseByRegex::parse_by_regex)
-        .collect();
-    Ok(mapped?)
-}
-
-pub fn input_as_lists<T: ParseByRegex, S: AsRef<str>>(input: S) -> Result<Vec<Vec<T>>> {
-    let mapped: Result<Vec<Vec<T>>> = input.as_ref().trim().lines().map(line_as_list).collect();
-    Ok(mapped?)
-}
-
-pub fn read_input_as_lists<T: ParseByRegex>(day: usize) -> Result<Vec<Vec<T>>> {
-    let plain = read_input(day)?;
-    Ok(input_as_lists(plain)?)
+/// Every day of 2019, generated from `src/bin` by `build.rs`
+#[cfg(not(test))]
+pub mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
 }
 
 // 2019 specific stuff
@@ -100,6 +45,7 @@ pub mod intcode;
 
 pub use std::str::FromStr;
 
+// 2019 talks about up/down/left/right, so this shadows the common `Facing`
 #[derive(Debug, Copy, Clone)]
 pub enum Facing {
     Up,
@@ -110,7 +56,7 @@ pub enum Facing {
 
 impl Facing {
     pub fn rotate_left(self) -> Self {
-        use Facing::*;
+        use self::Facing::*;
         match self {
             Up => Left,
             Down => Right,
@@ -120,7 +66,7 @@ impl Facing {
     }
 
     pub fn rotate_right(self) -> Self {
-        use Facing::*;
+        use self::Facing::*;
         match self {
             Up => Right,
             Down => Left,
@@ -133,7 +79,7 @@ impl Facing {
     where
         N: num_traits::Num,
     {
-        use Facing::*;
+        use self::Facing::*;
         match self {
             Up => (pos.0, pos.1 - N::one()),
             Down => (pos.0, pos.1 + N::one()),
@@ -175,7 +121,7 @@ where
     ]
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
     North,
     South,
@@ -197,6 +143,17 @@ impl FromStr for Direction {
     }
 }
 
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::North => "north",
+            Self::South => "south",
+            Self::East => "east",
+            Self::West => "west",
+        })
+    }
+}
+
 impl Direction {
     pub fn opposite(self) -> Self {
         match self {
@@ -137,7 +137,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let mut input = read_input_as_vec(10)?;
     input.sort_unstable();
     // We make the assumption there are no diff-2 spaces in our sequence
@@ -272,7 +272,7 @@ L.LLLLL.LL"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(11)?;
     let input = Grid::new(&input);
     println!("Part 1: {}", part1(&input));
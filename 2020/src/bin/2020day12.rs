@@ -86,7 +86,7 @@ F11"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(12)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
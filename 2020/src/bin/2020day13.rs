@@ -91,7 +91,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let (start, busses) = read_input_as_first_and_vec_by_pat(13, ",")?;
     let start = start.parse()?;
     println!("Part 1: {}", part1(start, &busses));
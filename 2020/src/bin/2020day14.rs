@@ -167,7 +167,7 @@ mem[26] = 1"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(14)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -71,7 +71,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec_split(15, ",")?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -208,7 +208,7 @@ nearby tickets:
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(16)?;
     let input = Puzzle::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
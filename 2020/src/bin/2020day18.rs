@@ -2,15 +2,31 @@ use aoc2020::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Part {
-    Number(usize),
+    Number(u64),
     Add,
+    Subtract,
     Multiply,
+    Divide,
     Descend,
     Return,
 }
 
 use Part::*;
 
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number(n) => write!(f, "{}", n),
+            Add => write!(f, "+"),
+            Subtract => write!(f, "-"),
+            Multiply => write!(f, "*"),
+            Divide => write!(f, "/"),
+            Descend => write!(f, "("),
+            Return => write!(f, ")"),
+        }
+    }
+}
+
 fn parse_expr(s: &str) -> Result<Vec<Part>> {
     let mut ret = Vec::new();
     let mut numacc = String::new();
@@ -22,11 +38,13 @@ fn parse_expr(s: &str) -> Result<Vec<Part>> {
         match c {
             ' ' => {}
             '+' => ret.push(Add),
+            '-' => ret.push(Subtract),
             '*' => ret.push(Multiply),
+            '/' => ret.push(Divide),
             '(' => ret.push(Descend),
             ')' => ret.push(Return),
             '0'..='9' => numacc.push(c),
-            _ => unimplemented!(),
+            _ => return Err(format!("Unexpected '{}' in {}", c, s).into()),
         }
     }
     if !numacc.is_empty() {
@@ -35,149 +53,181 @@ fn parse_expr(s: &str) -> Result<Vec<Part>> {
     Ok(ret)
 }
 
-fn expr_to_postfix(input: impl Iterator<Item = Part>) -> Vec<Part> {
-    let mut ret = Vec::new();
-    let mut opstack = Vec::new();
-    for part in input {
-        match part {
-            Number(_) => {
-                //println!("Emit1: {}", n);
-                ret.push(part);
-            }
-            Add | Multiply => {
-                // while the opstack is not empty
-                while !opstack.is_empty() {
-                    let op = opstack[opstack.len() - 1];
-                    let cont =
-                        // and op is greater precedence than part
-                        (op == Add && part == Multiply)
-                        // or op is equal to part and left-assoc
-                        || (op == part)
-                        ;
-                    if !cont {
-                        break;
-                    }
-                    // and the operator at the top of the stack is not Descend
-                    if op == Descend {
-                        break;
-                    }
-                    // then pop from the opstack and push to ret
-                    //println!("Emit2: {:?}", op);
-                    ret.push(opstack.pop().unwrap());
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Assoc {
+    Left,
+    // Neither of the puzzle's tables has any of these
+    #[allow(dead_code)]
+    Right,
+}
+
+/// The operators we understand, how tightly each binds (higher binds
+/// tighter), and which way round runs of the same precedence go
+type Precedence = [(Part, u8, Assoc)];
+
+/// Everything the same, left to right
+const SAME: &Precedence = &[(Add, 1, Assoc::Left), (Multiply, 1, Assoc::Left)];
+
+/// Addition before multiplication
+const ADD_FIRST: &Precedence = &[(Add, 2, Assoc::Left), (Multiply, 1, Assoc::Left)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(u64),
+    Binary(Part, Box<Expr>, Box<Expr>),
+}
+
+/// Every operation in parentheses, so there's no question of precedence
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Number(n) => write!(f, "{}", n),
+            Expr::Binary(op, left, right) => write!(f, "({} {} {})", left, op, right),
+        }
+    }
+}
+
+/// Numbers we can evaluate expressions in, failing rather than overflowing
+trait Arith: Sized + Copy {
+    fn from_u64(n: u64) -> Option<Self>;
+    fn apply(op: Part, left: Self, right: Self) -> Option<Self>;
+}
+
+macro_rules! arith {
+    ($($t:ty),*) => {
+        $(
+            impl Arith for $t {
+                fn from_u64(n: u64) -> Option<Self> {
+                    Self::try_from(n).ok()
                 }
-                //println!("Stack: {:?}", part);
-                opstack.push(part);
-            }
-            Descend => {
-                //println!("Stack: Descend");
-                opstack.push(part);
-            }
-            Return => {
-                loop {
-                    if opstack.is_empty() {
-                        break;
-                    };
-                    match opstack.pop().unwrap() {
-                        Descend => {
-                            //println!("Found descend");
-                            break;
-                        }
-                        other => {
-                            //println!("Emit3: {:?}", other);
-                            ret.push(other);
-                        }
+
+                fn apply(op: Part, left: Self, right: Self) -> Option<Self> {
+                    match op {
+                        Add => left.checked_add(right),
+                        Subtract => left.checked_sub(right),
+                        Multiply => left.checked_mul(right),
+                        Divide => left.checked_div(right),
+                        _ => None,
                     }
                 }
             }
+        )*
+    };
+}
+
+arith!(u64, i128);
+
+impl Expr {
+    fn eval<T: Arith>(&self) -> Result<T> {
+        match self {
+            Expr::Number(n) => T::from_u64(*n).ok_or_else(|| format!("{} is too big", n).into()),
+            Expr::Binary(op, left, right) => T::apply(*op, left.eval()?, right.eval()?)
+                .ok_or_else(|| format!("Unable to evaluate {}", self).into()),
         }
     }
-    while let Some(op) = opstack.pop() {
-        //println!("Emit5: {:?}", op);
-        ret.push(op);
-    }
-    ret
 }
 
-fn p1eval(input: &mut impl Iterator<Item = Part>) -> usize {
-    let mut acc = match input.next() {
-        Some(Number(number)) => number,
-        Some(Descend) => p1eval(input),
-        _ => unimplemented!(),
-    };
-    let mut op = None;
-    let mut nval = None;
-    //println!("Start with {}", acc);
-    loop {
-        match input.next() {
-            None | Some(Return) => {
-                //println!("Returning {}", acc);
-                break acc;
-            }
-            Some(Add) => op = Some(Add),
-            Some(Multiply) => op = Some(Multiply),
-            Some(Descend) => nval = Some(p1eval(input)),
-            Some(Number(n)) => nval = Some(n),
-        };
-        match (op, nval) {
-            (None, _) => {}
-            (_, None) => {}
-            (Some(Add), Some(donval)) => {
-                op = None;
-                nval = None;
-                acc += donval;
-                //println!("Add {} -> {}", donval, acc);
-            }
-            (Some(Multiply), Some(donval)) => {
-                op = None;
-                nval = None;
-                acc *= donval;
-                //println!("Times {} -> {}", donval, acc);
+/// A Pratt parser for a list of parts, with the operators in `precedence`
+struct Parser<'a, I: Iterator<Item = Part>> {
+    parts: std::iter::Peekable<I>,
+    precedence: &'a Precedence,
+}
+
+impl<'a, I: Iterator<Item = Part>> Parser<'a, I> {
+    fn operator(&self, part: Part) -> Option<(u8, Assoc)> {
+        self.precedence
+            .iter()
+            .find(|(op, _, _)| *op == part)
+            .map(|&(_, prec, assoc)| (prec, assoc))
+    }
+
+    /// A number, or a parenthesised expression
+    fn operand(&mut self) -> Result<Expr> {
+        match self.parts.next() {
+            Some(Number(n)) => Ok(Expr::Number(n)),
+            Some(Descend) => {
+                let expr = self.expr(0)?;
+                match self.parts.next() {
+                    Some(Return) => Ok(expr),
+                    Some(part) => Err(format!("Expected ) but found {}", part).into()),
+                    None => Err("Missing )".into()),
+                }
             }
-            (Some(_), Some(_)) => unimplemented!(),
+            Some(part) => Err(format!("Expected a number but found {}", part).into()),
+            None => Err("Expected a number but found the end".into()),
         }
     }
-}
 
-fn eval_postfix(input: &[Part]) -> usize {
-    let mut numstack = Vec::new();
-    for part in input.iter().copied() {
-        match part {
-            Number(n) => numstack.push(n),
-            Add => {
-                let right = numstack.pop().unwrap();
-                let left = numstack.pop().unwrap();
-                numstack.push(left + right);
+    /// As much as we can with operators which bind tighter than `min`
+    fn expr(&mut self, min: u8) -> Result<Expr> {
+        let mut left = self.operand()?;
+        loop {
+            let op = match self.parts.peek() {
+                None | Some(Return) => break,
+                Some(&op) => op,
+            };
+            let (prec, assoc) = self
+                .operator(op)
+                .ok_or_else(|| format!("Expected an operator but found {}", op))?;
+            if prec <= min {
+                break;
             }
-            Multiply => {
-                let right = numstack.pop().unwrap();
-                let left = numstack.pop().unwrap();
-                numstack.push(left * right);
-            }
-            _ => unimplemented!(),
+            self.parts.next();
+            // Anything of the same precedence on the right belongs to us if
+            // we're right associative, and to whoever is after us if not
+            let right = match assoc {
+                Assoc::Left => self.expr(prec)?,
+                Assoc::Right => self.expr(prec - 1)?,
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
         }
+        Ok(left)
     }
-    numstack.pop().unwrap()
 }
 
-fn part1(input: &[Vec<Part>]) -> usize {
-    input.iter().map(|v| p1eval(&mut v.iter().copied())).sum()
+fn parse_ast(parts: &[Part], precedence: &Precedence) -> Result<Expr> {
+    if let Some((op, _, _)) = precedence.iter().find(|(_, prec, _)| *prec == 0) {
+        return Err(format!("{} needs a precedence above zero", op).into());
+    }
+    let mut parser = Parser {
+        parts: parts.iter().copied().peekable(),
+        precedence,
+    };
+    let expr = parser.expr(0)?;
+    match parser.parts.next() {
+        Some(part) => Err(format!("Unexpected {}", part).into()),
+        None => Ok(expr),
+    }
+}
+
+fn sum(input: &[Vec<Part>], precedence: &Precedence) -> Result<u64> {
+    input.iter().try_fold(0u64, |acc, parts| {
+        let value: u64 = parse_ast(parts, precedence)?.eval()?;
+        acc.checked_add(value)
+            .ok_or_else(|| "Sum is too big".into())
+    })
 }
 
-fn part2(input: &[Vec<Part>]) -> usize {
-    input
-        .iter()
-        .map(|v| {
-            let pfe = expr_to_postfix(&mut v.iter().copied());
-            eval_postfix(&pfe)
-        })
-        .sum()
+fn part1(input: &[Vec<Part>]) -> Result<u64> {
+    sum(input, SAME)
+}
+
+fn part2(input: &[Vec<Part>]) -> Result<u64> {
+    sum(input, ADD_FIRST)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT1: &[(&str, usize)] = &[
+    fn eval(s: &str, precedence: &Precedence) -> u64 {
+        let input = parse_expr(s).unwrap();
+        let ast = parse_ast(&input, precedence).unwrap();
+        println!("Test case: {} = {}", s, ast);
+        ast.eval().unwrap()
+    }
+
+    const TEST_INPUT1: &[(&str, u64)] = &[
         (r#"1 + 2 * 3 + 4 * 5 + 6"#, 71),
         ("1 + (2 * 3) + (4 * (5 + 6))", 51),
         ("2 * 3 + (4 * 5)", 26),
@@ -189,14 +239,11 @@ mod test {
     #[test]
     fn testcase1() {
         for (s, n) in TEST_INPUT1.iter().copied() {
-            println!("Test case: {} = {}", s, n);
-            let input = parse_expr(s).unwrap();
-            println!("Test case: {:?}", input);
-            assert_eq!(p1eval(&mut input.iter().copied()), n);
+            assert_eq!(eval(s, SAME), n);
         }
     }
 
-    const TEST_INPUT2: &[(&str, usize)] = &[
+    const TEST_INPUT2: &[(&str, u64)] = &[
         ("1 + 2 * 3 + 4 * 5 + 6", 231),
         ("1 + (2 * 3) + (4 * (5 + 6))", 51),
         ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 1445),
@@ -207,21 +254,46 @@ mod test {
     #[test]
     fn testcase2() {
         for (s, n) in TEST_INPUT2.iter().copied() {
-            println!("Test case: {} = {}", s, n);
-            let input = parse_expr(s).unwrap();
-            println!("Parsed: {:?}", input);
-            let input = expr_to_postfix(input.into_iter());
-            println!("Postfix: {:?}", input);
-            assert_eq!(eval_postfix(&input), n);
+            assert_eq!(eval(s, ADD_FIRST), n);
         }
     }
+
+    #[test]
+    fn arithmetic() {
+        const USUAL: &Precedence = &[
+            (Add, 1, Assoc::Left),
+            (Subtract, 1, Assoc::Left),
+            (Multiply, 2, Assoc::Left),
+            (Divide, 2, Assoc::Left),
+        ];
+        let parts = parse_expr("2 * 3 - 8 / 2 / 2 + 1").unwrap();
+        let ast = parse_ast(&parts, USUAL).unwrap();
+        assert_eq!(ast.to_string(), "(((2 * 3) - ((8 / 2) / 2)) + 1)");
+        assert_eq!(ast.eval::<u64>().unwrap(), 5);
+
+        const RIGHT: &Precedence = &[(Subtract, 1, Assoc::Right)];
+        let parts = parse_expr("1 - 2 - 3").unwrap();
+        let ast = parse_ast(&parts, RIGHT).unwrap();
+        assert_eq!(ast.to_string(), "(1 - (2 - 3))");
+        assert_eq!(ast.eval::<i128>().unwrap(), 2);
+
+        let parts = parse_expr("1 - 2").unwrap();
+        let ast = parse_ast(&parts, USUAL).unwrap();
+        assert!(ast.eval::<u64>().is_err());
+        assert_eq!(ast.eval::<i128>().unwrap(), -1);
+
+        let parts = parse_expr("1 / (2 - 2)").unwrap();
+        assert!(parse_ast(&parts, USUAL).unwrap().eval::<i128>().is_err());
+        // Division isn't in this table
+        assert!(parse_ast(&parts, SAME).is_err());
+    }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(18)?;
     let input: Result<Vec<Vec<Part>>> = input.trim().lines().map(parse_expr).collect();
     let input = input?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+    println!("Part 1: {}", part1(&input)?);
+    println!("Part 2: {}", part2(&input)?);
     Ok(())
 }
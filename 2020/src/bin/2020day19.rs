@@ -1,136 +1,252 @@
-use std::cell::RefCell;
-
 use aoc2020::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Rule {
     Terminal(char),
     Recurse(Vec<Vec<usize>>),
-    Repeat(usize),
-    Matched(usize, usize),
 }
 
+fn parse_rule(l: &str) -> Result<(usize, Rule)> {
+    let l = l.trim();
+    let colon = l.find(':').ok_or("no colon")?;
+    let rulenum = l[..colon].trim();
+    let rulenum = rulenum.parse()?;
+    let rule = if let Some(quote) = l.find('"') {
+        Rule::Terminal(l.chars().nth(quote + 1).ok_or("no chars?")?)
+    } else {
+        let rest = l[colon + 1..].trim();
+        let alts = rest.split('|');
+        let alts = alts.map(|s| {
+            s.trim()
+                .split_ascii_whitespace()
+                .map(|n| n.parse::<usize>())
+                .collect::<StdResult<Vec<_>, _>>()
+        });
+        let alts: StdResult<Vec<_>, _> = alts.collect();
+        let alts = alts?;
+        // Earley parsing, as below, gets more complicated with rules which
+        // can match nothing at all, and we don't need them
+        if alts.iter().any(Vec::is_empty) {
+            return Err(format!("Rule {} can match nothing", rulenum).into());
+        }
+        Rule::Recurse(alts)
+    };
+    Ok((rulenum, rule))
+}
+
+#[derive(Debug, Clone)]
 struct Ruleset {
     rules: HashMap<usize, Rule>,
-    min_lens: RefCell<HashMap<usize, usize>>,
 }
 
 impl FromStr for Ruleset {
     type Err = GenError;
 
     fn from_str(value: &str) -> Result<Self> {
-        let mut rules = HashMap::new();
-        for l in value.trim().lines() {
-            let l = l.trim();
-            let colon = l.find(':').ok_or("no colon")?;
-            let rulenum = l[..colon].trim();
-            let rulenum = rulenum.parse()?;
-            let rule = if let Some(quote) = l.find('"') {
-                Rule::Terminal(l.chars().nth(quote + 1).ok_or("no chars?")?)
-            } else {
-                let rest = l[colon + 1..].trim();
-                let alts = rest.split('|');
-                let alts = alts.map(|s| {
-                    s.trim()
-                        .split_ascii_whitespace()
-                        .map(|n| n.parse::<usize>())
-                        .collect::<StdResult<_, _>>()
-                });
-                let alts: StdResult<Vec<_>, _> = alts.collect();
-                let alts = alts?;
-                Rule::Recurse(alts)
-            };
-            rules.insert(rulenum, rule);
-        }
-        let ret = Self {
-            rules,
-            min_lens: RefCell::new(HashMap::new()),
-        };
+        let rules = value
+            .trim()
+            .lines()
+            .map(parse_rule)
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+}
 
-        ret.compute_min_len(0);
+/// How a message matches a rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tree {
+    Char(char),
+    Rule(usize, Vec<Tree>),
+}
 
-        Ok(ret)
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tree::Char(c) => write!(f, "{}", c),
+            Tree::Rule(rule, children) => {
+                write!(f, "{}(", rule)?;
+                for (n, child) in children.iter().enumerate() {
+                    if n > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", child)?;
+                }
+                write!(f, ")")
+            }
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol {
+    Char(char),
+    Rule(usize),
+}
+
+/// An Earley item: an alternative of a rule, matched as far as `dot` from
+/// `origin` in the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Item {
+    rule: usize,
+    alt: usize,
+    dot: usize,
+    origin: usize,
+}
+
 impl Ruleset {
-    fn regex_string(&self, maxlen: usize) -> String {
-        let mut ret = String::new();
-        ret.push('^');
-        self._regex_string(&mut ret, 0, maxlen);
-        ret.push('$');
-        ret
+    /// The symbols of alternative `alt` of `rule`, a terminal rule having
+    /// just the one alternative
+    fn symbols(&self, rule: usize, alt: usize) -> Vec<Symbol> {
+        match self.rules.get(&rule) {
+            Some(Rule::Terminal(c)) => vec![Symbol::Char(*c)],
+            Some(Rule::Recurse(alts)) => alts[alt].iter().copied().map(Symbol::Rule).collect(),
+            None => vec![],
+        }
     }
 
-    fn compute_min_len(&self, rule: usize) -> usize {
-        if self.min_lens.borrow().contains_key(&rule) {
-            return *self.min_lens.borrow().get(&rule).unwrap();
+    fn alternatives(&self, rule: usize) -> usize {
+        match self.rules.get(&rule) {
+            Some(Rule::Terminal(_)) => 1,
+            Some(Rule::Recurse(alts)) => alts.len(),
+            // Rules which don't exist match nothing
+            None => 0,
         }
-        let len = match self.rules.get(&rule).unwrap() {
-            Rule::Terminal(_) => 1,
-            Rule::Recurse(alts) => alts
-                .iter()
-                .map(|alt| alt.iter().map(|r| self.compute_min_len(*r)).sum())
-                .max()
-                .unwrap(),
-            Rule::Repeat(sub) => self.compute_min_len(*sub),
-            Rule::Matched(first, second) => {
-                self.compute_min_len(*first) + self.compute_min_len(*second)
+    }
+
+    /// Every `(rule, start, end)` for which `rule` matches `message[start..end]`
+    /// as part of a match of `start_rule` from the beginning of the message
+    fn earley(&self, start_rule: usize, message: &[char]) -> HashSet<(usize, usize, usize)> {
+        let mut charts: Vec<Vec<Item>> = vec![vec![]; message.len() + 1];
+        let mut seen: Vec<HashSet<Item>> = vec![HashSet::new(); message.len() + 1];
+        let mut completed = HashSet::new();
+        let add = |charts: &mut Vec<Vec<Item>>, seen: &mut Vec<HashSet<Item>>, pos: usize, item| {
+            if seen[pos].insert(item) {
+                charts[pos].push(item);
             }
         };
-        self.min_lens.borrow_mut().insert(rule, len);
-        len
+        for alt in 0..self.alternatives(start_rule) {
+            let item = Item {
+                rule: start_rule,
+                alt,
+                dot: 0,
+                origin: 0,
+            };
+            add(&mut charts, &mut seen, 0, item);
+        }
+        for pos in 0..=message.len() {
+            let mut n = 0;
+            while n < charts[pos].len() {
+                let item = charts[pos][n];
+                n += 1;
+                let advanced = Item {
+                    dot: item.dot + 1,
+                    ..item
+                };
+                match self.symbols(item.rule, item.alt).get(item.dot) {
+                    // Predict
+                    Some(&Symbol::Rule(rule)) => {
+                        for alt in 0..self.alternatives(rule) {
+                            let item = Item {
+                                rule,
+                                alt,
+                                dot: 0,
+                                origin: pos,
+                            };
+                            add(&mut charts, &mut seen, pos, item);
+                        }
+                    }
+                    // Scan
+                    Some(&Symbol::Char(c)) => {
+                        if message.get(pos) == Some(&c) {
+                            add(&mut charts, &mut seen, pos + 1, advanced);
+                        }
+                    }
+                    // Complete
+                    None => {
+                        completed.insert((item.rule, item.origin, pos));
+                        for waiting in charts[item.origin].clone() {
+                            let symbols = self.symbols(waiting.rule, waiting.alt);
+                            if symbols.get(waiting.dot) == Some(&Symbol::Rule(item.rule)) {
+                                let advanced = Item {
+                                    dot: waiting.dot + 1,
+                                    ..waiting
+                                };
+                                add(&mut charts, &mut seen, pos, advanced);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        completed
     }
 
-    fn min_len_of(&self, rule: usize) -> usize {
-        *self.min_lens.borrow().get(&rule).unwrap()
+    /// How all of `message` matches `rule`, if it does.  If there's more than
+    /// one way, this is one of them.
+    fn parse(&self, rule: usize, message: &str) -> Option<Tree> {
+        let message: Vec<char> = message.chars().collect();
+        let completed = self.earley(rule, &message);
+        self.tree(&completed, &message, rule, 0, message.len(), &mut vec![])
     }
 
-    fn _regex_string(&self, acc: &mut String, rule: usize, maxlen: usize) {
-        match self.rules.get(&rule).unwrap() {
-            Rule::Terminal(c) => acc.push(*c),
-            Rule::Recurse(alts) => {
-                let alts = alts.iter().map(|subs| {
-                    let mut alt = String::new();
-                    subs.iter()
-                        .copied()
-                        .for_each(|n| self._regex_string(&mut alt, n, maxlen));
-                    alt
-                });
-                acc.push_str("(?:");
-                alts.for_each(|s| {
-                    acc.push_str(&s);
-                    acc.push('|');
-                });
-                acc.pop(); // remove last |
-                acc.push(')');
-            }
-            Rule::Repeat(n) => {
-                acc.push_str("(?:");
-                self._regex_string(acc, *n, maxlen);
-                acc.push_str(")+");
-            }
-            Rule::Matched(first, second) => {
-                let mut first_str = String::new();
-                self._regex_string(&mut first_str, *first, maxlen);
-                let mut second_str = String::new();
-                self._regex_string(&mut second_str, *second, maxlen);
-                acc.push_str("(?:");
-                let totlen = self.min_len_of(*first) + self.min_len_of(*second);
-                let maxrep = (maxlen + totlen - 1) / totlen;
-                for n in 1..=maxrep {
-                    for _ in 1..=n {
-                        acc.push_str(&first_str)
-                    }
-                    for _ in 1..=n {
-                        acc.push_str(&second_str)
-                    }
-                    acc.push('|');
+    fn tree(
+        &self,
+        completed: &HashSet<(usize, usize, usize)>,
+        message: &[char],
+        rule: usize,
+        start: usize,
+        end: usize,
+        building: &mut Vec<(usize, usize, usize)>,
+    ) -> Option<Tree> {
+        // Going round in circles through rules which match the same span
+        // can't be how it matched
+        if !completed.contains(&(rule, start, end)) || building.contains(&(rule, start, end)) {
+            return None;
+        }
+        building.push((rule, start, end));
+        let tree = (0..self.alternatives(rule)).find_map(|alt| {
+            let symbols = self.symbols(rule, alt);
+            self.children(completed, message, &symbols, start, end, building)
+                .map(|children| Tree::Rule(rule, children))
+        });
+        building.pop();
+        tree
+    }
+
+    /// Trees for `symbols` matching `message[start..end]` in turn
+    fn children(
+        &self,
+        completed: &HashSet<(usize, usize, usize)>,
+        message: &[char],
+        symbols: &[Symbol],
+        start: usize,
+        end: usize,
+        building: &mut Vec<(usize, usize, usize)>,
+    ) -> Option<Vec<Tree>> {
+        let (first, rest) = match symbols.split_first() {
+            Some(split) => split,
+            None => return if start == end { Some(vec![]) } else { None },
+        };
+        // Every symbol matches at least one character
+        if end - start < symbols.len() {
+            return None;
+        }
+        for mid in start + 1..=end - rest.len() {
+            let child = match *first {
+                Symbol::Char(c) if mid == start + 1 && message[start] == c => Some(Tree::Char(c)),
+                Symbol::Char(_) => None,
+                Symbol::Rule(rule) => self.tree(completed, message, rule, start, mid, building),
+            };
+            if let Some(child) = child {
+                if let Some(mut children) =
+                    self.children(completed, message, rest, mid, end, building)
+                {
+                    children.insert(0, child);
+                    return Some(children);
                 }
-                acc.pop(); //last |
-                acc.push(')');
             }
         }
+        None
     }
 }
 
@@ -153,31 +269,19 @@ impl FromStr for Puzzle {
 }
 
 fn part1(input: &Puzzle) -> usize {
-    // Build a regexp representing the puzzle and then test all the goals
-    let maxlen = input.goals.iter().map(|s| s.len()).max().unwrap();
-    let rex = input.rules.regex_string(maxlen);
-    let rex = RegexBuilder::new(&rex)
-        .size_limit(104857600)
-        .build()
-        .unwrap();
-    input.goals.iter().filter(|s| rex.is_match(s)).count()
+    input
+        .goals
+        .iter()
+        .filter(|s| input.rules.parse(0, s).is_some())
+        .count()
 }
 
 fn part2(mut input: Puzzle) -> usize {
-    // replace:
-    // 8: 42
-    // with:
-    // 8: 42 | 42 8
-    // replace:
-    // 11: 42 31
-    // with:
-    // 11: 42 31 | 42 11 31
-    // The 8 rule becomes 8+ which is pretty simple to encode in a regex
-    // but the 11 rule becomes a counting pattern which is more of a parsing
-    // operation, so we basically end up replacing those with custom expressions
-    // which cannot exceed the length of a given input
-    input.rules.rules.insert(8, Rule::Repeat(42));
-    input.rules.rules.insert(11, Rule::Matched(42, 31));
+    // Rules 8 and 11 become recursive, which is all the same to the parser
+    for rule in &["8: 42 | 42 8", "11: 42 31 | 42 11 31"] {
+        let (n, rule) = parse_rule(rule).expect("Bad replacement rule");
+        input.rules.rules.insert(n, rule);
+    }
     part1(&input)
 }
 
@@ -204,6 +308,31 @@ aaaabbb"#;
         assert_eq!(part1(&input), 2);
     }
 
+    #[test]
+    fn parse_tree() {
+        let input: Puzzle = TEST_INPUT.parse().unwrap();
+        let tree = input.rules.parse(0, "ababbb").unwrap();
+        assert_eq!(
+            tree.to_string(),
+            "0(4(a) 1(3(5(b) 4(a)) 2(5(b) 5(b))) 5(b))"
+        );
+        assert_eq!(input.rules.parse(0, "bababa"), None);
+    }
+
+    #[test]
+    fn short_message() {
+        // The first alternative has more symbols than there are characters
+        let input: Puzzle = "0: 1 2 3 | 1\n1: \"a\"\n2: \"b\"\n3: \"b\"\n\na"
+            .parse()
+            .unwrap();
+        assert_eq!(input.rules.parse(0, "a").unwrap().to_string(), "0(1(a))");
+        assert_eq!(
+            input.rules.parse(0, "abb").unwrap().to_string(),
+            "0(1(a) 2(b) 3(b))"
+        );
+        assert_eq!(input.rules.parse(0, "ab"), None);
+    }
+
     #[test]
     fn testcase2() {
         let input = r#"42: 9 14 | 10 1
@@ -260,7 +389,7 @@ aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(19)?;
     let input = input.parse()?;
     println!("Part 1: {}", part1(&input));
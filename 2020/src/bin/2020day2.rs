@@ -55,7 +55,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<TobogganEntry> = read_input_as_vec(2)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -632,7 +632,7 @@ Tile 3079:
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec_split(20, "\n\n")?;
     println!("There are {} tiles", input.len());
     println!("Part 1: {}", part1(&input));
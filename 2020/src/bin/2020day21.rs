@@ -99,7 +99,7 @@ sqjhc mxmxvkd sbzzf (contains fish)"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(21)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
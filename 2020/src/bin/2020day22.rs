@@ -158,7 +158,7 @@ Player 2:
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(22)?;
     let input = Decks::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
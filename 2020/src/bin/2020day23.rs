@@ -96,7 +96,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(23)?;
     let input = input
         .trim()
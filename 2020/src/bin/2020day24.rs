@@ -206,7 +206,7 @@ wseweeenwnesenwwwswnew"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(24)?;
     let input: Vec<_> = input
         .trim()
@@ -59,7 +59,7 @@ mod test {
     fn testcase2() {}
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(25)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
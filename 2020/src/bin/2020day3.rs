@@ -1,34 +1,26 @@
 use aoc2020::*;
 
 struct MapSegment {
-    height: usize,
-    width: usize,
-    rows: Vec<Vec<bool>>,
+    trees: Grid<bool>,
 }
 
 impl MapSegment {
-    fn new(input: &str) -> Self {
-        let mut ret = MapSegment {
-            height: 0,
-            width: 0,
-            rows: Vec::new(),
-        };
-        for l in input.lines() {
-            let l = l.trim();
-            ret.rows.push(l.chars().map(|c| c == '#').collect());
-        }
-        ret.height = ret.rows.len();
-        ret.width = ret.rows[0].len();
-        ret
+    fn new(input: &str) -> Result<Self> {
+        let trees = Grid::try_parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(MapSegment { trees })
     }
 
     fn is_tree(&self, row: usize, col: usize) -> bool {
-        let col = col % self.width;
-        self.rows[row][col]
+        let col = col % self.trees.width();
+        self.trees[(row, col)]
     }
 
     fn off_bottom(&self, row: usize) -> bool {
-        row >= self.height
+        row >= self.trees.height()
     }
 
     fn test_slope(&self, right: usize, down: usize) -> usize {
@@ -78,20 +70,20 @@ mod test {
 
     #[test]
     fn testcase1() {
-        let map = MapSegment::new(&TEST_INPUT);
+        let map = MapSegment::new(TEST_INPUT).unwrap();
         assert_eq!(part1(&map), 7)
     }
 
     #[test]
     fn testcase2() {
-        let map = MapSegment::new(&TEST_INPUT);
+        let map = MapSegment::new(TEST_INPUT).unwrap();
         assert_eq!(part2(&map), 336)
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(3)?;
-    let input = MapSegment::new(&input);
+    let input = MapSegment::new(&input)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
     Ok(())
@@ -206,7 +206,7 @@ iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: String = read_input(4)?;
     let input = Passport::load_all(&input);
     println!("Part 1: {}", part1(&input));
@@ -52,7 +52,7 @@ mod test {
     fn testcase2() {}
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<String> = read_input_as_vec(5)?;
     let input: Vec<_> = input.iter().map(|s| s.as_str()).collect();
     println!("Part 1: {}", part1(&input));
@@ -84,7 +84,7 @@ b"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec_split(6, "\n\n")?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
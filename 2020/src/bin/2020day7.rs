@@ -152,7 +152,7 @@ dark violet bags contain no other bags.",
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(7)?;
     let input = input.into();
     println!("Part 1: {}", part1(&input));
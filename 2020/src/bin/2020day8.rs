@@ -102,7 +102,7 @@ acc +6"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(8)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
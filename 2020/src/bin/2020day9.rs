@@ -85,7 +85,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input_as_vec(9)?;
     println!("Part 1: {}", part1(&input, 25));
     println!("Part 2: {}", part2(&input, 25));
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_common::registry::generate_days(2021);
+}
@@ -112,7 +112,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<String> = read_input_as_vec(10)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
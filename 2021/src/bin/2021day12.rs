@@ -153,7 +153,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<RawLink> = read_input_as_vec(12)?;
     let input = input.into();
     println!("Part 1: {}", part1(&input));
@@ -73,22 +73,24 @@ impl Instructions {
         self.dots = dots;
     }
 
-    fn show(&self) {
+    fn draw(&self) -> String {
         let (maxx, maxy) = self
             .dots
             .iter()
             .copied()
             .fold((0, 0), |(accx, accy), (x, y)| (max(accx, x), max(accy, y)));
+        let mut ret = String::new();
         for y in 0..=maxy {
             for x in 0..=maxx {
                 if self.dots.contains(&(x, y)) {
-                    print!("#");
+                    ret.push('#');
                 } else {
-                    print!(" ");
+                    ret.push(' ');
                 }
             }
-            println!();
+            ret.push('\n');
         }
+        ret
     }
 }
 fn part1(input: &Instructions) -> usize {
@@ -98,11 +100,12 @@ fn part1(input: &Instructions) -> usize {
     input.dots.len()
 }
 
-fn part2(input: &Instructions) {
+fn part2(input: &Instructions) -> String {
     let mut input = input.clone();
     input.do_all_folds();
-    println!();
-    input.show();
+    let points = input.dots.iter().map(|&(x, y)| (x as i64, y as i64));
+    // If the letters can't be read, the drawing is still an answer
+    ocr::read_points(points).unwrap_or_else(|_| input.draw())
 }
 
 #[cfg(test)]
@@ -138,13 +141,19 @@ fold along x=5
         let input = Instructions::from_str(TEST_INPUT).unwrap();
         assert_eq!(part1(&input), 17);
     }
+
+    #[test]
+    fn testcase2() {
+        // The example folds up into a square, which isn't a letter
+        let input = Instructions::from_str(TEST_INPUT).unwrap();
+        assert_eq!(part2(&input), "#####\n#   #\n#   #\n#   #\n#####\n");
+    }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(13)?;
     let input = Instructions::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
-    println!("Part 2:");
-    part2(&input);
+    println!("Part 2: {}", part2(&input));
     Ok(())
 }
@@ -122,7 +122,7 @@ CN -> C"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(14)?;
     let input = Input::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
@@ -1,68 +1,44 @@
 use aoc2021::*;
 
 struct Cave {
-    risks: HashMap<(i32, i32), i32>,
-    width: i32,
-    height: i32,
+    risks: Grid<i32>,
 }
 
 impl FromStr for Cave {
     type Err = GenError;
 
     fn from_str(input: &str) -> Result<Self> {
-        let input = input.trim();
-        let width = input.lines().next().unwrap().len() as i32;
-        let height = input.lines().count() as i32;
-        let mut risks = HashMap::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, ch) in line.trim().bytes().enumerate() {
-                assert!((b'0'..=b'9').contains(&ch));
-                let coord = (i32::try_from(x)?, i32::try_from(y)?);
-                let risk = i32::from(ch - b'0');
-                risks.insert(coord, risk);
-            }
-        }
-        Ok(Self {
-            risks,
-            width,
-            height,
-        })
+        let risks = Grid::try_parse(input, |c| c.to_digit(10).map(|d| d as i32))?;
+        Ok(Self { risks })
     }
 }
 
 impl Cave {
     // Essentially dijkstra's algorithm
     fn shortest_path(&self) -> i32 {
-        let target = (self.width - 1, self.height - 1);
-        let mut costs = vec![vec![i32::MAX; self.width as usize]; self.height as usize];
+        let target = (self.risks.height() - 1, self.risks.width() - 1);
+        let mut costs = self.risks.map(|_| i32::MAX);
         // We use a binaryheap which is, for all intents and purposes an always-ordered vec
-        // values are (-cost, x, y)
+        // values are (-cost, pos)
         // using negative cost because that way when we pop we're doing a min-heap not a max-heap
         // i.e. we're always getting the smallest cost (since -1 is bigger than -2 and we want the 1)
         // this is effectively a less-type-filled version of the dijkstra on
         // https://doc.rust-lang.org/std/collections/binary_heap/index.html
         // (which I found with searching for "rust std dijkstra" with google)
-        // Dijkstra works on edge costs, the edge cost for any edge into (x,y) is the risk at (x,y)
+        // Dijkstra works on edge costs, the edge cost for any edge into pos is the risk at pos
         let mut walk_queue = std::collections::BinaryHeap::new();
-        walk_queue.push((0, 0, 0));
-        while let Some((cost, cur_x, cur_y)) = walk_queue.pop() {
+        walk_queue.push((0, (0, 0)));
+        while let Some((cost, cur)) = walk_queue.pop() {
             let cost = -cost;
-            if (cur_x, cur_y) == target {
+            if cur == target {
                 return cost;
             }
-            for (new_x, new_y) in [
-                (cur_x - 1, cur_y),
-                (cur_x + 1, cur_y),
-                (cur_x, cur_y - 1),
-                (cur_x, cur_y + 1),
-            ] {
-                // not all orthog coords will be real (i.e. map edges) so don't just index...
-                if let Some(risk) = self.risks.get(&(new_x, new_y)).copied() {
-                    let next_cost = cost + risk;
-                    if next_cost < costs[new_x as usize][new_y as usize] {
-                        walk_queue.push((-next_cost, new_x, new_y));
-                        costs[new_x as usize][new_y as usize] = next_cost;
-                    }
+            // the grid only gives us the neighbours which are on the map
+            for next in self.risks.neighbours4(cur) {
+                let next_cost = cost + self.risks[next];
+                if next_cost < costs[next] {
+                    walk_queue.push((-next_cost, next));
+                    costs[next] = next_cost;
                 }
             }
         }
@@ -70,31 +46,22 @@ impl Cave {
     }
 
     fn expanded_map(&self) -> Self {
-        let width = self.width * 5;
-        let height = self.height * 5;
-        let mut risks = HashMap::new();
-        for ((x, y), r) in self.risks.iter() {
-            let x = *x;
-            let y = *y;
-            let r = *r;
-            for tile_x in 0..5 {
-                for tile_y in 0..5 {
-                    let x = x + (tile_x * self.width);
-                    let y = y + (tile_y * self.height);
-                    let mut r = r + tile_x + tile_y;
+        let (width, height) = (self.risks.width(), self.risks.height());
+        let mut risks = Grid::new(width * 5, height * 5, 0);
+        for ((row, col), &r) in self.risks.iter() {
+            for tile_row in 0..5 {
+                for tile_col in 0..5 {
+                    let pos = (row + tile_row * height, col + tile_col * width);
+                    let mut r = r + (tile_row + tile_col) as i32;
                     // wrap risk into 1..=9
                     if r > 9 {
                         r -= 9;
                     }
-                    risks.insert((x, y), r);
+                    risks[pos] = r;
                 }
             }
         }
-        Self {
-            risks,
-            width,
-            height,
-        }
+        Self { risks }
     }
 }
 fn part1(input: &Cave) -> i32 {
@@ -133,7 +100,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(15)?;
     let input = Cave::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
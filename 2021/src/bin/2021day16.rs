@@ -210,7 +210,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(16)?;
     let input = Packet::read_all(input.trim())?;
     println!("Part 1: {}", part1(&input));
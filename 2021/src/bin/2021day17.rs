@@ -98,7 +98,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(17)?;
     let input = Conditions::parse_by_regex(input.trim())?;
     println!("Part 1: {}", part1(input));
@@ -258,7 +258,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<SFValue> = read_input_as_vec(18)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
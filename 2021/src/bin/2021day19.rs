@@ -301,7 +301,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(19)?;
     let input = Input::from_str(&input)?;
     let (scanners, beacons) = clouds(&input);
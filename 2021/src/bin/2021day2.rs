@@ -59,7 +59,7 @@ forward 2"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Command> = read_input_as_vec(2)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -245,7 +245,7 @@ mod test {
         assert_eq!(Input::flipped_output_idx(&map, &map, (0,0)), 511);
     }}
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(20)?;
     let input = Input::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
@@ -109,7 +109,7 @@ Player 2 starting position: 8"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<StartPos> = read_input_as_vec(21)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
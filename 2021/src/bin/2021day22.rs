@@ -297,7 +297,7 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507"#;
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Instruction> = read_input_as_vec(22)?;
     let input = all_on_cubes(&input);
     println!("Part 1: {}", part1(&input));
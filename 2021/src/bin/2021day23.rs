@@ -240,7 +240,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(23)?;
     let input = AmbiCave::from_str(&input).unwrap();
     println!("Part 1: {}", part1(&input));
@@ -1,5 +1,8 @@
 use aoc2021::*;
 
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
 #[derive(Debug, Clone, Copy, ParseByRegex, Eq, PartialEq, Hash)]
 enum Reg {
     #[regex = "^w$"]
@@ -187,138 +190,761 @@ resolve a value which will work.
 
 In pushes, the important value is param 3, since that is added to the digit
 In pops, the important value is param 2, since that's added to the popped value before comparing with the digit
+
+Rather than rely on every program having exactly that shape, we run programs
+symbolically.  Each register holds an expression over the input digits, and we
+can always work out the range of values an expression might have.  Knowing the
+ranges is what lets `(a * 26 + b) % 26` become `b`, and `(a * 26 + b) / 26`
+become `a`, when `b` can only be 0 to 25, which is how the stack in z shows up.
+
+When an `eql` can't be decided from the ranges, the run splits in two: one way
+where the values are equal, which needs to be a relationship between digits
+like the ones above, and one where the same relationship doesn't hold.  In the
+end only the ways through which leave z as 0 matter, and their relationships
+between digits, both the ones which must hold and the ones which mustn't, are
+the constraints we need to pick digits with.
 */
 
-#[derive(Debug)]
-enum Simplified {
-    Push(i64),
-    Pop(i64),
+/// What a register holds, in terms of the input digits
+#[derive(Debug, PartialEq, Eq)]
+enum Expr {
+    Num(i64),
+    Digit(usize),
+    Add(Sym, Sym),
+    Mul(Sym, Sym),
+    Div(Sym, i64),
+    Mod(Sym, i64),
 }
 
-fn simplify_prog(input: &[Instruction]) -> Vec<Simplified> {
-    assert_eq!(input.len(), 14 * 18);
-    let mut ret = Vec::new();
-    for i in 0..14 {
-        let base = i * 18;
-        assert!(matches!(input[base], Instruction::Input(Reg::W)));
-        let i1 = input[base + 4];
-        let i2 = input[base + 5];
-        let i3 = input[base + 15];
-        //println!("Instruction {}", i);
-        //println!("i1 = {:?}", i1);
-        //println!("i2 = {:?}", i2);
-        //println!("i3 = {:?}", i3);
-        let input1 = if let Instruction::Div(Reg::Z, BVal::Num(l)) = i1 {
-            l
-        } else {
-            panic!("i1 is not div z {{num}}")
-        };
-        let input2 = if let Instruction::Add(Reg::X, BVal::Num(l)) = i2 {
-            l
-        } else {
-            panic!("i2 is not add x {{num}}")
-        };
-        let input3 = if let Instruction::Add(Reg::Y, BVal::Num(l)) = i3 {
-            l
-        } else {
-            panic!("i3 is not add y {{num}}")
-        };
-        let instr = if input1 == 1 {
-            Simplified::Push(input3)
-        } else {
-            Simplified::Pop(input2)
-        };
-        ret.push(instr);
+type Sym = Rc<Expr>;
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Num(n) => write!(f, "{}", n),
+            Self::Digit(d) => write!(f, "digits[{}]", d),
+            Self::Add(a, b) => write!(f, "({} + {})", a, b),
+            Self::Mul(a, b) => write!(f, "({} * {})", a, b),
+            Self::Div(a, k) => write!(f, "({} / {})", a, k),
+            Self::Mod(a, k) => write!(f, "({} % {})", a, k),
+        }
     }
-    ret
 }
 
-/*
-
-With a simplified program we can do digit pairing, this is done by managing the stack
-and looking basically for constraints which will be of the form "digit X must equal digit Y + some value"
-there will be seven such constraints which will give us what will effectively be our goal constraints
+fn num(n: i64) -> Sym {
+    Rc::new(Expr::Num(n))
+}
 
-*/
+/// A sum of multiples of digits, plus a constant
+type Linear = (BTreeMap<usize, i64>, i64);
+
+fn linear(expr: &Expr) -> Option<Linear> {
+    match expr {
+        Expr::Num(n) => Some((BTreeMap::new(), *n)),
+        Expr::Digit(d) => Some((std::iter::once((*d, 1)).collect(), 0)),
+        Expr::Add(a, b) => {
+            let (mut digits, n) = linear(a)?;
+            let (more, m) = linear(b)?;
+            for (d, k) in more {
+                *digits.entry(d).or_insert(0) += k;
+            }
+            Some((digits, n + m))
+        }
+        Expr::Mul(a, b) => match **b {
+            Expr::Num(k) => {
+                let (digits, n) = linear(a)?;
+                Some((digits.into_iter().map(|(d, m)| (d, m * k)).collect(), n * k))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
 
-#[derive(Debug, Clone, Copy)]
-struct Constraint {
-    x: usize,
-    y: usize,
-    val: i8,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Constraint {
+    /// `digits[y]` must (or if not `equal`, mustn't) equal `digits[x] + val`
+    Offset {
+        x: usize,
+        y: usize,
+        val: i64,
+        equal: bool,
+    },
+    /// `digits[x]` must (or if not `equal`, mustn't) be `val`
+    Fixed { x: usize, val: i64, equal: bool },
 }
 
 impl fmt::Display for Constraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "digits[{}] must equal digits[{}] + {}",
-            self.y, self.x, self.val
-        )
+        let must = |equal: bool| if equal { "must" } else { "must not" };
+        match self {
+            Self::Offset { x, y, val, equal } => write!(
+                f,
+                "digits[{}] {} equal digits[{}] + {}",
+                y,
+                must(*equal),
+                x,
+                val
+            ),
+            Self::Fixed { x, val, equal } => {
+                write!(f, "digits[{}] {} be {}", x, must(*equal), val)
+            }
+        }
+    }
+}
+
+impl Constraint {
+    /// The opposite constraint, which holds exactly when this one doesn't
+    fn negated(self) -> Self {
+        match self {
+            Self::Offset { x, y, val, equal } => Self::Offset {
+                x,
+                y,
+                val,
+                equal: !equal,
+            },
+            Self::Fixed { x, val, equal } => Self::Fixed {
+                x,
+                val,
+                equal: !equal,
+            },
+        }
+    }
+
+    /// Whether the digits `digit` knows about keep to this, where digits it
+    /// doesn't know could be anything
+    fn allows<F: Fn(usize) -> Option<i64>>(self, digit: F) -> bool {
+        match self {
+            Self::Offset { x, y, val, equal } => match (digit(x), digit(y)) {
+                (Some(dx), Some(dy)) => (dy == dx + val) == equal,
+                _ => true,
+            },
+            Self::Fixed { x, val, equal } => digit(x).is_none_or(|d| (d == val) == equal),
+        }
+    }
+}
+
+/// What `a == b` comes down to
+enum Comparison {
+    /// The same whatever the digits are
+    Always(bool),
+    /// Whether this constraint holds
+    When(Constraint),
+}
+
+/// Which sort of constant an instruction needs as its second operand
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Divisor,
+    Modulus,
+}
+
+impl Operand {
+    /// Dividing by 0, or taking the modulus by anything but a positive
+    /// number, is an error on the ALU anyway
+    fn allows(self, k: i64) -> bool {
+        match self {
+            Self::Divisor => k != 0,
+            Self::Modulus => k > 0,
+        }
     }
+
+    fn verb(self) -> &'static str {
+        match self {
+            Self::Divisor => "divides",
+            Self::Modulus => "takes the modulus",
+        }
+    }
+}
+
+/// The most ways through a program we'll keep track of at once
+const MAX_PATHS: usize = 1 << 16;
+
+/// One way through a program
+#[derive(Debug, Clone)]
+struct Path {
+    regs: [Sym; 4],
+    /// The range of each digit read so far
+    digits: Vec<(i64, i64)>,
+    constraints: Vec<Constraint>,
 }
 
-fn gen_constraints(input: &[Simplified]) -> Vec<Constraint> {
-    let mut ret = Vec::new();
-    let mut stack = Vec::new();
-    for (digit, op) in input.iter().enumerate() {
-        match op {
-            Simplified::Push(val) => {
-                stack.push((digit, (*val as i8)));
+impl Path {
+    fn new() -> Self {
+        Self {
+            regs: [num(0), num(0), num(0), num(0)],
+            digits: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    fn range(&self, expr: &Expr) -> (i64, i64) {
+        match expr {
+            Expr::Num(n) => (*n, *n),
+            Expr::Digit(d) => self.digits[*d],
+            Expr::Add(a, b) => {
+                let ((alo, ahi), (blo, bhi)) = (self.range(a), self.range(b));
+                (alo.saturating_add(blo), ahi.saturating_add(bhi))
+            }
+            Expr::Mul(a, b) => {
+                let ((alo, ahi), (blo, bhi)) = (self.range(a), self.range(b));
+                let products = [
+                    alo.saturating_mul(blo),
+                    alo.saturating_mul(bhi),
+                    ahi.saturating_mul(blo),
+                    ahi.saturating_mul(bhi),
+                ];
+                (
+                    products.iter().copied().min().unwrap(),
+                    products.iter().copied().max().unwrap(),
+                )
             }
-            Simplified::Pop(val) => {
-                let (digit1, partial) = stack.pop().unwrap();
-                ret.push(Constraint {
-                    x: digit1,
-                    y: digit,
-                    val: (*val as i8) + partial,
-                });
+            Expr::Div(a, k) => {
+                let (lo, hi) = self.range(a);
+                if *k > 0 {
+                    (lo / k, hi / k)
+                } else {
+                    (hi / k, lo / k)
+                }
             }
+            Expr::Mod(a, k) => match self.range(a) {
+                (lo, hi) if lo >= 0 && hi < *k => (lo, hi),
+                _ => (0, k - 1),
+            },
         }
     }
-    ret
-}
 
-fn part1(input: &[Constraint]) -> String {
-    let mut digits = [0i8; 14];
-    for Constraint { x, y, val } in input.iter().copied() {
-        if val < 0 {
-            digits[x] = 9;
-            digits[y] = 9 + val;
+    fn add(&self, a: Sym, b: Sym) -> Sym {
+        match (&*a, &*b) {
+            (Expr::Num(n), Expr::Num(m)) => num(n + m),
+            (Expr::Num(0), _) => b,
+            (_, Expr::Num(0)) => a,
+            (Expr::Num(_), _) => self.add(b, a),
+            (Expr::Add(c, n), Expr::Num(m)) => match **n {
+                Expr::Num(n) => self.add(c.clone(), num(n + m)),
+                _ => Rc::new(Expr::Add(a, b)),
+            },
+            _ => Rc::new(Expr::Add(a, b)),
+        }
+    }
+
+    fn mul(&self, a: Sym, b: Sym) -> Sym {
+        match (&*a, &*b) {
+            (Expr::Num(n), Expr::Num(m)) => num(n * m),
+            (Expr::Num(0), _) | (_, Expr::Num(0)) => num(0),
+            (Expr::Num(1), _) => b,
+            (_, Expr::Num(1)) => a,
+            (Expr::Num(_), _) => self.mul(b, a),
+            (Expr::Mul(c, n), Expr::Num(m)) => match **n {
+                Expr::Num(n) => self.mul(c.clone(), num(n * m)),
+                _ => Rc::new(Expr::Mul(a, b)),
+            },
+            _ => Rc::new(Expr::Mul(a, b)),
+        }
+    }
+
+    fn terms(expr: &Sym, terms: &mut Vec<Sym>) {
+        match &**expr {
+            Expr::Add(a, b) => {
+                Self::terms(a, terms);
+                Self::terms(b, terms);
+            }
+            _ => terms.push(expr.clone()),
+        }
+    }
+
+    /// Split `expr` into `q * k + r` where `r` is from 0 to `k - 1`, so long
+    /// as we can tell `q` won't be negative
+    fn split(&self, expr: &Sym, k: i64) -> Option<(Sym, Sym)> {
+        let mut terms = Vec::new();
+        Self::terms(expr, &mut terms);
+        let (mut q, mut r) = (num(0), num(0));
+        for term in terms {
+            let multiple = match &*term {
+                Expr::Num(n) if n % k == 0 => Some(num(n / k)),
+                Expr::Mul(a, b) => match **b {
+                    Expr::Num(m) if m % k == 0 => Some(self.mul(a.clone(), num(m / k))),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match multiple {
+                Some(multiple) => q = self.add(q, multiple),
+                None => r = self.add(r, term),
+            }
+        }
+        let ((qlo, _), (rlo, rhi)) = (self.range(&q), self.range(&r));
+        if qlo >= 0 && rlo >= 0 && rhi < k {
+            Some((q, r))
         } else {
-            digits[y] = 9;
-            digits[x] = 9 - val;
+            None
         }
     }
 
-    digits
-        .into_iter()
-        .map(|v| ((v as u8) + b'0') as char)
-        .collect()
-}
-fn part2(input: &[Constraint]) -> String {
-    let mut digits = [0i8; 14];
-    for Constraint { x, y, val } in input.iter().copied() {
-        if val < 0 {
-            digits[x] = -val + 1;
-            digits[y] = 1;
+    fn div(&self, a: Sym, k: i64) -> Sym {
+        let (lo, hi) = self.range(&a);
+        match *a {
+            _ if k == 1 => a,
+            Expr::Num(n) => num(n / k),
+            _ if lo > -k.abs() && hi < k.abs() => num(0),
+            _ => match self.split(&a, k) {
+                Some((q, _)) if k > 0 => q,
+                _ => Rc::new(Expr::Div(a, k)),
+            },
+        }
+    }
+
+    fn modulo(&self, a: Sym, k: i64) -> Sym {
+        let (lo, hi) = self.range(&a);
+        match *a {
+            Expr::Num(n) => num(n % k),
+            _ if lo >= 0 && hi < k => a,
+            _ => match self.split(&a, k) {
+                Some((_, r)) => r,
+                None => Rc::new(Expr::Mod(a, k)),
+            },
+        }
+    }
+
+    /// Whether `a` equals `b`, if we can tell
+    fn decide(&self, a: &Sym, b: &Sym) -> Option<bool> {
+        let ((alo, ahi), (blo, bhi)) = (self.range(a), self.range(b));
+        if ahi < blo || bhi < alo {
+            Some(false)
+        } else if a == b || (alo == ahi && blo == bhi) {
+            Some(true)
         } else {
-            digits[y] = val + 1;
-            digits[x] = 1;
+            None
         }
     }
 
-    digits
-        .into_iter()
-        .map(|v| ((v as u8) + b'0') as char)
-        .collect()
+    /// Narrow `digits[d]` to `lo..=hi`, returning whether that's possible
+    fn narrow(&mut self, d: usize, lo: i64, hi: i64) -> bool {
+        let range = &mut self.digits[d];
+        *range = (range.0.max(lo), range.1.min(hi));
+        range.0 <= range.1
+    }
+
+    /// The relationship between digits which `a` equalling `b` needs
+    fn compare(&self, n: usize, a: &Sym, b: &Sym) -> Result<Comparison> {
+        let unsolvable = || {
+            format!(
+                "Instruction {} compares {} with {}, which isn't a relationship between digits we can solve",
+                n + 1,
+                a,
+                b
+            )
+        };
+        let ((mut digits, an), (bdigits, bn)) = match (linear(a), linear(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Err(unsolvable().into()),
+        };
+        for (d, k) in bdigits {
+            *digits.entry(d).or_insert(0) -= k;
+        }
+        digits.retain(|_, k| *k != 0);
+        // So now the digits times their multiples, plus c, must be 0
+        let c = an - bn;
+        let digits: Vec<_> = digits.into_iter().collect();
+        Ok(match digits[..] {
+            [] => Comparison::Always(c == 0),
+            [(x, k)] if k.abs() == 1 => Comparison::When(Constraint::Fixed {
+                x,
+                val: -c * k,
+                equal: true,
+            }),
+            [(x, kx), (y, ky)] if kx.abs() == 1 && kx == -ky => {
+                Comparison::When(Constraint::Offset {
+                    x,
+                    y,
+                    val: c * kx,
+                    equal: true,
+                })
+            }
+            _ => return Err(unsolvable().into()),
+        })
+    }
+
+    /// Carry on assuming `constraint` holds, returning whether that's
+    /// possible
+    fn assume(&mut self, constraint: Constraint) -> bool {
+        self.constraints.push(constraint);
+        match constraint {
+            Constraint::Fixed {
+                x,
+                val,
+                equal: true,
+            } => self.narrow(x, val, val),
+            Constraint::Fixed {
+                x,
+                val,
+                equal: false,
+            } => match self.digits[x] {
+                (lo, hi) if lo == val && hi == val => false,
+                (lo, _) if lo == val => self.narrow(x, lo + 1, i64::MAX),
+                (_, hi) if hi == val => self.narrow(x, i64::MIN, hi - 1),
+                _ => true,
+            },
+            Constraint::Offset {
+                x,
+                y,
+                val,
+                equal: true,
+            } => {
+                let ((xlo, xhi), (ylo, yhi)) = (self.digits[x], self.digits[y]);
+                self.narrow(y, xlo + val, xhi + val) && self.narrow(x, ylo - val, yhi - val)
+            }
+            Constraint::Offset {
+                x,
+                y,
+                val,
+                equal: false,
+            } => match (self.digits[x], self.digits[y]) {
+                ((xlo, xhi), (ylo, yhi)) if xlo == xhi && ylo == yhi => yhi != xhi + val,
+                _ => true,
+            },
+        }
+    }
+
+    /// Operand `val` of instruction `n`, which has to be a constant
+    fn constant(&self, n: usize, val: BVal, operand: Operand) -> Result<i64> {
+        match *self.value(val) {
+            Expr::Num(k) if operand.allows(k) => Ok(k),
+            ref val => Err(format!(
+                "Instruction {} {} by {}, which isn't a constant we can use",
+                n + 1,
+                operand.verb(),
+                val
+            )
+            .into()),
+        }
+    }
+
+    fn value(&self, val: BVal) -> Sym {
+        match val {
+            BVal::Reg(r) => self.regs[r.regnum()].clone(),
+            BVal::Num(n) => num(n),
+        }
+    }
+
+    /// Run instruction `n`, giving every way it could go
+    fn exec(mut self, n: usize, instr: Instruction) -> Result<Vec<Path>> {
+        match instr {
+            Instruction::Input(r) => {
+                self.regs[r.regnum()] = Rc::new(Expr::Digit(self.digits.len()));
+                self.digits.push((1, 9));
+            }
+            Instruction::Add(r, b) => {
+                let (a, b) = (self.regs[r.regnum()].clone(), self.value(b));
+                self.regs[r.regnum()] = self.add(a, b);
+            }
+            Instruction::Mul(r, b) => {
+                let (a, b) = (self.regs[r.regnum()].clone(), self.value(b));
+                self.regs[r.regnum()] = self.mul(a, b);
+            }
+            Instruction::Div(r, b) => {
+                let k = self.constant(n, b, Operand::Divisor)?;
+                self.regs[r.regnum()] = self.div(self.regs[r.regnum()].clone(), k);
+            }
+            Instruction::Mod(r, b) => {
+                let k = self.constant(n, b, Operand::Modulus)?;
+                let a = self.regs[r.regnum()].clone();
+                if self.range(&a).0 < 0 {
+                    return Err(format!(
+                        "Instruction {} takes the modulus of {}, which might be negative",
+                        n + 1,
+                        a
+                    )
+                    .into());
+                }
+                self.regs[r.regnum()] = self.modulo(a, k);
+            }
+            Instruction::Eql(r, b) => {
+                let (a, b) = (self.regs[r.regnum()].clone(), self.value(b));
+                let equal = match self.decide(&a, &b) {
+                    Some(equal) => Comparison::Always(equal),
+                    None => self.compare(n, &a, &b)?,
+                };
+                match equal {
+                    Comparison::Always(equal) => self.regs[r.regnum()] = num(equal as i64),
+                    Comparison::When(constraint) => {
+                        let mut equal = self.clone();
+                        equal.regs[r.regnum()] = num(1);
+                        self.regs[r.regnum()] = num(0);
+                        let ways = [(equal, constraint), (self, constraint.negated())];
+                        return Ok(ways
+                            .into_iter()
+                            .filter_map(|(mut path, constraint)| {
+                                path.assume(constraint).then_some(path)
+                            })
+                            .collect());
+                    }
+                }
+            }
+        }
+        Ok(vec![self])
+    }
+
+    /// The biggest (or smallest) digits this way through allows
+    fn solve(&self, biggest: bool) -> Option<Vec<i64>> {
+        // Each digit as an offset from another digit it's tied to
+        let mut tied: Vec<_> = (0..self.digits.len()).map(|d| (d, 0)).collect();
+        for constraint in &self.constraints {
+            if let Constraint::Offset {
+                x,
+                y,
+                val,
+                equal: true,
+            } = *constraint
+            {
+                let ((rx, ox), (ry, oy)) = (tied[x], tied[y]);
+                if rx == ry {
+                    if oy != ox + val {
+                        return None;
+                    }
+                    continue;
+                }
+                for tie in tied.iter_mut().filter(|(r, _)| *r == ry) {
+                    *tie = (rx, tie.1 + ox + val - oy);
+                }
+            }
+        }
+        // Fixed digits are already narrowed to their value
+        let mut ranges: HashMap<usize, (i64, i64)> = HashMap::new();
+        for (&(root, offset), &(lo, hi)) in tied.iter().zip(self.digits.iter()) {
+            let range = ranges.entry(root).or_insert((i64::MIN, i64::MAX));
+            *range = (range.0.max(lo - offset), range.1.min(hi - offset));
+        }
+        // Then give each group a value in the order they first turn up, as
+        // big (or small) as we can without digits being equal which mustn't
+        let mut roots: Vec<usize> = Vec::new();
+        for &(root, _) in &tied {
+            if !roots.contains(&root) {
+                roots.push(root);
+            }
+        }
+        let mut values = HashMap::new();
+        if !self.pick(&tied, &ranges, &roots, &mut values, biggest) {
+            return None;
+        }
+        Some(
+            tied.iter()
+                .map(|(root, offset)| values[root] + offset)
+                .collect(),
+        )
+    }
+
+    /// Pick values for the groups of tied digits in `roots`, given the ones
+    /// already in `values`, returning whether there are any which work
+    fn pick(
+        &self,
+        tied: &[(usize, i64)],
+        ranges: &HashMap<usize, (i64, i64)>,
+        roots: &[usize],
+        values: &mut HashMap<usize, i64>,
+        biggest: bool,
+    ) -> bool {
+        let (root, rest) = match roots.split_first() {
+            Some(split) => split,
+            None => return true,
+        };
+        let (lo, hi) = ranges[root];
+        let mut candidates: Vec<i64> = (lo..=hi).collect();
+        if biggest {
+            candidates.reverse();
+        }
+        for value in candidates {
+            values.insert(*root, value);
+            let digit = |d: usize| {
+                let (root, offset) = tied[d];
+                values.get(&root).map(|value| value + offset)
+            };
+            if self.constraints.iter().all(|c| c.allows(digit))
+                && self.pick(tied, ranges, rest, values, biggest)
+            {
+                return true;
+            }
+        }
+        values.remove(root);
+        false
+    }
+}
+
+/// Every way through the program which leaves z as 0
+fn analyse(prog: &[Instruction]) -> Result<Vec<Path>> {
+    let mut paths = vec![Path::new()];
+    for (n, instr) in prog.iter().enumerate() {
+        let mut next = Vec::new();
+        for path in paths {
+            next.extend(path.exec(n, *instr)?);
+        }
+        if next.len() > MAX_PATHS {
+            return Err(format!(
+                "There are {} ways through the program by instruction {}, which is too many",
+                next.len(),
+                n + 1
+            )
+            .into());
+        }
+        paths = next;
+    }
+    let mut zero = Vec::new();
+    for path in paths {
+        let z = &path.regs[Reg::Z.regnum()];
+        if **z == Expr::Num(0) {
+            zero.push(path);
+        } else if matches!(path.range(z), (lo, hi) if lo <= 0 && hi >= 0) {
+            return Err(format!("z ends up as {}, which we can't tell is 0 or not", z).into());
+        }
+    }
+    if zero.is_empty() {
+        return Err("No way through the program leaves z as 0".into());
+    }
+    Ok(zero)
 }
 
-fn main() -> Result<()> {
+/// The biggest (or smallest) model number the program accepts
+fn solve(prog: &[Instruction], paths: &[Path], biggest: bool) -> Result<String> {
+    let candidates = paths.iter().filter_map(|path| path.solve(biggest));
+    let digits = if biggest {
+        candidates.max()
+    } else {
+        candidates.min()
+    }
+    .ok_or("None of the ways through the program leaving z as 0 have digits which work")?;
+    // Every constraint holds, so this is only checking the analysis
+    if run_program(prog, digits.iter().copied())[3] != 0 {
+        return Err(format!(
+            "{:?} meets every constraint, but the program doesn't accept it",
+            digits
+        )
+        .into());
+    }
+    Ok(digits.into_iter().map(|d| d.to_string()).collect())
+}
+
+pub fn main() -> Result<()> {
     let input: Vec<Instruction> = read_input_as_vec(24)?;
-    let simplified = simplify_prog(&input);
-    let constraints = gen_constraints(&simplified);
-    println!("Part 1: {}", part1(&constraints));
-    println!("Part 2: {}", part2(&constraints));
+    let paths = analyse(&input)?;
+    println!("Part 1: {}", solve(&input, &paths, true)?);
+    println!("Part 2: {}", solve(&input, &paths, false)?);
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A push of `digits[0] + 7` and a pop adding -4 to compare with
+    /// `digits[1]`, in the shape of the real thing
+    static TEMPLATE: &str = r"inp w
+mul x 0
+add x z
+mod x 26
+div z 1
+add x 12
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 7
+mul y x
+add z y
+inp w
+mul x 0
+add x z
+mod x 26
+div z 26
+add x -4
+eql x w
+eql x 0
+mul y 0
+add y 25
+mul y x
+add y 1
+mul z y
+mul y 0
+add y w
+add y 3
+mul y x
+add z y";
+
+    /// Not that shape at all, with digits[1] having to be digits[0] + 2, and
+    /// digits[2] having to be 4
+    static FREEFORM: &str = r"inp w
+add w 2
+inp x
+eql w x
+eql w 0
+mul w 100
+inp y
+eql y 4
+eql y 0
+add z w
+add z y";
+
+    #[test]
+    fn template() {
+        let prog: Vec<Instruction> = input_as_vec(TEMPLATE).unwrap();
+        let paths = analyse(&prog).unwrap();
+        assert_eq!(
+            paths[0].constraints,
+            [Constraint::Offset {
+                x: 0,
+                y: 1,
+                val: 3,
+                equal: true
+            }]
+        );
+        assert_eq!(solve(&prog, &paths, true).unwrap(), "69");
+        assert_eq!(solve(&prog, &paths, false).unwrap(), "14");
+    }
+
+    #[test]
+    fn freeform() {
+        let prog: Vec<Instruction> = input_as_vec(FREEFORM).unwrap();
+        let paths = analyse(&prog).unwrap();
+        assert_eq!(solve(&prog, &paths, true).unwrap(), "794");
+        assert_eq!(solve(&prog, &paths, false).unwrap(), "134");
+    }
+
+    #[test]
+    fn differing() {
+        // z is 0 when digits[0] isn't digits[1] and digits[2] isn't 1
+        let prog: Vec<Instruction> =
+            input_as_vec("inp w\ninp x\neql w x\nadd z w\ninp y\neql y 1\nadd z y").unwrap();
+        let paths = analyse(&prog).unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(
+            paths[0]
+                .constraints
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "digits[1] must not equal digits[0] + 0",
+                "digits[2] must not be 1"
+            ]
+        );
+        assert_eq!(solve(&prog, &paths, true).unwrap(), "989");
+        assert_eq!(solve(&prog, &paths, false).unwrap(), "122");
+    }
+
+    #[test]
+    fn unsolvable() {
+        let square: Vec<Instruction> = input_as_vec("inp w\nmul w w\nadd z w").unwrap();
+        let err = analyse(&square).unwrap_err().to_string();
+        assert_eq!(err, "No way through the program leaves z as 0");
+        let divide: Vec<Instruction> = input_as_vec("inp w\ninp z\ndiv z w").unwrap();
+        let err = analyse(&divide).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Instruction 3 divides by digits[0], which isn't a constant we can use"
+        );
+    }
+}
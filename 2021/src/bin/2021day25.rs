@@ -168,7 +168,7 @@ v.v..>>v.v
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(25)?;
     let input = SeaFloor::from_str(&input).unwrap();
     println!("Loaded sea floor {}x{}", input.width, input.height);
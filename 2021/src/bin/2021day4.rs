@@ -178,7 +178,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(4)?;
     let input = BingoGame::from_str(&input)?;
     println!("Part 1: {}", part1(&input));
@@ -100,7 +100,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<Vent> = read_input_as_vec(5)?;
     let input = input.into();
     println!("Part 1: {}", part1(&input));
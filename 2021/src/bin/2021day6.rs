@@ -62,7 +62,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(6)?;
     let input = input_by_split_pat(input, ",")?;
     println!("Part 1: {}", part1(&input));
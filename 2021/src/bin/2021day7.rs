@@ -39,7 +39,7 @@ mod test {
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input = read_input(7)?;
     let mut input = input_by_split_pat(input, ",")?;
     input.sort_unstable();
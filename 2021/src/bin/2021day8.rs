@@ -230,7 +230,7 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
     }
 }
 
-fn main() -> Result<()> {
+pub fn main() -> Result<()> {
     let input: Vec<SpacedString> = read_input_as_vec(8)?;
     println!("Part 1: {}", part1(&input));
     println!("Part 2: {}", part2(&input));
@@ -1,78 +1,5 @@
 use aoc2022::*;
 
-#[path = "2022day1.rs"]
-mod day01;
-#[path = "2022day2.rs"]
-mod day02;
-#[path = "2022day3.rs"]
-mod day03;
-#[path = "2022day4.rs"]
-mod day04;
-#[path = "2022day5.rs"]
-mod day05;
-#[path = "2022day6.rs"]
-mod day06;
-#[path = "2022day7.rs"]
-mod day07;
-#[path = "2022day8.rs"]
-mod day08;
-#[path = "2022day9.rs"]
-mod day09;
-#[path = "2022day10.rs"]
-mod day10;
-#[path = "2022day11.rs"]
-mod day11;
-#[path = "2022day12.rs"]
-mod day12;
-#[path = "2022day13.rs"]
-mod day13;
-#[path = "2022day14.rs"]
-mod day14;
-#[path = "2022day15.rs"]
-mod day15;
-//#[path = "2022day16.rs"]
-//mod day16;
-#[path = "2022day17.rs"]
-mod day17;
-#[path = "2022day18.rs"]
-mod day18;
-#[path = "2022day19.rs"]
-mod day19;
-#[path = "2022day20.rs"]
-mod day20;
-#[path = "2022day21.rs"]
-mod day21;
-//#[path = "2022day22.rs"]
-//mod day22;
-#[path = "2022day23.rs"]
-mod day23;
-#[path = "2022day24.rs"]
-mod day24;
-
 fn main() -> Result<()> {
-    day01::main()?;
-    day02::main()?;
-    day03::main()?;
-    day04::main()?;
-    day05::main()?;
-    day06::main()?;
-    day07::main()?;
-    day08::main()?;
-    day09::main()?;
-    day10::main()?;
-    day11::main()?;
-    day12::main()?;
-    day13::main()?;
-    day14::main()?;
-    day15::main()?;
-    //day16::main()?;
-    day17::main()?;
-    day18::main()?;
-    day19::main()?;
-    day20::main()?;
-    day21::main()?;
-    //day22::main()?;
-    day23::main()?;
-    day24::main()?;
-    Ok(())
+    aoc_common::registry::run_year(&days::YEAR)
 }
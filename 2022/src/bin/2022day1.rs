@@ -17,6 +17,16 @@ fn part2(input: &[Vec<u64>]) -> u64 {
         .sum()
 }
 
+solution! {
+    Day1: Vec<Vec<u64>> = |input| input_as_groups(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 1)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -49,10 +59,3 @@ mod test {
         assert_eq!(part2(&input), 45000);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Vec<u64>> = read_input_as_groups(1)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -51,7 +51,7 @@ fn within(spos: i32, xpos: usize) -> bool {
     xpos >= spos - 1 && xpos <= spos + 1
 }
 
-fn part2(input: &[InputStep]) -> String {
+fn draw_screen(input: &[InputStep]) -> String {
     let cycle_map = run_machine(input);
     let mut ret = String::new();
     for (cycle, spos) in cycle_map.iter().copied().enumerate().skip(1) {
@@ -68,6 +68,22 @@ fn part2(input: &[InputStep]) -> String {
     ret
 }
 
+fn part2(input: &[InputStep]) -> String {
+    let screen = draw_screen(input);
+    // If the letters can't be read, the screen itself is still an answer
+    ocr::read_grid(&screen).unwrap_or(screen)
+}
+
+solution! {
+    Day10: Vec<InputStep> = |input| input_as_vec(input),
+    part1 -> i32,
+    part2 -> String,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 10)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -238,13 +254,6 @@ noop
     #[test]
     fn testcase2() {
         let input: Vec<InputStep> = input_as_vec(TEST_INPUT).unwrap();
-        assert_eq!(part2(&input), TEST_OUTPUT);
+        assert_eq!(draw_screen(&input), TEST_OUTPUT);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<InputStep> = read_input_as_vec(10)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -135,6 +135,16 @@ fn part2(input: &[Monkey]) -> usize {
         .product()
 }
 
+solution! {
+    Day11: Vec<Monkey> = |input| input_as_chunks(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 11)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -181,10 +191,3 @@ Monkey 3:
         assert_eq!(part2(&input), 2713310158);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Monkey> = read_input_as_chunks(11)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
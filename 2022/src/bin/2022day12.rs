@@ -82,6 +82,16 @@ fn part2(input: &Input) -> usize {
         .unwrap()
 }
 
+solution! {
+    Day12: Input = |input| Ok(Input::from(input)),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 12)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -106,11 +116,3 @@ abdefghi
         assert_eq!(part2(&input), 29);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input(12)?;
-    let input = Input::from(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -85,6 +85,16 @@ fn part2(input: &[InputPair]) -> usize {
     (idx1 + 1) * (idx2 + 1)
 }
 
+solution! {
+    Day13: Vec<InputPair> = |input| input_as_chunks(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 13)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -127,10 +137,3 @@ mod test {
         assert_eq!(part2(&input), 140);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<InputPair> = read_input_as_chunks(13)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
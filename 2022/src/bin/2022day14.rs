@@ -105,6 +105,16 @@ fn part2(input: &Cave) -> usize {
     unreachable!()
 }
 
+solution! {
+    Day14: Cave = |input| Ok(Cave::new(&input_as_vec(input)?)),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 14)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -128,11 +138,3 @@ mod test {
         assert_eq!(part2(&cave), 93);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input_as_vec(14).unwrap();
-    let cave = Cave::new(&input);
-    println!("Part 1: {}", part1(&cave));
-    println!("Part 2: {}", part2(&cave));
-    Ok(())
-}
@@ -97,6 +97,16 @@ fn part2(input: &[Sensor], range: i32) -> u64 {
     unreachable!()
 }
 
+solution! {
+    Day15: Vec<Sensor> = |input| input_as_vec(input),
+    part1 -> usize = |input| part1(input, 2_000_000),
+    part2 -> u64 = |input| part2(input, 4_000_000),
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 15)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -130,10 +140,3 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3
         assert_eq!(part2(&input, 20), 56_000_011);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Sensor> = read_input_as_vec(15)?;
-    println!("Part 1: {}", part1(&input, 2_000_000));
-    println!("Part 2: {}", part2(&input, 4_000_000));
-    Ok(())
-}
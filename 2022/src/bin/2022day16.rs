@@ -142,6 +142,16 @@ fn part2(input: &Network) -> usize {
     max
 }
 
+solution! {
+    Day16: Network = |input| Ok(Network::new(&input_as_vec(input)?)),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 16)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -173,11 +183,3 @@ Valve JJ has flow rate=21; tunnel leads to valve II
         assert_eq!(part2(&network), 1707);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input_as_vec(16).unwrap();
-    let network = Network::new(&input);
-    println!("Part 1: {}", part1(&network));
-    println!("Part 2: {}", part2(&network));
-    Ok(())
-}
@@ -145,6 +145,16 @@ fn part2(input: &str) -> i64 {
     shaft.drop_rocks(input, 1000000000000)
 }
 
+solution! {
+    Day17: String = |input| Ok(input.trim().to_string()),
+    part1 -> i64,
+    part2 -> i64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 17)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -161,11 +171,3 @@ mod test {
         assert_eq!(part2(TEST_INPUT), 1514285714288);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input(17)?;
-    let input = input.trim();
-    println!("Part 1: {}", part1(input));
-    println!("Part 2: {}", part2(input));
-    Ok(())
-}
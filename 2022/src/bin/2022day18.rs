@@ -101,6 +101,16 @@ fn part2(input: &[LavaCube]) -> usize {
     total_surface
 }
 
+solution! {
+    Day18: Vec<LavaCube> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 18)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -132,10 +142,3 @@ mod test {
         assert_eq!(part2(&input), 64);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<LavaCube> = read_input_as_vec(18)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -199,6 +199,16 @@ fn part2(input: &[Blueprint]) -> usize {
         .product()
 }
 
+solution! {
+    Day19: Vec<Blueprint> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 19)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -220,10 +230,3 @@ Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsid
         assert_eq!(part2(&input), (46 * 62));
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Blueprint> = read_input_as_vec(19)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -80,6 +80,16 @@ fn part2(input: &[Round]) -> usize {
         .sum()
 }
 
+solution! {
+    Day2: Vec<Round> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 2)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -103,10 +113,3 @@ C Z
         assert_eq!(part2(&input), 12);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Round> = read_input_as_vec(2)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
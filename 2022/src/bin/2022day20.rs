@@ -82,6 +82,16 @@ fn part2(input: &[i64]) -> i64 {
     dec.key(input_zeropos)
 }
 
+solution! {
+    Day20: Vec<i64> = |input| input_as_vec(input),
+    part1 -> i64,
+    part2 -> i64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 20)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -125,10 +135,3 @@ mod test {
         assert_eq!(part2(&input), 1623178306);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input_as_vec(20)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -192,6 +192,16 @@ fn part2(input: &[MonkeyDef]) -> MVal {
     value
 }
 
+solution! {
+    Day21: Vec<MonkeyDef> = |input| input_as_vec(input),
+    part1 -> MVal,
+    part2 -> MVal,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 21)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -226,10 +236,3 @@ hmdt: 32
         assert_eq!(part2(&input), 301);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<MonkeyDef> = read_input_as_vec(21)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
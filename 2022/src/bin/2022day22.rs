@@ -362,12 +362,14 @@ fn part2(input: &Lock) -> usize {
     input.follow_plan()
 }
 
+solution! {
+    Day22: Lock = |input| Ok(Lock::from(input)),
+    part1 -> usize,
+    part2 -> usize,
+}
+
 pub fn main() -> Result<()> {
-    let input = read_input(22)?;
-    let input = Lock::from(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
+    run_solution(SOLUTION, 22)
 }
 
 #[cfg(test)]
@@ -168,6 +168,16 @@ fn part2(input: &ElfMap) -> usize {
     unreachable!()
 }
 
+solution! {
+    Day23: ElfMap = |input| Ok(ElfMap::from(input)),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 23)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -214,11 +224,3 @@ mod test {
         assert_eq!(part2(&input), 20);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input(23)?;
-    let input = ElfMap::from(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -134,6 +134,16 @@ fn part2(input: &Valley) -> i32 {
     input.trek(input.entrance, input.exit, back)
 }
 
+solution! {
+    Day24: Valley = |input| Ok(Valley::from(input)),
+    part1 -> i32,
+    part2 -> i32,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 24)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -173,11 +183,3 @@ mod test {
         assert_eq!(part2(&input), 54);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input(24)?;
-    let input = Valley::from(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -59,6 +59,16 @@ fn part2(input: &[Rucksack]) -> u64 {
         .sum()
 }
 
+solution! {
+    Day3: Vec<Rucksack> = |input| input_as_vec(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 3)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -84,10 +94,3 @@ CrZsJsPPZsGzwwsLwLmpwMDw
         assert_eq!(part2(&input), 70);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Rucksack> = read_input_as_vec(3)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
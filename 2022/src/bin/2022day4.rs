@@ -42,6 +42,16 @@ fn part2(input: &[Pairing]) -> usize {
     input.iter().copied().filter(Pairing::overlapped).count()
 }
 
+solution! {
+    Day4: Vec<Pairing> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 4)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -67,10 +77,3 @@ mod test {
         assert_eq!(part2(&input), 4);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<Pairing> = read_input_as_vec(4)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -130,6 +130,16 @@ fn part2(input: &Input) -> String {
     piles.tips()
 }
 
+solution! {
+    Day5: Input = |input| Ok(Input::from(input)),
+    part1 -> String,
+    part2 -> String,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 5)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -159,11 +169,3 @@ move 1 from 1 to 2
         assert_eq!(part2(&input), "MCD");
     }
 }
-
-pub fn main() -> Result<()> {
-    let input = read_input(5)?;
-    let input = Input::from(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
@@ -22,6 +22,16 @@ fn part2(input: &str) -> usize {
         .unwrap()
 }
 
+solution! {
+    Day6: String = |input| Ok(input.to_string()),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 6)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -48,10 +58,3 @@ mod test {
         }
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: String = read_input(6)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
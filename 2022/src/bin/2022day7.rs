@@ -74,6 +74,16 @@ fn part2(input: &[InputLine]) -> usize {
         .unwrap()
 }
 
+solution! {
+    Day7: Vec<InputLine> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 7)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -115,10 +125,3 @@ $ ls
         assert_eq!(part2(&input), 24933642);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<InputLine> = read_input_as_vec(7)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
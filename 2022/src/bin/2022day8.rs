@@ -1,29 +1,15 @@
 use aoc2022::*;
 
 struct Forest {
-    width: usize,
-    height: usize,
-    rows: Vec<Vec<u8>>,
+    trees: Grid<u8>,
 }
 
-impl<T> From<T> for Forest
-where
-    T: AsRef<str>,
-{
-    fn from(input: T) -> Self {
-        let rows = input
-            .as_ref()
-            .trim()
-            .lines()
-            .map(|l| l.trim().bytes().map(|b| b - b'0').collect_vec())
-            .collect_vec();
-        let width = rows[0].len();
-        let height = rows.len();
-        Self {
-            width,
-            height,
-            rows,
-        }
+impl FromStr for Forest {
+    type Err = GenError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let trees = Grid::try_parse(input, |c| c.to_digit(10).map(|d| d as u8))?;
+        Ok(Self { trees })
     }
 }
 
@@ -47,20 +33,21 @@ impl Forest {
     }
 }
 fn part1(input: &Forest) -> usize {
-    let mut total = input.width * 2 + input.height * 2 - 4;
+    let (width, height) = (input.trees.width(), input.trees.height());
+    let mut total = width * 2 + height * 2 - 4;
 
-    for row in 1..(input.height - 1) {
-        for col in 1..(input.width - 1) {
+    for row in 1..(height - 1) {
+        for col in 1..(width - 1) {
             #[allow(unused_parens)]
             if (
                 // left to right
-                Forest::is_highest(input.rows[row].iter().copied().skip(col)) ||
+                Forest::is_highest(input.trees.row(row).iter().copied().skip(col)) ||
                 // right to left
-                Forest::is_highest(input.rows[row].iter().copied().rev().skip(input.width - col - 1)) ||
+                Forest::is_highest(input.trees.row(row).iter().copied().rev().skip(width - col - 1)) ||
                 // top to bottom
-                Forest::is_highest(input.rows.iter().map(|v|v[col]).skip(row)) ||
+                Forest::is_highest(input.trees.column(col).copied().skip(row)) ||
                 // bottom to top
-                Forest::is_highest(input.rows.iter().rev().map(|v|v[col]).skip(input.height-row-1))
+                Forest::is_highest(input.trees.column(col).copied().rev().skip(height-row-1))
             ) {
                 total += 1;
             }
@@ -71,20 +58,21 @@ fn part1(input: &Forest) -> usize {
 }
 
 fn part2(input: &Forest) -> usize {
+    let (width, height) = (input.trees.width(), input.trees.height());
     let mut best_score = 0;
 
-    for row in 1..(input.height - 1) {
-        for col in 1..(input.width - 1) {
+    for row in 1..(height - 1) {
+        for col in 1..(width - 1) {
             #[allow(unused_parens)]
             let score = (
                 // left to right
-                Forest::sees(input.rows[row].iter().copied().skip(col)) *
+                Forest::sees(input.trees.row(row).iter().copied().skip(col)) *
                 // right to left
-                Forest::sees(input.rows[row].iter().copied().rev().skip(input.width - col - 1)) *
+                Forest::sees(input.trees.row(row).iter().copied().rev().skip(width - col - 1)) *
                 // top to bottom
-                Forest::sees(input.rows.iter().map(|v|v[col]).skip(row)) *
+                Forest::sees(input.trees.column(col).copied().skip(row)) *
                 // bottom to top
-                Forest::sees(input.rows.iter().rev().map(|v|v[col]).skip(input.height-row-1))
+                Forest::sees(input.trees.column(col).copied().rev().skip(height-row-1))
             );
             if score > best_score {
                 best_score = score;
@@ -95,6 +83,16 @@ fn part2(input: &Forest) -> usize {
     best_score
 }
 
+solution! {
+    Day8: Forest = |input| input.parse(),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 8)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -108,21 +106,19 @@ mod test {
 
     #[test]
     fn testcase1() {
-        let input = Forest::from(TEST_INPUT);
+        let input: Forest = TEST_INPUT.parse().unwrap();
         assert_eq!(part1(&input), 21);
     }
 
     #[test]
     fn testcase2() {
-        let input = Forest::from(TEST_INPUT);
+        let input: Forest = TEST_INPUT.parse().unwrap();
         assert_eq!(part2(&input), 8);
     }
-}
 
-pub fn main() -> Result<()> {
-    let input: String = read_input(8)?;
-    let input = input.into();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
+    #[test]
+    fn bad_forest() {
+        assert!("303\n25".parse::<Forest>().is_err());
+        assert!("303\n2x5".parse::<Forest>().is_err());
+    }
 }
@@ -102,6 +102,16 @@ fn part2(input: &[InputStep]) -> usize {
     tailpositions.len()
 }
 
+solution! {
+    Day9: Vec<InputStep> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 9)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -140,10 +150,3 @@ U 20"#;
         assert_eq!(part2(&input), 36);
     }
 }
-
-pub fn main() -> Result<()> {
-    let input: Vec<InputStep> = read_input_as_vec(9)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
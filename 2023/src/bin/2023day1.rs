@@ -1,12 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input: Vec<String> = read_input_as_vec(1)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &[String]) -> u64 {
     // For each entry we want the first and last digits and then we treat that as a number and sum them
     input
@@ -55,6 +48,16 @@ fn part2(input: &[String]) -> u64 {
         .sum()
 }
 
+solution! {
+    Day1: Vec<String> = |input| input_as_vec(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 1)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
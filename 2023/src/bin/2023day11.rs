@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(11)?;
-    let input = parse_map(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct Map {
     galaxies: Vec<(usize, usize)>,
@@ -73,6 +65,16 @@ fn part2(input: &Map) -> usize {
     sum_distances(input, 999_999)
 }
 
+solution! {
+    Day11: Map = |input| Ok(parse_map(input)),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 11)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
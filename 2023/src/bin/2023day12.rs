@@ -1,12 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input: Vec<SpringMap> = read_input_as_vec(12)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct SpringMap {
     springs: Vec<Spring>,
@@ -164,6 +157,16 @@ fn part2(input: &[SpringMap]) -> usize {
     input.iter().map(|l| l.unfold().ways()).sum()
 }
 
+solution! {
+    Day12: Vec<SpringMap> = |input| input_as_vec(input),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 12)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
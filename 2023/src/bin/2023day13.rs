@@ -1,16 +1,9 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input: Vec<Mountains> = read_input_as_chunks(13)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct Mountains {
     #[allow(unused)]
-    grid: Vec<Vec<Cell>>,
+    grid: Grid<Cell>,
     perfect_hmirror: Option<usize>,
     perfect_vmirror: Option<usize>,
     smudged_hmirror: Option<usize>,
@@ -21,22 +14,13 @@ impl FromStr for Mountains {
     type Err = Infallible;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let grid = s
-            .trim()
-            .lines()
-            .map(|s| s.trim().chars().map(Cell::from_char).collect_vec())
-            .collect_vec();
+        let grid = Grid::parse(s, Cell::from_char).unwrap();
 
         let perfect_hmirror = Self::find_hmirror(&grid, 0);
         let smudged_hmirror = Self::find_hmirror(&grid, 1);
 
         // For vertical mirror, let's just transpose the grid and ask the same question
-        let mut tgrid = vec![Vec::new(); grid[0].len()];
-        for row in &grid {
-            for (tr, c) in row.iter().enumerate() {
-                tgrid[tr].push(*c);
-            }
-        }
+        let tgrid = grid.transpose();
         let perfect_vmirror = Self::find_hmirror(&tgrid, 0);
         let smudged_vmirror = Self::find_hmirror(&tgrid, 1);
 
@@ -51,21 +35,22 @@ impl FromStr for Mountains {
 }
 
 impl Mountains {
-    fn find_hmirror(grid: &[Vec<Cell>], smudge_goal: usize) -> Option<usize> {
+    fn find_hmirror(grid: &Grid<Cell>, smudge_goal: usize) -> Option<usize> {
         // A horizontal mirror involves checking if rows are equal...
         let mut hmirror = None;
-        'hmirror: for hpos in 1..grid.len() {
+        'hmirror: for hpos in 1..grid.height() {
             // A mirror at hpos as rows 0..hpos before it and hpos.. after it
             // The mirror is valid if all possible rows before / after match or are missing
             let mut smudges = 0;
             for mrow in 0..hpos {
                 let dist = hpos - mrow - 1;
                 let rrow = hpos + dist;
-                if let Some(lower) = grid.get(rrow) {
-                    smudges += grid[mrow]
+                if rrow < grid.height() {
+                    smudges += grid
+                        .row(mrow)
                         .iter()
                         .copied()
-                        .zip(lower.iter().copied())
+                        .zip(grid.row(rrow).iter().copied())
                         .filter(|&(a, b)| a != b)
                         .count();
                     if smudges > smudge_goal {
@@ -118,6 +103,16 @@ fn part2(input: &[Mountains]) -> u64 {
     input.iter().map(Mountains::smudged_mirror_score).sum()
 }
 
+solution! {
+    Day13: Vec<Mountains> = |input| input_as_chunks(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 13)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
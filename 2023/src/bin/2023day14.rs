@@ -1,12 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input: Vec<Platform> = read_input_as_chunks(14)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Platform {
     grid: Vec<Vec<Cell>>,
@@ -162,6 +155,16 @@ fn part2(input: &[Platform]) -> u64 {
     plaf.north_load()
 }
 
+solution! {
+    Day14: Vec<Platform> = |input| input_as_chunks(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 14)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
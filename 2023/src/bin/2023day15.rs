@@ -1,12 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(15)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &str) -> u64 {
     input.trim().split(',').map(badhash).sum()
 }
@@ -59,6 +52,16 @@ fn badhash(s: &str) -> u64 {
     s.bytes().fold(0, |acc, b| ((acc + (b as u64)) * 17) & 0xff)
 }
 
+solution! {
+    Day15: String = |input| Ok(input.to_string()),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 15)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
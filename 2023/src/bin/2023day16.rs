@@ -2,14 +2,6 @@ use aoc2023::*;
 
 use rayon::prelude::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(16)?;
-    let input = LightMap::from_str(&input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &LightMap) -> usize {
     input.energised(1, 1, Facing::East)
 }
@@ -157,6 +149,16 @@ impl Tile {
     }
 }
 
+solution! {
+    Day16: LightMap = |input| Ok(LightMap::from_str(input)?),
+    part1 -> usize,
+    part2 -> usize,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 16)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(17)?;
-    let input = RoadMap::from_str(&input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &RoadMap) -> u64 {
     pathfinding::prelude::astar(
         &(0, 0, Facing::North),
@@ -32,35 +24,23 @@ fn part2(input: &RoadMap) -> u64 {
 
 #[derive(Debug, Clone)]
 struct RoadMap {
-    cost: Vec<Vec<u64>>,
+    cost: Grid<u64>,
 }
 
 impl FromStr for RoadMap {
-    type Err = Infallible;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let cost = s
-            .trim()
-            .lines()
-            .map(|s| s.trim().bytes().map(|b| (b - b'0') as u64).collect_vec())
-            .collect_vec();
+    type Err = GenError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let cost = Grid::try_parse(s, |c| c.to_digit(10).map(u64::from))?;
         Ok(Self { cost })
     }
 }
 
 impl RoadMap {
-    fn cost_to_enter(&self, row: i32, col: i32) -> u64 {
-        if row < 0 || col < 0 {
-            u64::MAX
-        } else {
-            let urow = row as usize;
-            let ucol = col as usize;
-            if urow >= self.cost.len() || ucol >= self.cost[0].len() {
-                u64::MAX
-            } else {
-                self.cost[urow][ucol]
-            }
-        }
+    fn cost_to_enter(&self, row: i32, col: i32) -> Option<u64> {
+        self.cost
+            .locate(row.into(), col.into())
+            .map(|pos| self.cost[pos])
     }
 
     fn successors(
@@ -97,24 +77,28 @@ impl RoadMap {
         for step in 1..=skip_dist {
             let row = srow + (rofs * step);
             let col = scol + (cofs * step);
-            cost = cost.saturating_add(self.cost_to_enter(row, col));
+            match self.cost_to_enter(row, col) {
+                Some(enter) => cost += enter,
+                None => return,
+            }
         }
         for step in 1..=try_dist {
             let row = srow + (rofs * (step + skip_dist));
             let col = scol + (cofs * (step + skip_dist));
-            cost = cost.saturating_add(self.cost_to_enter(row, col));
-            if cost < u64::MAX {
-                // We can enter this cell, so let's do so
-                ret.push(((row, col, sdir.turn_left_deg(90)), cost));
-                ret.push(((row, col, sdir.turn_right_deg(90)), cost));
-            }
+            // Once we've left the map there's no coming back
+            let Some(enter) = self.cost_to_enter(row, col) else {
+                return;
+            };
+            cost += enter;
+            ret.push(((row, col, sdir.turn_left_deg(90)), cost));
+            ret.push(((row, col, sdir.turn_right_deg(90)), cost));
         }
     }
 
     fn heuristic(&self, state: &(i32, i32, Facing)) -> u64 {
         // Our A* heuristic is simply the manhattan distance from here to there
-        let drow = (self.cost.len() as i32) - 1;
-        let dcol = (self.cost[0].len() as i32) - 1;
+        let drow = (self.cost.height() as i32) - 1;
+        let dcol = (self.cost.width() as i32) - 1;
         (state.0.abs_diff(drow) + state.1.abs_diff(dcol)) as u64
     }
 
@@ -122,28 +106,37 @@ impl RoadMap {
         // Success is if we've reached the bottom right
         let urow = state.0 as usize;
         let ucol = state.1 as usize;
-        urow == self.cost.len() - 1 && ucol == self.cost[0].len() - 1
+        urow == self.cost.height() - 1 && ucol == self.cost.width() - 1
     }
 }
 
+solution! {
+    Day17: RoadMap = |input| RoadMap::from_str(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 17)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     static TEST_INPUT: &str = r#"2413432311323
-    3215453535623
-    3255245654254
-    3446585845452
-    4546657867536
-    1438598798454
-    4457876987766
-    3637877979653
-    4654967986887
-    4564679986453
-    1224686865563
-    2546548887735
-    4322674655533
-    "#;
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533"#;
 
     #[test]
     fn testcase1() {
@@ -158,11 +151,10 @@ mod test {
     }
 
     static TEST_INPUT2: &str = r#"111111111111
-    999999999991
-    999999999991
-    999999999991
-    999999999991
-    "#;
+999999999991
+999999999991
+999999999991
+999999999991"#;
 
     #[test]
     fn testcase3() {
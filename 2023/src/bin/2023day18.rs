@@ -1,12 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input: Vec<Instruction> = read_input_as_vec(18)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &[Instruction]) -> i64 {
     let plan = DigPlan::from_instrs(input);
     plan.area()
@@ -106,6 +99,16 @@ impl DigPlan {
     }
 }
 
+solution! {
+    Day18: Vec<Instruction> = |input| input_as_vec(input),
+    part1 -> i64,
+    part2 -> i64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 18)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(19)?;
-    let input = PartSorter::from_str(&input)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &PartSorter) -> i64 {
     input
         .parts
@@ -289,6 +281,16 @@ struct AttrAssign {
     value: i64,
 }
 
+solution! {
+    Day19: PartSorter = |input| Ok(PartSorter::from_str(input)?),
+    part1 -> i64,
+    part2 -> i64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 19)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -61,13 +61,6 @@ struct Game {
     rounds: Rounds,
 }
 
-pub fn main() -> Result<()> {
-    let input: Vec<Game> = read_input_as_vec(2)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 impl Game {
     fn possible(&self, red: u64, green: u64, blue: u64) -> bool {
         for round in &self.rounds.rounds {
@@ -108,6 +101,16 @@ fn part2(input: &[Game]) -> u64 {
     input.iter().map(|game| game.power()).sum()
 }
 
+solution! {
+    Day2: Vec<Game> = |input| input_as_vec(input),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 2)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
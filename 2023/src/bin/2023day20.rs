@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(20)?;
-    let input = Circuit::from_str(&input)?;
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn part1(input: &Circuit) -> usize {
     let mut circuit = input.clone();
     let mut stash = HashMap::new();
@@ -56,50 +48,71 @@ fn part1(input: &Circuit) -> usize {
     low * high
 }
 
-fn part2(input: &Circuit) -> u64 {
-    let mut circuit = input.clone();
-    let rx = circuit.partnames["rx"];
-    let inputs_to_rx = circuit
-        .elements
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, ele)| {
-            if ele.outputs.contains(&rx) {
-                Some(idx)
-            } else {
-                None
-            }
-        })
-        .collect_vec();
-    //println!("RX is {rx} and elements which input to it are {inputs_to_rx:?}");
-    assert_eq!(inputs_to_rx.len(), 1);
-    let inputs_to_rx_input = circuit
-        .elements
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, ele)| {
-            if ele.outputs.contains(&inputs_to_rx[0]) {
-                Some(idx)
-            } else {
-                None
-            }
-        })
-        .collect_vec();
-    //println!("Inputs to RX inputs are: {inputs_to_rx_input:?}");
-    let mut vals = vec![];
-    for to_find in inputs_to_rx_input {
-        circuit = input.clone();
-        for i in 1u64.. {
-            circuit.push_button();
-            if circuit.quiesce_rx(to_find, inputs_to_rx[0]) {
-                // On this button push, this particular thing went high
-                vals.push(i);
-                break;
+fn part2(input: &Circuit) -> Result<u64> {
+    input
+        .analyse("rx")
+        .and_then(|analysis| analysis.presses())
+        .map_err(|e| format!("Can't work out when rx gets a low pulse: {e}").into())
+}
+
+/*
+The circuit which feeds rx is built from counters.  Each is some flip-flops and
+a conjunction, fed only by the broadcaster, which every so often sends a high
+pulse on to a conjunction which, if they all do that on the same button press,
+sends rx a low pulse.  Rather than assume that shape, we look for it: walk back
+from rx to the conjunction which needs all its inputs high, check that what
+feeds each of those inputs has nothing in common with the others, and then run
+the circuit until each of those sub-circuits repeats a state, noting when in its
+cycle it sends the high pulse.  The cycles line up every so often, which is when
+rx gets its pulse.
+*/
+
+/// A sub-circuit, fed only by the broadcaster, which feeds the hub
+#[derive(Debug, Clone)]
+struct Counter {
+    /// The element of it which feeds the hub, and its name
+    output: usize,
+    name: String,
+    elements: Vec<usize>,
+    /// How many presses it takes to get into its cycle, and how long that is
+    start: u64,
+    period: u64,
+    /// The press in its first cycle on which it sends the hub a high pulse
+    fires: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Analysis {
+    /// The conjunction which needs a high pulse from every counter at once
+    hub: usize,
+    counters: Vec<Counter>,
+}
+
+impl Analysis {
+    /// The first press on which every counter sends its pulse
+    fn presses(&self) -> Result<u64> {
+        // Presses `at`, `at + step`, `at + 2 * step`... suit the counters so far
+        let (mut step, mut at) = (1i128, 0i128);
+        for counter in &self.counters {
+            let (period, fires) = (counter.period as i128, counter.fires as i128);
+            // We want `at + step * k` to be `fires`, modulo `period`
+            let (gcd, inverse, _) = egcd(step, period);
+            if (fires - at) % gcd != 0 {
+                return Err(format!("{} never lines up with the others", counter.name).into());
             }
+            let modulus = period / gcd;
+            let k = ((fires - at) / gcd * inverse).rem_euclid(modulus);
+            at += step * k;
+            step *= modulus;
+            at = at.rem_euclid(step);
         }
+        // And only once every counter has got there at least once
+        let earliest = self.counters.iter().map(|c| c.fires).max().unwrap_or(1) as i128;
+        while at < earliest {
+            at += step;
+        }
+        Ok(at as u64)
     }
-    //println!("We found {vals:?}");
-    vals.into_iter().fold(1, |acc, v| acc.lcm(v))
 }
 
 #[derive(Debug, Default, Clone)]
@@ -153,25 +166,27 @@ impl Circuit {
         }
     }
 
-    fn quiesce_rx(&mut self, from_watch: usize, to_watch: usize) -> bool {
-        // Run queue until quiescence
-        let mut saw_it = false;
+    /// Run until quiescence, returning the pulses sent to `watch` on the way
+    fn quiesce_watching(&mut self, watch: usize) -> Vec<(usize, Pulse)> {
+        let mut seen = vec![];
         while let Some((sender, part, pulse)) = self.queue.pop_front() {
-            //println!("{sender} pulses {part} {pulse:?}");
-            if sender == from_watch && part == to_watch && pulse == Pulse::High {
-                saw_it = true;
+            if part == watch {
+                seen.push((sender, pulse));
             }
             for (target, pulse) in self.elements[part].receive(sender, pulse) {
-                //println!("==> {part} queues {target} {pulse:?}");
                 self.emit(part, target, pulse);
             }
         }
-        saw_it
+        seen
     }
 
     fn state(&self) -> Vec<Pulse> {
+        self.state_of(0..self.elements.len())
+    }
+
+    fn state_of(&self, elements: impl IntoIterator<Item = usize>) -> Vec<Pulse> {
         let mut ret = vec![];
-        for ele in &self.elements {
+        for ele in elements.into_iter().map(|idx| &self.elements[idx]) {
             match &ele.kind {
                 ElementKind::FlipFlop(v) => {
                     ret.push(*v);
@@ -184,8 +199,188 @@ impl Circuit {
         }
         ret
     }
+
+    fn name(&self, idx: usize) -> &str {
+        self.partnames
+            .iter()
+            .find_map(|(name, &i)| (i == idx).then_some(name.as_str()))
+            .unwrap_or("?")
+    }
+
+    /// The elements which output to `idx`
+    fn inputs(&self, idx: usize) -> Vec<usize> {
+        (0..self.elements.len())
+            .filter(|&i| self.elements[i].outputs.contains(&idx))
+            .collect()
+    }
+
+    /// Work out how `target` comes to get a low pulse
+    fn analyse(&self, target: &str) -> Result<Analysis> {
+        let broadcaster = self.partnames["broadcaster"];
+        let mut hub = *self
+            .partnames
+            .get(target)
+            .ok_or_else(|| format!("There is no {target}"))?;
+        // Walk back to a conjunction of several inputs, keeping track of what
+        // it has to send for target to get a low pulse
+        let mut wanted = Pulse::Low;
+        let inputs = loop {
+            let inputs = self.inputs(hub);
+            let name = self.name(hub);
+            match (&inputs[..], &self.elements[hub].kind) {
+                ([], _) => return Err(format!("Nothing sends anything to {name}").into()),
+                ([input], ElementKind::Conjunction(_)) => {
+                    hub = *input;
+                    wanted.invert();
+                }
+                ([input], ElementKind::Broadcast) if hub != broadcaster => hub = *input,
+                (_, ElementKind::Conjunction(_)) if inputs.len() > 1 => {
+                    if wanted == Pulse::High {
+                        return Err(format!(
+                            "{name} would have to send a high pulse, which any of its inputs could cause"
+                        )
+                        .into());
+                    }
+                    break inputs;
+                }
+                _ => return Err(format!("{name} isn't a conjunction of several inputs").into()),
+            }
+        };
+        let mut counters: Vec<Counter> = vec![];
+        for output in inputs {
+            // Everything which feeds this input, bar the broadcaster
+            let mut elements = HashSet::new();
+            let mut todo = vec![output];
+            while let Some(idx) = todo.pop() {
+                if idx != broadcaster && elements.insert(idx) {
+                    todo.extend(self.inputs(idx));
+                }
+            }
+            let name = self.name(output).to_string();
+            for counter in &counters {
+                if let Some(&shared) = counter.elements.iter().find(|e| elements.contains(e)) {
+                    return Err(format!(
+                        "{} and {name} both depend on {}",
+                        counter.name,
+                        self.name(shared)
+                    )
+                    .into());
+                }
+            }
+            counters.push(Counter {
+                output,
+                name,
+                elements: elements.into_iter().sorted().collect(),
+                start: 0,
+                period: 0,
+                fires: 0,
+            });
+        }
+        // Now run them all until each repeats a state, noting when they fire
+        let mut circuit = self.clone();
+        let mut seen = counters
+            .iter()
+            .map(|counter| HashMap::from([(circuit.state_of(counter.elements.clone()), 0)]))
+            .collect_vec();
+        let mut fired = vec![vec![]; counters.len()];
+        for press in 1..=MAX_PRESSES {
+            circuit.push_button();
+            let pulses = circuit.quiesce_watching(hub);
+            for (n, counter) in counters.iter_mut().enumerate() {
+                if counter.period != 0 {
+                    continue;
+                }
+                if pulses.contains(&(counter.output, Pulse::High)) {
+                    fired[n].push(press);
+                }
+                match seen[n].entry(circuit.state_of(counter.elements.clone())) {
+                    Entry::Occupied(o) => {
+                        counter.start = *o.get();
+                        counter.period = press - counter.start;
+                    }
+                    Entry::Vacant(v) => {
+                        v.insert(press);
+                    }
+                }
+            }
+            if counters.iter().all(|counter| counter.period != 0) {
+                break;
+            }
+        }
+        for (counter, fired) in counters.iter_mut().zip(fired) {
+            let name = &counter.name;
+            counter.fires = match fired[..] {
+                _ if counter.period == 0 => {
+                    return Err(
+                        format!("{name} doesn't repeat within {MAX_PRESSES} presses").into(),
+                    )
+                }
+                [press] if press > counter.start => press,
+                [] => return Err(format!("{name} never sends a high pulse").into()),
+                _ => {
+                    return Err(format!(
+                        "{name} sends high pulses on presses {fired:?}, rather than once a cycle"
+                    )
+                    .into())
+                }
+            };
+        }
+        Ok(Analysis { hub, counters })
+    }
+
+    /// The circuit as a graphviz graph, with the counters from an analysis
+    /// of it grouped together
+    fn dot(&self, analysis: Option<&Analysis>) -> String {
+        let node = |idx: usize| {
+            let name = self.name(idx);
+            let ele = &self.elements[idx];
+            let (prefix, shape) = match ele.kind {
+                ElementKind::Broadcast if ele.outputs.is_empty() => ("", "plaintext"),
+                ElementKind::Broadcast => ("", "doublecircle"),
+                ElementKind::FlipFlop(_) => ("%", "box"),
+                ElementKind::Conjunction(_) => ("&", "invhouse"),
+            };
+            format!("\"{name}\" [label=\"{prefix}{name}\", shape={shape}];\n")
+        };
+        let mut dot = String::from("digraph circuit {\n");
+        let counters = analysis.map(|a| &a.counters[..]).unwrap_or_default();
+        for (n, counter) in counters.iter().enumerate() {
+            dot.push_str(&format!("    subgraph cluster_{n} {{\n"));
+            dot.push_str(&format!(
+                "        label=\"{} every {} presses\";\n",
+                counter.name, counter.period
+            ));
+            for &idx in &counter.elements {
+                dot.push_str(&format!("        {}", node(idx)));
+            }
+            dot.push_str("    }\n");
+        }
+        for idx in 0..self.elements.len() {
+            if !counters.iter().any(|c| c.elements.contains(&idx)) {
+                dot.push_str(&format!("    {}", node(idx)));
+            }
+        }
+        for (idx, ele) in self.elements.iter().enumerate() {
+            for &out in &ele.outputs {
+                let style = match analysis {
+                    Some(analysis) if out == analysis.hub => " [style=bold]",
+                    _ => "",
+                };
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\"{style};\n",
+                    self.name(idx),
+                    self.name(out)
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
 }
 
+/// How many presses we'll wait for a counter to repeat
+const MAX_PRESSES: u64 = 1 << 16;
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 struct Element {
     kind: ElementKind,
@@ -301,6 +496,23 @@ impl FromStr for Circuit {
     }
 }
 
+solution! {
+    Day20: Circuit = |input| Ok(Circuit::from_str(input)?),
+    part1 -> usize,
+    part2 -> Result<u64>,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 20)?;
+    // `aoc dot` draws the circuit, with the counters feeding rx picked out
+    if let Some(path) = aoc_common::options::take_dot_output() {
+        let circuit = Circuit::from_str(&read_input(20)?)?;
+        let analysis = circuit.analyse("rx").ok();
+        std::fs::write(path, circuit.dot(analysis.as_ref()))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use rstest::rstest;
@@ -323,4 +535,53 @@ mod test {
         println!("{input:?}");
         assert_eq!(part1(&input), goal);
     }
+
+    #[test]
+    fn counters() {
+        // x fires every other press, and y every fourth
+        let input = Circuit::from_str(
+            r#"broadcaster -> a, b
+%a -> x
+&x -> h
+%b -> c
+%c -> y
+&y -> h
+&h -> rx
+"#,
+        )
+        .unwrap();
+        let analysis = input.analyse("rx").unwrap();
+        let counters = analysis
+            .counters
+            .iter()
+            .map(|c| (c.name.as_str(), c.period, c.fires))
+            .collect_vec();
+        assert_eq!(counters, [("x", 2, 2), ("y", 4, 4)]);
+        assert_eq!(analysis.presses().unwrap(), 4);
+        assert_eq!(part2(&input).unwrap(), 4);
+        let dot = input.dot(Some(&analysis));
+        assert!(dot.contains("label=\"y every 4 presses\""));
+        assert!(dot.contains("\"c\" [label=\"%c\", shape=box]"));
+        assert!(dot.contains("\"x\" -> \"h\" [style=bold]"));
+    }
+
+    #[test]
+    fn shared_counters() {
+        let input = Circuit::from_str(
+            r#"broadcaster -> a
+%a -> x, y
+&x -> h
+&y -> h
+&h -> rx
+"#,
+        )
+        .unwrap();
+        let err = input.analyse("rx").unwrap_err().to_string();
+        assert_eq!(err, "x and y both depend on a");
+        let err = part2(&input).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Can't work out when rx gets a low pulse: x and y both depend on a"
+        );
+    }
 }
@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(3)?;
-    let input = Schematic::from_str(&input).unwrap();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Default, Debug)]
 struct Schematic {
     rows: Vec<String>,
@@ -130,6 +122,16 @@ fn part2(input: &Schematic) -> u64 {
         .sum()
 }
 
+solution! {
+    Day3: Schematic = |input| Ok(Schematic::from_str(input)?),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 3)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
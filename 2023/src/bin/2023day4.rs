@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(4)?;
-    let input = parse_winners(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 fn parse_winners(input: &str) -> Vec<usize> {
     let mut ret = Vec::new();
     for card in input.lines() {
@@ -43,6 +35,16 @@ fn part2(input: &[usize]) -> u64 {
     card_count.into_iter().sum()
 }
 
+solution! {
+    Day4: Vec<usize> = |input| Ok(parse_winners(input)),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 4)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
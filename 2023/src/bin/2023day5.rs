@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(5)?;
-    let input = parse_almanac(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct Almanac {
     seeds: Vec<u64>,
@@ -143,6 +135,16 @@ fn part2(input: &Almanac) -> u64 {
     ranges.into_iter().map(|(start, _len)| start).min().unwrap()
 }
 
+solution! {
+    Day5: Almanac = |input| Ok(parse_almanac(input)),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 5)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(6)?;
-    let input = parse_racedata(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct RaceData {
     times: Vec<u64>,
@@ -75,6 +67,16 @@ fn part2(input: &RaceData) -> u64 {
     beats
 }
 
+solution! {
+    Day6: RaceData = |input| Ok(parse_racedata(input)),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 6)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
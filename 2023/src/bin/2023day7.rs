@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(7)?;
-    let input = parse_hands(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct Hands {
     hands: Vec<String>,
@@ -195,6 +187,16 @@ fn part2(input: &Hands) -> u64 {
         .sum()
 }
 
+solution! {
+    Day7: Hands = |input| Ok(parse_hands(input)),
+    part1 -> u64,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 7)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(8)?;
-    let input = parse_map(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug, ParseByRegex)]
 #[regex = r"^(?P<from>...) = .(?P<left>...), (?P<right>...).$"]
 struct Rule {
@@ -129,6 +121,16 @@ fn part2(input: &Map) -> u64 {
     plen.into_iter().fold(1, |acc, x| acc.lcm(x))
 }
 
+solution! {
+    Day8: Map = |input| Ok(parse_map(input)),
+    part1 -> usize,
+    part2 -> u64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 8)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,13 +1,5 @@
 use aoc2023::*;
 
-pub fn main() -> Result<()> {
-    let input = read_input(9)?;
-    let input = parse_input(&input);
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
-}
-
 #[derive(Debug)]
 struct Input {
     seqs: Vec<Vec<i64>>,
@@ -76,6 +68,16 @@ fn part2(input: &Input) -> i64 {
         .sum()
 }
 
+solution! {
+    Day9: Input = |input| Ok(parse_input(input)),
+    part1 -> i64,
+    part2 -> i64,
+}
+
+pub fn main() -> Result<()> {
+    run_solution(SOLUTION, 9)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,156 @@
+//! Known-good answers, kept in `answers/YYYY.toml` at the top of the tree
+//!
+//! ```toml
+//! [day1]
+//! part1 = "1930746032"
+//! part2 = "1154"
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use aoc_common::locate::workspace_dir;
+use aoc_common::Result;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayAnswers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl DayAnswers {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => panic!("There is no part {}", part),
+        }
+    }
+}
+
+pub struct Answers {
+    path: PathBuf,
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+impl Answers {
+    /// Load a year's answers, a year with no file simply has no answers yet
+    pub fn load(year: u16) -> Result<Answers> {
+        let path = workspace_dir()?
+            .join("answers")
+            .join(format!("{}.toml", year));
+        let days = if path.exists() {
+            parse(&std::fs::read_to_string(&path)?)
+                .map_err(|e| format!("{}: {}", path.display(), e))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Answers { path, days })
+    }
+
+    pub fn day(&self, day: u8) -> Option<&DayAnswers> {
+        self.days.get(&day)
+    }
+
+    /// Record any answers we don't already know, returning whether we learned
+    /// anything.  Known answers are never replaced, to do that edit the file.
+    pub fn record(&mut self, day: u8, answers: &[Option<String>; 2]) -> bool {
+        let known = self.days.entry(day).or_default();
+        let mut learned = false;
+        for (part, answer) in (1..=2).zip(answers) {
+            let slot = known.part_mut(part);
+            if slot.is_none() && answer.is_some() {
+                *slot = answer.clone();
+                learned = true;
+            }
+        }
+        learned
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, render(&self.days))?;
+        Ok(())
+    }
+}
+
+fn parse(text: &str) -> Result<BTreeMap<u8, DayAnswers>> {
+    let raw: BTreeMap<String, DayAnswers> = toml::from_str(text)?;
+    raw.into_iter()
+        .map(|(key, answers)| {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| format!("Bad day {:?}", key))?;
+            Ok((day, answers))
+        })
+        .collect()
+}
+
+/// Written by hand so that the days come out in numeric order
+fn render(days: &BTreeMap<u8, DayAnswers>) -> String {
+    let mut out = String::new();
+    for (day, answers) in days {
+        if answers.part1.is_none() && answers.part2.is_none() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        writeln!(out, "[day{}]", day).unwrap();
+        for part in 1..=2 {
+            if let Some(answer) = answers.part(part) {
+                let value = toml::Value::String(answer.to_string());
+                writeln!(out, "part{} = {}", part, value).unwrap();
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut days = BTreeMap::new();
+        days.insert(
+            10,
+            DayAnswers {
+                part1: Some("13140".to_string()),
+                part2: Some("##..##\n#....#".to_string()),
+            },
+        );
+        days.insert(
+            2,
+            DayAnswers {
+                part1: Some("15".to_string()),
+                part2: None,
+            },
+        );
+        let text = render(&days);
+        assert!(text.starts_with("[day2]\npart1 = \"15\"\n\n[day10]\n"));
+        assert_eq!(parse(&text).unwrap(), days);
+    }
+
+    #[test]
+    fn bad_days() {
+        assert!(parse("[twelve]\npart1 = \"1\"\n").is_err());
+        assert!(parse("[day1]\npart3 = \"1\"\n").is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! Benchmarking days
+//!
+//! `aoc bench` solves each day a few times to warm up, then repeatedly to
+//! measure, and reports the median of each phase.  Results can also be
+//! written as JSON or CSV so that runs from before and after a change can be
+//! compared.
+//!
+//! Older days only have a `main`, so all we can measure of them is the total.
+//! That is timed inside the child process which runs them, so it doesn't
+//! include starting that process, and like every day's total it includes
+//! reading the input.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use aoc_common::registry::{Day, Year};
+use aoc_common::Result;
+
+use crate::solve::{solve, Timings};
+use crate::{selected_days, Selection};
+
+/// How many of the slowest days of each year to point out
+const SLOWEST: usize = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub repeat: usize,
+    pub json: Option<PathBuf>,
+    pub csv: Option<PathBuf>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 1,
+            repeat: 5,
+            json: None,
+            csv: None,
+        }
+    }
+}
+
+/// Summary of one phase of a day over every measured run, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct Stats {
+    min_ns: u64,
+    median_ns: u64,
+    mean_ns: u64,
+    max_ns: u64,
+}
+
+impl Stats {
+    fn of<I: IntoIterator<Item = Duration>>(samples: I) -> Option<Stats> {
+        let mut samples: Vec<u64> = samples.into_iter().map(|d| d.as_nanos() as u64).collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        Some(Stats {
+            min_ns: samples[0],
+            median_ns: samples[samples.len() / 2],
+            mean_ns: samples.iter().sum::<u64>() / samples.len() as u64,
+            max_ns: samples[samples.len() - 1],
+        })
+    }
+
+    fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DayBench {
+    year: u16,
+    day: u8,
+    runs: usize,
+    parse: Option<Stats>,
+    part1: Option<Stats>,
+    part2: Option<Stats>,
+    total: Stats,
+}
+
+impl DayBench {
+    fn phases(&self) -> impl Iterator<Item = (&'static str, Stats)> {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+            ("total", Some(self.total)),
+        ]
+        .into_iter()
+        .filter_map(|(name, stats)| Some((name, stats?)))
+    }
+}
+
+impl std::fmt::Display for DayBench {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}: ", self.year, self.day)?;
+        for (name, stats) in self.phases() {
+            if name != "total" {
+                write!(f, "{} {:.2?}, ", name, stats.median())?;
+            }
+        }
+        write!(f, "total {:.2?}", self.total.median())
+    }
+}
+
+pub fn bench(selection: &Selection, options: &BenchOptions) -> Result<()> {
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for (year, days) in selected_days(selection)? {
+        for day in days {
+            match bench_day(year, day, options) {
+                Ok(result) => {
+                    println!("{}", result);
+                    results.push(result);
+                }
+                Err(e) => {
+                    eprintln!("{} day {} failed: {}", year.year, day.day, e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+    print_slowest(&results);
+    if let Some(path) = &options.json {
+        std::fs::write(path, serde_json::to_string_pretty(&results)?)?;
+    }
+    if let Some(path) = &options.csv {
+        std::fs::write(path, to_csv(&results))?;
+    }
+    if failed > 0 {
+        Err(format!("{} days failed", failed).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn bench_day(year: &Year, day: &Day, options: &BenchOptions) -> Result<DayBench> {
+    for _ in 0..options.warmup {
+        solve(year, day, None)?;
+    }
+    let runs = (0..options.repeat)
+        .map(|_| Ok(solve(year, day, None)?.timings))
+        .collect::<Result<Vec<Timings>>>()?;
+    let phase = |f: fn(&Timings) -> Option<Duration>| {
+        // A phase is only worth reporting if every run could measure it
+        runs.iter()
+            .map(f)
+            .collect::<Option<Vec<_>>>()
+            .and_then(Stats::of)
+    };
+    Ok(DayBench {
+        year: year.year,
+        day: day.day,
+        runs: runs.len(),
+        parse: phase(|t| t.parse),
+        part1: phase(|t| t.parts[0]),
+        part2: phase(|t| t.parts[1]),
+        total: Stats::of(runs.iter().map(|t| t.total)).ok_or("Nothing was measured")?,
+    })
+}
+
+fn print_slowest(results: &[DayBench]) {
+    let mut years: Vec<u16> = results.iter().map(|r| r.year).collect();
+    years.dedup();
+    for year in years {
+        let mut days: Vec<&DayBench> = results.iter().filter(|r| r.year == year).collect();
+        if days.len() < 2 {
+            continue;
+        }
+        days.sort_by_key(|r| std::cmp::Reverse(r.total.median_ns));
+        let slowest = days
+            .iter()
+            .take(SLOWEST)
+            .map(|r| format!("day {} ({:.2?})", r.day, r.total.median()))
+            .collect::<Vec<_>>();
+        println!("{} slowest: {}", year, slowest.join(", "));
+    }
+}
+
+/// One row per phase per day, which is easy to pivot however you like
+fn to_csv(results: &[DayBench]) -> String {
+    let mut out = String::from("year,day,phase,runs,min_ns,median_ns,mean_ns,max_ns\n");
+    for result in results {
+        for (name, stats) in result.phases() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{}",
+                result.year,
+                result.day,
+                name,
+                result.runs,
+                stats.min_ns,
+                stats.median_ns,
+                stats.mean_ns,
+                stats.max_ns
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let stats = Stats::of([30, 10, 20, 100].map(Duration::from_nanos)).unwrap();
+        assert_eq!(
+            stats,
+            Stats {
+                min_ns: 10,
+                median_ns: 30,
+                mean_ns: 40,
+                max_ns: 100
+            }
+        );
+        assert_eq!(Stats::of([]), None);
+    }
+
+    #[test]
+    fn csv_rows() {
+        let stats = Stats::of([Duration::from_nanos(5)]).unwrap();
+        let results = [
+            DayBench {
+                year: 2023,
+                day: 9,
+                runs: 1,
+                parse: Some(stats),
+                part1: None,
+                part2: None,
+                total: stats,
+            },
+            DayBench {
+                year: 2018,
+                day: 3,
+                runs: 1,
+                parse: None,
+                part1: None,
+                part2: None,
+                total: stats,
+            },
+        ];
+        assert_eq!(
+            to_csv(&results),
+            "year,day,phase,runs,min_ns,median_ns,mean_ns,max_ns\n\
+             2023,9,parse,1,5,5,5,5\n\
+             2023,9,total,1,5,5,5,5\n\
+             2018,3,total,1,5,5,5,5\n"
+        );
+        assert_eq!(results[1].to_string(), "2018 day 3: total 5.00ns");
+    }
+}
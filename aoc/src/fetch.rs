@@ -0,0 +1,329 @@
+//! Downloading inputs
+//!
+//! `aoc fetch YEAR DAY` downloads a day's input, and the examples from its
+//! puzzle text, into the year's input cache (see
+//! [`input_cache_dir`](aoc_common::locate::input_cache_dir)).  Files which
+//! are already there are never overwritten, so fetching again after solving
+//! part 1 only adds the examples which part 2 brought with it.
+//!
+//! The site asks that automated requests are throttled, so every request
+//! first waits until enough time has passed since the last one, which is
+//! remembered in a `last-request` file next to the session token.
+
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::locate::{example_path, input_cache_dir};
+use aoc_common::Result;
+
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_FILE_VAR: &str = "AOC_SESSION_FILE";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Who we are, as the site asks automated tools to say
+const USER_AGENT: &str = concat!("aoc fetch (", env!("CARGO_PKG_AUTHORS"), ")");
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FetchOptions {
+    pub base_url: String,
+    /// A file holding the value of the site's `session` cookie
+    pub session_file: PathBuf,
+    /// The least time between two requests
+    pub interval: Duration,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        let session_file = std::env::var_os(SESSION_FILE_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                let home = std::env::var_os("HOME").unwrap_or_default();
+                Path::new(&home).join(".config/aoc/session")
+            });
+        FetchOptions {
+            base_url: std::env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.into()),
+            session_file,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+}
+
+struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    cookie: String,
+    stamp: PathBuf,
+    interval: Duration,
+}
+
+impl Client {
+    fn new(options: &FetchOptions) -> Result<Client> {
+        let session = std::fs::read_to_string(&options.session_file).map_err(|e| {
+            format!(
+                "Unable to read session token {}: {}",
+                options.session_file.display(),
+                e
+            )
+        })?;
+        let stamp = options
+            .session_file
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("last-request");
+        Ok(Client {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: options.base_url.trim_end_matches('/').to_string(),
+            cookie: format!("session={}", session.trim()),
+            stamp,
+            interval: options.interval,
+        })
+    }
+
+    /// Wait until we're allowed to make another request, and note that we
+    /// are making one now
+    fn throttle(&self) -> Result<()> {
+        // The time is written out rather than using the file's mtime, which
+        // the filesystem may only keep to the nearest tick or so
+        let last = std::fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last) = last {
+            // A stamp from the future (clock changes) just means no waiting
+            let since = SystemTime::now()
+                .duration_since(last)
+                .unwrap_or(self.interval);
+            if since < self.interval {
+                std::thread::sleep(self.interval - since);
+            }
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        std::fs::write(&self.stamp, now.as_millis().to_string())?;
+        Ok(())
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        self.throttle()?;
+        let url = format!("{}{}", self.base_url, path);
+        match self.agent.get(&url).set("Cookie", &self.cookie).call() {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(code, _)) => {
+                Err(format!("Fetching {} failed: HTTP {}", url, code).into())
+            }
+            Err(e) => Err(format!("Fetching {} failed: {}", url, e).into()),
+        }
+    }
+}
+
+/// Write a new file, returning false if it is already there
+fn create(path: &Path, contents: &str) -> Result<bool> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => {
+            file.write_all(contents.as_bytes())?;
+            Ok(true)
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(format!("Unable to create {}: {}", path.display(), e).into()),
+    }
+}
+
+fn report(path: &Path, created: bool) {
+    if created {
+        println!("Wrote {}", path.display());
+    } else {
+        println!("{} already exists, not overwriting it", path.display());
+    }
+}
+
+pub fn fetch(year: u16, day: u8, options: &FetchOptions) -> Result<()> {
+    fetch_into(&input_cache_dir(year)?, year, day, options)
+}
+
+fn fetch_into(dir: &Path, year: u16, day: u8, options: &FetchOptions) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {}", day).into());
+    }
+    let client = Client::new(options)?;
+    std::fs::create_dir_all(dir)?;
+    let day_path = format!("/{}/day/{}", year, day);
+
+    let input_path = dir.join(format!("day{}", day));
+    if input_path.exists() {
+        report(&input_path, false);
+    } else {
+        let input = client.get(&format!("{}/input", day_path))?;
+        report(&input_path, create(&input_path, &input)?);
+    }
+
+    let page = client.get(&day_path)?;
+    let examples = examples_in(&page);
+    if examples.is_empty() {
+        println!("No examples found in the puzzle text");
+    }
+    for (n, example) in (1..).zip(&examples) {
+        let path = example_path(dir, day as usize, n);
+        report(&path, create(&path, example)?);
+    }
+    Ok(())
+}
+
+/// Every `<pre><code>` block in a puzzle's page, as plain text
+fn examples_in(page: &str) -> Vec<String> {
+    let mut examples = Vec::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+        let Some(end) = rest.find("</code></pre>") else {
+            break;
+        };
+        examples.push(unescape(&strip_tags(&rest[..end])));
+        rest = &rest[end..];
+    }
+    examples
+}
+
+/// Examples often have parts emphasised with `<em>` and such
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    // `&amp;` last, so that `&amp;lt;` comes out as `&lt;`
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    const PAGE: &str = "<article><p>For example:</p>\n<pre><code>1 &lt; 2\n<em>3</em> &amp;&amp; 4\n</code></pre>\n<p>Then:</p><pre><code>x\n</code></pre></article>";
+
+    #[test]
+    fn picking_examples() {
+        assert_eq!(examples_in(PAGE), vec!["1 < 2\n3 && 4\n", "x\n"]);
+        assert!(examples_in("<p>Nothing here</p>").is_empty());
+    }
+
+    /// Answer `requests` requests, sending back what was asked for and
+    /// remembering what was asked with which cookie
+    fn mock_server(requests: usize) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut seen = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let reader = BufReader::new(stream.try_clone().unwrap());
+                let mut path = String::new();
+                let mut cookie = String::new();
+                for line in reader.lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(request) = line.strip_prefix("GET ") {
+                        path = request.split(' ').next().unwrap().to_string();
+                    }
+                    if let Some(value) = line.strip_prefix("Cookie: ") {
+                        cookie = value.to_string();
+                    }
+                }
+                let (status, body) = match (cookie.as_str(), path.as_str()) {
+                    ("session=sekrit", "/2022/day/10/input") => ("200 OK", "noop\naddx 3\n"),
+                    ("session=sekrit", "/2022/day/10") => ("200 OK", PAGE),
+                    ("session=sekrit", _) => ("404 Not Found", ""),
+                    _ => ("400 Bad Request", ""),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                seen.push(path);
+            }
+            seen
+        });
+        (base_url, server)
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn fetching_from_a_mock_server() {
+        let dir = scratch_dir("mock");
+        let session_file = dir.join("session");
+        std::fs::write(&session_file, "sekrit\n").unwrap();
+        let cache = dir.join("2022");
+        // Pretend we already have the second example, which mustn't change
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(example_path(&cache, 10, 2), "mine").unwrap();
+
+        let (base_url, server) = mock_server(4);
+        let options = FetchOptions {
+            base_url,
+            session_file,
+            interval: Duration::from_millis(200),
+        };
+        let start = Instant::now();
+        fetch_into(&cache, 2022, 10, &options).unwrap();
+        // Two requests, the second of which had to wait
+        assert!(start.elapsed() >= options.interval);
+        assert_eq!(
+            std::fs::read_to_string(cache.join("day10")).unwrap(),
+            "noop\naddx 3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(example_path(&cache, 10, 1)).unwrap(),
+            "1 < 2\n3 && 4\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(example_path(&cache, 10, 2)).unwrap(),
+            "mine"
+        );
+
+        // Once we have the input it isn't asked for again, and a missing
+        // day is an error rather than an empty input
+        fetch_into(&cache, 2022, 10, &options).unwrap();
+        assert!(fetch_into(&cache, 2022, 11, &options).is_err());
+        assert!(!cache.join("day11").exists());
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                "/2022/day/10/input",
+                "/2022/day/10",
+                "/2022/day/10",
+                "/2022/day/11/input"
+            ]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
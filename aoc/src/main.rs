@@ -0,0 +1,593 @@
+//! `aoc` runs any day of any year
+//!
+//! ```text
+//! aoc run 2019 12 --part 2
+//! aoc run 2019 12 --input - < other-input
+//! aoc run 2018 --time
+//! aoc run --all
+//! aoc run 2019 25 --script moves.txt
+//! aoc explain 2018 19
+//! aoc dot 2023 20 circuit.dot
+//! aoc verify 2022 --record
+//! aoc bench 2023 --repeat 10 --json before.json
+//! aoc fetch 2023 12
+//! aoc new 2023 12 --example 1
+//! aoc list
+//! ```
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+
+use aoc_common::locate::{set_explicit_input, set_year, InputSource};
+use aoc_common::options::{set_dot_output, set_explain, set_script, take_dot_output};
+use aoc_common::registry::{Day, Year};
+use aoc_common::Result;
+
+mod answers;
+mod bench;
+mod fetch;
+mod scaffold;
+mod solve;
+mod verify;
+mod years;
+
+use bench::BenchOptions;
+use fetch::FetchOptions;
+use solve::{print_answer, report_main_time, solve};
+
+use years::YEARS;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Selection {
+    All,
+    Year(u16),
+    Day(u16, u8),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Run {
+        selection: Selection,
+        part: Option<u8>,
+        time: bool,
+        input: Option<InputSource>,
+        script: Option<PathBuf>,
+    },
+    Explain {
+        year: u16,
+        day: u8,
+    },
+    Dot {
+        year: u16,
+        day: u8,
+        path: PathBuf,
+    },
+    Verify {
+        selection: Selection,
+        record: bool,
+    },
+    Bench {
+        selection: Selection,
+        options: BenchOptions,
+    },
+    Fetch {
+        year: u16,
+        day: u8,
+        options: FetchOptions,
+    },
+    New {
+        year: u16,
+        day: u8,
+        example: Option<usize>,
+    },
+    List,
+}
+
+const USAGE: &str = "usage: aoc run (--all | YEAR [DAY]) [--part N] [--time] [--input FILE|-]
+       aoc run YEAR DAY [--input FILE|-] [--script FILE]
+       aoc explain YEAR DAY
+       aoc dot YEAR DAY FILE
+       aoc verify (--all | YEAR [DAY]) [--record]
+       aoc bench (--all | YEAR [DAY]) [--warmup N] [--repeat N] [--json FILE] [--csv FILE]
+       aoc fetch YEAR DAY [--base-url URL] [--session-file FILE]
+       aoc new YEAR DAY [--example N]
+       aoc list";
+
+fn parse_selection(all: bool, positional: &[String]) -> Result<Selection> {
+    Ok(match (all, positional) {
+        (true, []) => Selection::All,
+        (false, [year]) => Selection::Year(year.parse()?),
+        (false, [year, day]) => Selection::Day(year.parse()?, day.parse()?),
+        _ => return Err(USAGE.into()),
+    })
+}
+
+fn flag_value<T: FromStr, I: Iterator<Item = String>>(flag: &str, args: &mut I) -> Result<T> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("{} needs a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("Bad value for {}: {}", flag, value).into())
+}
+
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("list") => {
+            if args.next().is_some() {
+                return Err(USAGE.into());
+            }
+            Ok(Command::List)
+        }
+        Some("run") => {
+            let mut all = false;
+            let mut part = None;
+            let mut time = false;
+            let mut input = None;
+            let mut script = None;
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--all" => all = true,
+                    "--time" => time = true,
+                    "--input" => {
+                        let path: PathBuf = flag_value(&arg, &mut args)?;
+                        input = Some(InputSource::from(path.into_os_string()));
+                    }
+                    "--script" => script = Some(flag_value(&arg, &mut args)?),
+                    "--part" => {
+                        let value = args.next().ok_or("--part needs a value")?;
+                        match value.parse::<u8>() {
+                            Ok(n @ 1..=2) => part = Some(n),
+                            _ => return Err(format!("Bad part: {}", value).into()),
+                        }
+                    }
+                    _ => positional.push(arg),
+                }
+            }
+            let selection = parse_selection(all, &positional)?;
+            if input.is_some() && !matches!(selection, Selection::Day(..)) {
+                return Err("--input only makes sense for a single day".into());
+            }
+            // Only a day's own `main` plays scripts, and --part or --time
+            // would run its solution instead
+            if script.is_some()
+                && (!matches!(selection, Selection::Day(..)) || part.is_some() || time)
+            {
+                return Err("--script only makes sense for a single day, run whole".into());
+            }
+            Ok(Command::Run {
+                selection,
+                part,
+                time,
+                input,
+                script,
+            })
+        }
+        Some("explain") => {
+            let positional: Vec<String> = args.collect();
+            match parse_selection(false, &positional)? {
+                Selection::Day(year, day) => Ok(Command::Explain { year, day }),
+                _ => Err(USAGE.into()),
+            }
+        }
+        Some("dot") => {
+            let mut positional: Vec<String> = args.collect();
+            let path = match positional.len() {
+                3 => PathBuf::from(positional.pop().unwrap()),
+                _ => return Err(USAGE.into()),
+            };
+            match parse_selection(false, &positional)? {
+                Selection::Day(year, day) => Ok(Command::Dot { year, day, path }),
+                _ => Err(USAGE.into()),
+            }
+        }
+        Some("verify") => {
+            let mut all = false;
+            let mut record = false;
+            let mut positional = Vec::new();
+            for arg in args {
+                match arg.as_str() {
+                    "--all" => all = true,
+                    "--record" => record = true,
+                    _ => positional.push(arg),
+                }
+            }
+            let selection = parse_selection(all, &positional)?;
+            Ok(Command::Verify { selection, record })
+        }
+        Some("bench") => {
+            let mut all = false;
+            let mut options = BenchOptions::default();
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--all" => all = true,
+                    "--warmup" => options.warmup = flag_value(&arg, &mut args)?,
+                    "--repeat" => options.repeat = flag_value(&arg, &mut args)?,
+                    "--json" => options.json = Some(flag_value::<PathBuf, _>(&arg, &mut args)?),
+                    "--csv" => options.csv = Some(flag_value::<PathBuf, _>(&arg, &mut args)?),
+                    _ => positional.push(arg),
+                }
+            }
+            if options.repeat == 0 {
+                return Err("--repeat must be at least 1".into());
+            }
+            let selection = parse_selection(all, &positional)?;
+            Ok(Command::Bench { selection, options })
+        }
+        Some("fetch") => {
+            let mut options = FetchOptions::default();
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--base-url" => options.base_url = flag_value(&arg, &mut args)?,
+                    "--session-file" => options.session_file = flag_value(&arg, &mut args)?,
+                    _ => positional.push(arg),
+                }
+            }
+            match parse_selection(false, &positional)? {
+                Selection::Day(year, day) => Ok(Command::Fetch { year, day, options }),
+                _ => Err(USAGE.into()),
+            }
+        }
+        Some("new") => {
+            let mut example = None;
+            let mut positional = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--example" => example = Some(flag_value(&arg, &mut args)?),
+                    _ => positional.push(arg),
+                }
+            }
+            match parse_selection(false, &positional)? {
+                Selection::Day(year, day) => Ok(Command::New { year, day, example }),
+                _ => Err(USAGE.into()),
+            }
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn find_year(year: u16) -> Result<&'static Year> {
+    years::year(year).ok_or_else(|| format!("No solutions for {}", year).into())
+}
+
+fn find_day(year: &Year, day: u8) -> Result<&Day> {
+    year.day(day)
+        .ok_or_else(|| format!("{} day {} has no solution", year.year, day).into())
+}
+
+/// The days a selection covers, by year
+///
+/// A day asked for by name is run even if it is skipped, otherwise skipped
+/// days are mentioned and then left out.
+fn selected_days(selection: &Selection) -> Result<Vec<(&'static Year, Vec<&'static Day>)>> {
+    fn unskipped(year: &'static Year) -> (&'static Year, Vec<&'static Day>) {
+        let days = year
+            .days
+            .iter()
+            .filter(|day| match year.skip_reason(day.day) {
+                Some(reason) => {
+                    println!("{} day {}: skipped ({})", year.year, day.day, reason);
+                    false
+                }
+                None => true,
+            })
+            .collect();
+        (year, days)
+    }
+    match *selection {
+        Selection::Day(year, day) => {
+            let year = find_year(year)?;
+            Ok(vec![(year, vec![find_day(year, day)?])])
+        }
+        Selection::Year(year) => Ok(vec![unskipped(find_year(year)?)]),
+        Selection::All => Ok(YEARS.iter().map(|year| unskipped(year)).collect()),
+    }
+}
+
+/// Run a day's `main`, just as its own binary would
+fn run_main(year: &Year, day: &Day) -> Result<()> {
+    year.enter()?;
+    set_year(year.year);
+    let start = Instant::now();
+    let result = std::panic::catch_unwind(day.main)
+        .map_err(|_| format!("{} day {} panicked", day.year, day.day))?;
+    report_main_time(start.elapsed());
+    result
+}
+
+/// Run a single day, optionally only reporting one part of its answer, and
+/// optionally saying how long it took
+fn run_day(year: &Year, day: &Day, part: Option<u8>, time: bool) -> Result<()> {
+    if part.is_none() && !time {
+        return run_main(year, day);
+    }
+    let solved = solve(year, day, part)?;
+    for (n, answer) in (1..=2).zip(&solved.answers) {
+        // Older days always answer both parts, so we may have to drop one
+        if part.is_some_and(|part| part != n) {
+            continue;
+        }
+        match answer {
+            Some(answer) => print_answer(n, answer),
+            None if part.is_some() => {
+                return Err(
+                    format!("{} day {} gave no answer for part {}", day.year, day.day, n).into(),
+                );
+            }
+            None => {}
+        }
+    }
+    if time {
+        println!("Time: {}", solved.timings);
+    }
+    Ok(())
+}
+
+/// Run every day of a year which isn't skipped, returning how many failed
+fn run_year(year: &Year, part: Option<u8>, time: bool) -> usize {
+    let mut failed = 0;
+    for day in 1..=25 {
+        match (year.day(day), year.skip_reason(day)) {
+            (None, _) => println!("{} day {}: missing", year.year, day),
+            (Some(_), Some(reason)) => println!("{} day {}: skipped ({})", year.year, day, reason),
+            (Some(day), None) => {
+                println!("{} day {}:", year.year, day.day);
+                if let Err(e) = run_day(year, day, part, time) {
+                    eprintln!("{} day {} failed: {}", year.year, day.day, e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+    failed
+}
+
+fn run(selection: &Selection, part: Option<u8>, time: bool) -> Result<()> {
+    let failed = match *selection {
+        Selection::Day(year, day) => {
+            let year = find_year(year)?;
+            return run_day(year, find_day(year, day)?, part, time);
+        }
+        Selection::Year(year) => run_year(find_year(year)?, part, time),
+        Selection::All => YEARS.iter().map(|year| run_year(year, part, time)).sum(),
+    };
+    if failed > 0 {
+        Err(format!("{} days failed", failed).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Have a day draw itself at `path`, complaining if it can't
+fn dot(year: u16, day: u8, path: PathBuf) -> Result<()> {
+    let year = find_year(year)?;
+    let day = find_day(year, day)?;
+    set_dot_output(Some(path));
+    run_main(year, day)?;
+    if take_dot_output().is_some() {
+        return Err(format!("{} day {} has nothing to draw", year.year, day.day).into());
+    }
+    Ok(())
+}
+
+fn list() {
+    for year in YEARS {
+        let days = year
+            .days
+            .iter()
+            .map(|d| d.day.to_string())
+            .collect::<Vec<_>>();
+        println!("{}: {}", year.year, days.join(" "));
+        for (day, reason) in year.skipped {
+            println!("  day {} skipped: {}", day, reason);
+        }
+        let missing = year.missing();
+        if !missing.is_empty() {
+            let missing = missing.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+            println!("  missing: {}", missing.join(" "));
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    match parse_args(std::env::args().skip(1))? {
+        Command::Run {
+            selection,
+            part,
+            time,
+            input,
+            script,
+        } => {
+            set_explicit_input(input);
+            set_script(script);
+            run(&selection, part, time)
+        }
+        Command::Explain { year, day } => {
+            set_explain(true);
+            let year = find_year(year)?;
+            run_main(year, find_day(year, day)?)
+        }
+        Command::Dot { year, day, path } => dot(year, day, path),
+        Command::Verify { selection, record } => verify::verify(&selection, record),
+        Command::Bench { selection, options } => bench::bench(&selection, &options),
+        Command::Fetch { year, day, options } => fetch::fetch(year, day, &options),
+        Command::New { year, day, example } => scaffold::new_day(year, day, example),
+        Command::List => {
+            list();
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn run_selections() {
+        assert_eq!(
+            parse("run 2019 12 --part 2").unwrap(),
+            Command::Run {
+                selection: Selection::Day(2019, 12),
+                part: Some(2),
+                time: false,
+                input: None,
+                script: None
+            }
+        );
+        assert_eq!(
+            parse("run 2018 --time").unwrap(),
+            Command::Run {
+                selection: Selection::Year(2018),
+                part: None,
+                time: true,
+                input: None,
+                script: None
+            }
+        );
+        assert_eq!(
+            parse("run --all").unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                time: false,
+                input: None,
+                script: None
+            }
+        );
+        assert_eq!(
+            parse("run 2019 12 --input -").unwrap(),
+            Command::Run {
+                selection: Selection::Day(2019, 12),
+                part: None,
+                time: false,
+                input: Some(InputSource::Stdin),
+                script: None
+            }
+        );
+        assert_eq!(
+            parse("run 2019 25 --script moves.txt").unwrap(),
+            Command::Run {
+                selection: Selection::Day(2019, 25),
+                part: None,
+                time: false,
+                input: None,
+                script: Some(PathBuf::from("moves.txt"))
+            }
+        );
+    }
+
+    #[test]
+    fn explain_days() {
+        assert_eq!(
+            parse("explain 2018 19").unwrap(),
+            Command::Explain {
+                year: 2018,
+                day: 19
+            }
+        );
+    }
+
+    #[test]
+    fn dot_days() {
+        assert_eq!(
+            parse("dot 2023 20 circuit.dot").unwrap(),
+            Command::Dot {
+                year: 2023,
+                day: 20,
+                path: PathBuf::from("circuit.dot")
+            }
+        );
+    }
+
+    #[test]
+    fn verify_selections() {
+        assert_eq!(
+            parse("verify 2022 --record").unwrap(),
+            Command::Verify {
+                selection: Selection::Year(2022),
+                record: true
+            }
+        );
+        assert_eq!(
+            parse("verify --all").unwrap(),
+            Command::Verify {
+                selection: Selection::All,
+                record: false
+            }
+        );
+    }
+
+    #[test]
+    fn bench_selections() {
+        assert_eq!(
+            parse("bench 2023 9 --repeat 10 --csv out.csv").unwrap(),
+            Command::Bench {
+                selection: Selection::Day(2023, 9),
+                options: BenchOptions {
+                    repeat: 10,
+                    csv: Some(PathBuf::from("out.csv")),
+                    ..BenchOptions::default()
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn fetch_days() {
+        assert_eq!(
+            parse("fetch 2023 12 --base-url http://localhost:8080").unwrap(),
+            Command::Fetch {
+                year: 2023,
+                day: 12,
+                options: FetchOptions {
+                    base_url: "http://localhost:8080".to_string(),
+                    ..FetchOptions::default()
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn new_days() {
+        assert_eq!(
+            parse("new 2023 21 --example 2").unwrap(),
+            Command::New {
+                year: 2023,
+                day: 21,
+                example: Some(2)
+            }
+        );
+    }
+
+    #[test]
+    fn bad_args() {
+        assert!(parse("run").is_err());
+        assert!(parse("run --all 2019").is_err());
+        assert!(parse("run 2019 12 --part 3").is_err());
+        assert!(parse("walk 2019").is_err());
+        assert!(parse("run 2019 --input foo").is_err());
+        assert!(parse("run 2019 --script moves.txt").is_err());
+        assert!(parse("run 2019 25 --part 1 --script moves.txt").is_err());
+        assert!(parse("explain 2018").is_err());
+        assert!(parse("dot 2023 20").is_err());
+        assert!(parse("dot 2023 20 a.dot b.dot").is_err());
+        assert!(parse("verify").is_err());
+        assert!(parse("bench 2023 --repeat").is_err());
+        assert!(parse("bench 2023 --repeat 0").is_err());
+        assert!(parse("bench 2023 --warmup lots").is_err());
+        assert!(parse("fetch 2023").is_err());
+        assert!(parse("fetch 2023 12 --session-file").is_err());
+        assert!(parse("new --all").is_err());
+        assert!(parse("new 2023 21 --example first").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+//! Starting a new day
+//!
+//! `aoc new YEAR DAY` writes `src/bin/YYYYdayN.rs` in the year's crate from
+//! `templates/day.tmpl`.  The new day is a [`Solution`], so the year's build
+//! script registers it with the runner and the `YYYYall` aggregator as soon as
+//! it exists.  Years which don't have an aggregator yet get one.
+//!
+//! `--example N` fills the day's `TEST_INPUT` in from the Nth example which
+//! `aoc fetch` saved.
+//!
+//! [`Solution`]: aoc_common::solution::Solution
+
+use std::path::{Path, PathBuf};
+
+use aoc_common::locate::{example_path, input_cache_dir};
+use aoc_common::Result;
+
+use crate::find_year;
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.tmpl");
+
+/// What a year crate's `lib.rs` says when its days can simply `use` it
+const PRELUDE_EXPORT: &str = "pub use aoc_common::prelude::*;";
+
+pub fn new_day(year: u16, day: u8, example: Option<usize>) -> Result<()> {
+    let found = find_year(year)?;
+    // 2017's days aren't in `src/bin`, so check the registry rather than
+    // only finding out when we'd overwrite one
+    if found.day(day).is_some() {
+        return Err(format!("{} day {} already exists", year, day).into());
+    }
+    let crate_dir = found.base_dir()?;
+    let example = match example {
+        Some(n) => {
+            let path = example_path(&input_cache_dir(year)?, day as usize, n);
+            let example = std::fs::read_to_string(&path).map_err(|e| {
+                format!(
+                    "Unable to read example {}, try `aoc fetch {} {}`: {}",
+                    path.display(),
+                    year,
+                    day,
+                    e
+                )
+            })?;
+            Some(example)
+        }
+        None => None,
+    };
+    for path in scaffold(&crate_dir, year, day, example.as_deref())? {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Write the new day (and the aggregator if need be) into `crate_dir`,
+/// returning what was written
+fn scaffold(crate_dir: &Path, year: u16, day: u8, example: Option<&str>) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {}", day).into());
+    }
+    let bin_dir = crate_dir.join("src").join("bin");
+    let day_path = bin_dir.join(format!("{}day{}.rs", year, day));
+    if day_path.exists() {
+        return Err(format!("{} already exists", day_path.display()).into());
+    }
+    let lib = std::fs::read_to_string(crate_dir.join("src").join("lib.rs"))?;
+    // Older years don't re-export the prelude, so their days go direct
+    let prelude = if lib.contains(PRELUDE_EXPORT) {
+        format!("aoc{}", year)
+    } else {
+        "aoc_common::prelude".to_string()
+    };
+
+    let mut written = Vec::new();
+    std::fs::create_dir_all(&bin_dir)?;
+    std::fs::write(&day_path, render_day(&prelude, day, example))?;
+    written.push(day_path);
+
+    let all_path = bin_dir.join(format!("{}all.rs", year));
+    if !all_path.exists() {
+        std::fs::write(&all_path, render_aggregator(&prelude, year))?;
+        written.push(all_path);
+    }
+    Ok(written)
+}
+
+fn render_day(prelude: &str, day: u8, example: Option<&str>) -> String {
+    let example = example.unwrap_or("").trim_end_matches('\n');
+    DAY_TEMPLATE
+        .replace("{{PRELUDE}}", prelude)
+        .replace("{{DAY}}", &day.to_string())
+        .replace("{{TEST_INPUT}}", &raw_string(example))
+}
+
+fn render_aggregator(prelude: &str, year: u16) -> String {
+    if prelude == format!("aoc{}", year) {
+        format!(
+            "use aoc{}::*;\n\nfn main() -> Result<()> {{\n    aoc_common::registry::run_year(&days::YEAR)\n}}\n",
+            year
+        )
+    } else {
+        format!(
+            "fn main() -> aoc_common::Result<()> {{\n    aoc_common::registry::run_year(&aoc{}::days::YEAR)\n}}\n",
+            year
+        )
+    }
+}
+
+/// A raw string literal holding `text`, with enough `#`s that nothing in
+/// the text can end it early
+fn raw_string(text: &str) -> String {
+    let longest = text
+        .split('"')
+        .skip(1)
+        .map(|after| after.chars().take_while(|&c| c == '#').count())
+        .max()
+        .unwrap_or(0);
+    let hashes = "#".repeat(longest + 1);
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn raw_strings() {
+        assert_eq!(raw_string(""), "r#\"\"#");
+        assert_eq!(raw_string("1,2\n3,4"), "r#\"1,2\n3,4\"#");
+        assert_eq!(raw_string("say \"#hi\"##"), "r###\"say \"#hi\"##\"###");
+    }
+
+    #[test]
+    fn rendering() {
+        let day = render_day("aoc2023", 21, Some("..#\n.S.\n"));
+        assert!(day.starts_with("use aoc2023::*;\n\n#[derive(Debug)]\n"));
+        assert!(day.contains("solution! {\n    Day21: Input = "));
+        assert!(day.contains("run_solution(SOLUTION, 21)"));
+        assert!(day.contains("static TEST_INPUT: &str = r#\"..#\n.S.\"#;"));
+        assert!(!day.contains("{{"));
+        // Nothing which would panic when run before it is written
+        assert!(day.contains("Err(format!(\"part 1 not implemented"));
+        assert!(!day.contains("todo!"));
+    }
+
+    #[test]
+    fn scaffolding() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "pub const SKIPPED_DAYS: &[(u8, &str)] = &[];\n",
+        )
+        .unwrap();
+
+        let written = scaffold(&dir, 2016, 3, None).unwrap();
+        assert_eq!(
+            written,
+            vec![
+                dir.join("src/bin/2016day3.rs"),
+                dir.join("src/bin/2016all.rs")
+            ]
+        );
+        let day = std::fs::read_to_string(&written[0]).unwrap();
+        assert!(day.starts_with("use aoc_common::prelude::*;\n"));
+        assert!(day.contains("static TEST_INPUT: &str = r#\"\"#;"));
+        let all = std::fs::read_to_string(&written[1]).unwrap();
+        assert!(all.contains("run_year(&aoc2016::days::YEAR)"));
+
+        // A second day leaves the aggregator alone, and nothing is replaced
+        std::fs::write(&written[1], "mine").unwrap();
+        assert_eq!(
+            scaffold(&dir, 2016, 4, None).unwrap(),
+            vec![dir.join("src/bin/2016day4.rs")]
+        );
+        assert_eq!(std::fs::read_to_string(&written[1]).unwrap(), "mine");
+        assert!(scaffold(&dir, 2016, 3, None).is_err());
+        assert!(scaffold(&dir, 2016, 26, None).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,223 @@
+//! Getting the answers out of a day
+//!
+//! Days which are a [`Solution`](aoc_common::solution::Solution) are asked
+//! directly.  Older days only print their answers, so those are run in a
+//! child `aoc` and their output is picked apart.  The child times the day's
+//! `main` itself and says how long it took after the day's own output, so
+//! starting it isn't counted.
+
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use aoc_common::input::read_input;
+use aoc_common::locate::{explicit_input, set_year};
+use aoc_common::registry::{Day, Year};
+use aoc_common::solution::DynSolution;
+use aoc_common::Result;
+
+/// The answers a day gave, and how long it took to give them
+pub struct Solved {
+    /// Indexed by part number minus one
+    pub answers: [Option<String>; 2],
+    pub timings: Timings,
+}
+
+/// Set in a child `aoc` to have it say how long the day's `main` took
+const MAIN_TIME_VAR: &str = "AOC_REPORT_MAIN_TIME";
+
+/// What the child says, followed by the nanoseconds `main` took
+const MAIN_TIME_LABEL: &str = "aoc: main took";
+
+/// Wall time spent solving a day
+///
+/// We can only see inside days which are a `Solution`, for older days all we
+/// know is the total.  Either way the total includes reading the input.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Option<Duration>,
+    /// Indexed by part number minus one
+    pub parts: [Option<Duration>; 2],
+    pub total: Duration,
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(parse) = self.parse {
+            write!(f, "parse {:.2?}, ", parse)?;
+        }
+        for (part, time) in (1..=2).zip(self.parts) {
+            if let Some(time) = time {
+                write!(f, "part {} {:.2?}, ", part, time)?;
+            }
+        }
+        write!(f, "total {:.2?}", self.total)
+    }
+}
+
+/// Solve a day, either completely or just the one part
+///
+/// Older days can't be asked for only one part, so they always solve both.
+pub fn solve(year: &Year, day: &Day, only: Option<u8>) -> Result<Solved> {
+    set_year(year.year);
+    match day.solution {
+        Some(solution) => solve_solution(year, day, solution, only),
+        None => solve_legacy(day),
+    }
+}
+
+fn solve_solution(
+    year: &Year,
+    day: &Day,
+    solution: &dyn DynSolution,
+    only: Option<u8>,
+) -> Result<Solved> {
+    year.enter()?;
+    let panicked = |_| format!("{} day {} panicked", day.year, day.day);
+    let start = Instant::now();
+    let text = read_input(day.day as usize)?;
+    let mut timings = Timings::default();
+    let parse_start = Instant::now();
+    let input = catch_unwind(AssertUnwindSafe(|| solution.parse(&text))).map_err(panicked)??;
+    timings.parse = Some(parse_start.elapsed());
+    let mut answers: [Option<String>; 2] = Default::default();
+    for part in 1..=2 {
+        if only.is_none_or(|p| p == part) {
+            let part_start = Instant::now();
+            let answer = catch_unwind(AssertUnwindSafe(|| solution.part(&*input, part)))
+                .map_err(panicked)??;
+            timings.parts[part as usize - 1] = Some(part_start.elapsed());
+            answers[part as usize - 1] = Some(answer);
+        }
+    }
+    timings.total = start.elapsed();
+    Ok(Solved { answers, timings })
+}
+
+fn solve_legacy(day: &Day) -> Result<Solved> {
+    let mut child = std::process::Command::new(std::env::current_exe()?);
+    child.args(["run", &day.year.to_string(), &day.day.to_string()]);
+    if let Some(input) = explicit_input() {
+        child.arg("--input").arg(input.as_arg());
+    }
+    let output = child
+        .env(MAIN_TIME_VAR, "1")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(format!("{} day {} failed", day.year, day.day).into());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (stdout, total) = split_main_time(&stdout)
+        .ok_or_else(|| format!("{} day {} didn't say how long it took", day.year, day.day))?;
+    Ok(Solved {
+        answers: answers_in(stdout),
+        timings: Timings {
+            total,
+            ..Timings::default()
+        },
+    })
+}
+
+/// Say how long a day's `main` took, if the `aoc` which started us asked
+pub fn report_main_time(elapsed: Duration) {
+    if std::env::var_os(MAIN_TIME_VAR).is_some() {
+        println!("{} {}", MAIN_TIME_LABEL, elapsed.as_nanos());
+    }
+}
+
+/// Take what [`report_main_time`] said off the end of a child's output
+fn split_main_time(output: &str) -> Option<(&str, Duration)> {
+    let (output, said) = output.trim_end().rsplit_once(MAIN_TIME_LABEL)?;
+    let nanos = said.trim().parse().ok()?;
+    Some((output, Duration::from_nanos(nanos)))
+}
+
+/// Which part an output line starts the answer for, if any
+///
+/// Over the years days have said `Part 1:`, `Problem 1:` and `Result 1:`.
+fn answer_part(line: &str) -> Option<u8> {
+    let (label, rest) = line.split_once(' ')?;
+    if !matches!(label, "Part" | "Problem" | "Result") {
+        return None;
+    }
+    let (n, _) = rest.split_once(':')?;
+    n.parse().ok()
+}
+
+/// Pick the answers out of what an older day printed
+///
+/// An answer is whatever follows its label.  Some answers are drawn rather
+/// than printed, so a label with nothing after it takes the lines following
+/// it, up to the next label.
+fn answers_in(output: &str) -> [Option<String>; 2] {
+    let mut lines: [Vec<&str>; 2] = Default::default();
+    let mut current = None;
+    for line in output.lines() {
+        match answer_part(line) {
+            Some(part @ 1..=2) => {
+                let idx = part as usize - 1;
+                let (_, rest) = line.split_once(':').unwrap();
+                let rest = rest.trim();
+                lines[idx] = vec![rest];
+                current = if rest.is_empty() { Some(idx) } else { None };
+            }
+            Some(_) => current = None,
+            None => {
+                if let Some(idx) = current {
+                    lines[idx].push(line.trim_end());
+                }
+            }
+        }
+    }
+    lines.map(|mut lines| {
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        let first = lines.iter().position(|l| !l.is_empty())?;
+        Some(lines[first..].join("\n"))
+    })
+}
+
+/// Print an answer the way the days do, with drawn answers on their own lines
+pub fn print_answer(part: u8, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answer_lines() {
+        assert_eq!(answer_part("Part 2: 1154"), Some(2));
+        assert_eq!(answer_part("Problem 1: 12"), Some(1));
+        assert_eq!(answer_part("Result 2: lsoypmia"), Some(2));
+        assert_eq!(answer_part("#..#.###"), None);
+    }
+
+    #[test]
+    fn picking_answers() {
+        let output = "Reading input\nProblem 1: 12\nChatter\nPart 2:\n#..#\n####\n\n";
+        assert_eq!(
+            answers_in(output),
+            [Some("12".to_string()), Some("#..#\n####".to_string())]
+        );
+        assert_eq!(answers_in("Result 1: 5\n"), [Some("5".to_string()), None]);
+    }
+
+    #[test]
+    fn main_times() {
+        assert_eq!(
+            split_main_time("Part 1: 12\naoc: main took 1500\n"),
+            Some(("Part 1: 12\n", Duration::from_nanos(1500)))
+        );
+        assert_eq!(split_main_time("Part 1: 12\n"), None);
+    }
+}
@@ -0,0 +1,112 @@
+//! Checking days against their known-good answers
+//!
+//! `aoc verify` runs days on their real input and compares what they say with
+//! `answers/YYYY.toml`.  A day is missing if we have no answers recorded for
+//! it, `--record` fills those in from what the day says now.
+
+use aoc_common::registry::{Day, Year};
+use aoc_common::Result;
+
+use crate::answers::Answers;
+use crate::solve::solve;
+use crate::{selected_days, Selection};
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+pub fn verify(selection: &Selection, record: bool) -> Result<()> {
+    let mut tally = Tally::default();
+    for (year, days) in selected_days(selection)? {
+        verify_days(year, &days, record, &mut tally)?;
+    }
+    println!(
+        "{} passed, {} failed, {} missing",
+        tally.passed, tally.failed, tally.missing
+    );
+    if tally.failed > 0 {
+        Err(format!("{} days failed verification", tally.failed).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn verify_days(year: &Year, days: &[&Day], record: bool, tally: &mut Tally) -> Result<()> {
+    let mut answers = Answers::load(year.year)?;
+    for day in days {
+        // Saved as we go, so a day which brings the whole runner down doesn't
+        // lose what we learned from the days before it
+        if verify_day(year, day, &mut answers, record, tally) {
+            answers.save()?;
+        }
+    }
+    Ok(())
+}
+
+/// Verify one day, returning whether we recorded any new answers for it
+fn verify_day(
+    year: &Year,
+    day: &Day,
+    answers: &mut Answers,
+    record: bool,
+    tally: &mut Tally,
+) -> bool {
+    let label = format!("{} day {}", year.year, day.day);
+    let solved = match solve(year, day, None) {
+        Ok(solved) => solved,
+        Err(e) => {
+            println!("{}: FAIL ({})", label, e);
+            tally.failed += 1;
+            return false;
+        }
+    };
+    let known = answers.day(day.day).cloned().unwrap_or_default();
+    let mut checked = 0;
+    let mut wrong = Vec::new();
+    let mut unknown = Vec::new();
+    for part in 1..=2 {
+        let got = solved.answers[part as usize - 1].as_deref();
+        match (known.part(part), got) {
+            (None, None) => {}
+            (None, Some(_)) => unknown.push(part),
+            (Some(expected), got) => {
+                checked += 1;
+                if got != Some(expected) {
+                    wrong.push((part, expected, got.unwrap_or("nothing")));
+                }
+            }
+        }
+    }
+
+    let elapsed = format!("{:.2?}", solved.timings.total);
+    if !wrong.is_empty() {
+        println!("{}: FAIL ({})", label, elapsed);
+        for (part, expected, got) in wrong {
+            println!("  part {}: expected {:?}, got {:?}", part, expected, got);
+        }
+        tally.failed += 1;
+        // Don't learn anything from a day which is getting things wrong
+        return false;
+    }
+    if checked == 0 {
+        println!("{}: missing ({})", label, elapsed);
+        tally.missing += 1;
+    } else if unknown.is_empty() {
+        println!("{}: pass ({})", label, elapsed);
+        tally.passed += 1;
+    } else {
+        println!(
+            "{}: pass ({}), part {} not recorded",
+            label, elapsed, unknown[0]
+        );
+        tally.passed += 1;
+    }
+    if record && answers.record(day.day, &solved.answers) {
+        println!("  recorded answers for {}", label);
+        return true;
+    }
+    false
+}
@@ -0,0 +1,19 @@
+//! Every year the runner knows about
+
+use aoc_common::registry::Year;
+
+pub static YEARS: &[&Year] = &[
+    &aoc2015::days::YEAR,
+    &aoc2016::days::YEAR,
+    &aoc2017::days::YEAR,
+    &aoc2018::days::YEAR,
+    &aoc2019::days::YEAR,
+    &aoc2020::days::YEAR,
+    &aoc2021::days::YEAR,
+    &aoc2022::days::YEAR,
+    &aoc2023::days::YEAR,
+];
+
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().copied().find(|y| y.year == year)
+}
@@ -0,0 +1,143 @@
+//! Compass directions and positions on a plane
+//!
+//! `Facing` accepts both compass letters and `U`/`R`/`D`/`L` when parsed, since
+//! puzzles use both and 2023 needed the latter.
+
+use parsebyregex_derive::ParseByRegex;
+
+#[derive(ParseByRegex, Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Facing {
+    #[regex = "[NnUu]"]
+    North,
+    #[regex = "[EeRr]"]
+    East,
+    #[regex = "[SsDd]"]
+    South,
+    #[regex = "[WwLl]"]
+    West,
+}
+
+impl Facing {
+    fn turn_left(self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+
+    fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    pub fn turn_left_deg(mut self, mut deg: i32) -> Self {
+        if deg < 0 {
+            self.turn_right_deg(-deg)
+        } else {
+            while deg > 0 {
+                self = self.turn_left();
+                deg -= 90;
+            }
+            self
+        }
+    }
+
+    pub fn turn_right_deg(mut self, mut deg: i32) -> Self {
+        if deg < 0 {
+            self.turn_left_deg(-deg)
+        } else {
+            while deg > 0 {
+                self = self.turn_right();
+                deg -= 90;
+            }
+            self
+        }
+    }
+
+    pub fn row_col_offset(self) -> (i32, i32) {
+        match self {
+            Facing::North => (-1, 0),
+            Facing::East => (0, 1),
+            Facing::South => (1, 0),
+            Facing::West => (0, -1),
+        }
+    }
+
+    pub fn do_row_col_move(self, row: i32, col: i32) -> (i32, i32) {
+        let ofs = self.row_col_offset();
+        (row + ofs.0, col + ofs.1)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct XYPosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl XYPosition {
+    pub fn moved(self, dir: Facing, amount: i32) -> Self {
+        let delts = match dir {
+            Facing::North => (0, 1),
+            Facing::East => (1, 0),
+            Facing::South => (0, -1),
+            Facing::West => (-1, 0),
+        };
+        Self {
+            x: self.x + (delts.0 * amount),
+            y: self.y + (delts.1 * amount),
+        }
+    }
+
+    pub fn origin_manhattan(&self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    #[allow(clippy::comparison_chain)]
+    pub fn rotate_left(self, deg: i32) -> Self {
+        if deg == 0 {
+            self
+        } else if deg < 0 {
+            self.rotate_right(-deg)
+        } else {
+            (XYPosition {
+                x: -self.y,
+                y: self.x,
+            })
+            .rotate_left(deg - 90)
+        }
+    }
+
+    #[allow(clippy::comparison_chain)]
+    pub fn rotate_right(self, deg: i32) -> Self {
+        if deg == 0 {
+            self
+        } else if deg < 0 {
+            self.rotate_left(-deg)
+        } else {
+            (XYPosition {
+                x: self.y,
+                y: -self.x,
+            })
+            .rotate_right(deg - 90)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn posrot() {
+        let pos = XYPosition { x: 10, y: 1 };
+        assert_eq!(pos, pos.rotate_left(360));
+        assert_eq!(pos.rotate_left(180), pos.rotate_right(180));
+    }
+}
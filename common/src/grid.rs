@@ -0,0 +1,400 @@
+//! Rectangular grids of cells
+//!
+//! Positions are `(row, col)`, counting down and right from the top left, to
+//! match [`Facing::row_col_offset`].  A grid is either bounded, where stepping
+//! off an edge goes nowhere, or it wraps around like a torus.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::Facing;
+use crate::Result;
+
+/// What happens when stepping off the edge of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Edges {
+    /// There is nothing beyond the edge
+    #[default]
+    Bounded,
+    /// Leaving one edge brings you back in at the opposite one
+    Wrapping,
+}
+
+/// Offsets to the four orthogonal neighbours, in reading order
+const ORTHOGONAL: [(i64, i64); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets to all eight neighbours, in reading order
+const ALL_AROUND: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    edges: Edges,
+}
+
+impl<T> Grid<T> {
+    /// A bounded grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+            edges: Edges::Bounded,
+        }
+    }
+
+    /// A bounded grid from its rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(row) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {} is {} wide, but row 0 is {}",
+                row,
+                rows[row].len(),
+                width
+            )
+            .into());
+        }
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+            edges: Edges::Bounded,
+        })
+    }
+
+    /// Parse a grid with one cell per character
+    ///
+    /// Empty lines around the grid, and each line's `\r\n` or `\n`, are
+    /// ignored.  Any other whitespace is a cell like everything else, since
+    /// some grids start with spaces.
+    pub fn parse<F: FnMut(char) -> T>(input: &str, mut cell: F) -> Result<Self> {
+        Self::try_parse(input, |c| Some(cell(c)))
+    }
+
+    /// Parse a grid where some characters may not make sense as a cell
+    pub fn try_parse<F: FnMut(char) -> Option<T>>(input: &str, mut cell: F) -> Result<Self> {
+        let rows = input
+            .trim_matches(['\r', '\n'])
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.trim_end_matches('\r')
+                    .chars()
+                    .enumerate()
+                    .map(|(col, c)| {
+                        cell(c).ok_or_else(|| {
+                            format!("Unexpected {:?} at row {}, col {}", c, row, col).into()
+                        })
+                    })
+                    .collect::<Result<Vec<T>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_rows(rows)
+    }
+
+    /// Change what happens at the edges of this grid
+    pub fn with_edges(mut self, edges: Edges) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    /// Make this grid wrap around at its edges
+    pub fn wrapping(self) -> Self {
+        self.with_edges(Edges::Wrapping)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn edges(&self) -> Edges {
+        self.edges
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Where `(row, col)` is on this grid, if anywhere
+    ///
+    /// Bounded grids have nothing outside them, wrapping grids bring any
+    /// position back onto the grid.
+    pub fn locate(&self, row: i64, col: i64) -> Option<(usize, usize)> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let (height, width) = (self.height as i64, self.width as i64);
+        match self.edges {
+            Edges::Bounded => {
+                if (0..height).contains(&row) && (0..width).contains(&col) {
+                    Some((row as usize, col as usize))
+                } else {
+                    None
+                }
+            }
+            Edges::Wrapping => Some((
+                row.rem_euclid(height) as usize,
+                col.rem_euclid(width) as usize,
+            )),
+        }
+    }
+
+    /// The position `(drow, dcol)` away from `pos`
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        (drow, dcol): (i64, i64),
+    ) -> Option<(usize, usize)> {
+        self.locate(row as i64 + drow, col as i64 + dcol)
+    }
+
+    /// The position one step from `pos` in the direction `facing`
+    pub fn step(&self, pos: (usize, usize), facing: Facing) -> Option<(usize, usize)> {
+        let (drow, dcol) = facing.row_col_offset();
+        self.offset(pos, (drow.into(), dcol.into()))
+    }
+
+    /// The up to four positions orthogonally next to `pos`
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// The up to eight positions around `pos`, including diagonals
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ALL_AROUND
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// Every position on the grid, in reading order
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell along with its position, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // `chunks` can't be given a width of zero
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.width, "There is no column {}", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// A grid of the same shape with each cell replaced
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            edges: self.edges,
+        }
+    }
+
+    /// Rows become columns and columns become rows
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remapped(self.height, self.width, |row, col| (col, row))
+    }
+
+    /// Turn the grid a quarter turn clockwise
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remapped(height, self.width, |row, col| (height - 1 - col, row))
+    }
+
+    /// Turn the grid a quarter turn anticlockwise
+    pub fn rotate_anticlockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remapped(self.height, width, |row, col| (col, width - 1 - row))
+    }
+
+    /// Build a `width` by `height` grid whose cell at `(row, col)` is this
+    /// grid's cell at `source(row, col)`
+    fn remapped<F: Fn(usize, usize) -> (usize, usize)>(
+        &self,
+        width: usize,
+        height: usize,
+        source: F,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| self[source(row, col)].clone())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+            edges: self.edges,
+        }
+    }
+
+    /// Draw the grid with one character per cell
+    pub fn render<F: FnMut(&T) -> char>(&self, mut cell: F) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut cell));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, width, height))
+    }
+}
+
+/// Each row on its own line, with every cell written by its own `Display`
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u8> {
+        Grid::try_parse(input, |c| c.to_digit(10).map(|d| d as u8)).unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = digits("\n123\r\n456\r\n\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert!(Grid::try_parse("12\n3x", |c| c.to_digit(10)).is_err());
+        assert!(Grid::parse("12\n3", |c| c).is_err());
+        // Spaces are cells, even at the start or end of a line
+        let grid = Grid::parse("  #\n#  \n", |c| c);
+        assert_eq!(grid.unwrap().render(|&c| c), "  #\n#  \n");
+        assert!(Grid::try_parse("  12\n  34", |c| c.to_digit(10)).is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = digits("123\n456\n789");
+        let around =
+            |grid: &Grid<u8>, pos| grid.neighbours8(pos).map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(
+            grid.neighbours4((1, 1))
+                .map(|p| grid[p])
+                .collect::<Vec<_>>(),
+            vec![2, 4, 6, 8]
+        );
+        assert_eq!(around(&grid, (0, 0)), vec![2, 4, 5]);
+        assert_eq!(grid.step((0, 0), Facing::North), None);
+        assert_eq!(grid.locate(-1, 1), None);
+
+        let grid = grid.wrapping();
+        assert_eq!(around(&grid, (0, 0)), vec![9, 7, 8, 3, 2, 6, 4, 5]);
+        assert_eq!(grid.step((0, 0), Facing::North), Some((2, 0)));
+        assert_eq!(grid.locate(-4, 7), Some((2, 1)));
+    }
+
+    #[test]
+    fn reshaping() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "41\n52\n63\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "36\n25\n14\n");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_clockwise().to_string(),
+            "654\n321\n"
+        );
+        assert_eq!(grid.rotate_clockwise().rotate_anticlockwise(), grid);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.rows().next_back(), Some(&[4, 5, 6][..]));
+        assert_eq!(
+            grid.map(|&d| d > 3).render(|&b| if b { '#' } else { '.' }),
+            "...\n###\n"
+        );
+    }
+}
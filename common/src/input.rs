@@ -0,0 +1,144 @@
+//! Reading and splitting up puzzle inputs
+
+use std::io::Cursor;
+
+use itertools::Itertools;
+use parsebyregex::ParseByRegex;
+
+use crate::locate::find_input;
+use crate::Result;
+
+/// Read the input for `day`, see [`locate`](crate::locate) for where from
+pub fn read_input(day: usize) -> Result<String> {
+    color_backtrace::install();
+    find_input(day)
+}
+
+/// The input for `day` for days which want to `BufRead` it a line at a time
+pub fn open_input(day: usize) -> Result<Cursor<String>> {
+    Ok(Cursor::new(read_input(day)?))
+}
+
+pub fn read_input_as_vec<T: ParseByRegex>(day: usize) -> Result<Vec<T>> {
+    let plain = read_input(day)?;
+    input_as_vec(plain)
+}
+
+pub fn input_as_vec<T: ParseByRegex, S: AsRef<str>>(plain: S) -> Result<Vec<T>> {
+    plain
+        .as_ref()
+        .trim()
+        .lines()
+        .map(ParseByRegex::parse_by_regex)
+        .collect()
+}
+
+pub fn input_as_vec_and_first<T: ParseByRegex, S: AsRef<str>>(
+    plain: S,
+) -> Result<(Vec<T>, String)> {
+    let mut lines = plain.as_ref().trim().lines();
+    let first = lines.next();
+    let mapped: Result<Vec<T>> = lines
+        .filter(|s| !s.trim().is_empty()) // In case there are any blanks after the first line
+        .map(ParseByRegex::parse_by_regex)
+        .collect();
+    if let Some(first) = first {
+        Ok((mapped?, first.trim().to_owned()))
+    } else {
+        Err("No lines at all?".into())
+    }
+}
+
+pub fn read_input_as_vec_and_first<T: ParseByRegex>(day: usize) -> Result<(Vec<T>, String)> {
+    let plain = read_input(day)?;
+    input_as_vec_and_first(plain)
+}
+
+pub fn line_as_list<T: ParseByRegex, S: AsRef<str>>(line: S) -> Result<Vec<T>> {
+    line.as_ref()
+        .trim()
+        .split(',')
+        .map(ParseByRegex::parse_by_regex)
+        .collect()
+}
+
+pub fn input_as_lists<T: ParseByRegex, S: AsRef<str>>(input: S) -> Result<Vec<Vec<T>>> {
+    input.as_ref().trim().lines().map(line_as_list).collect()
+}
+
+pub fn read_input_as_lists<T: ParseByRegex>(day: usize) -> Result<Vec<Vec<T>>> {
+    let plain = read_input(day)?;
+    input_as_lists(plain)
+}
+
+pub fn input_by_split_pat<T: ParseByRegex, S: AsRef<str>>(input: S, pat: &str) -> Result<Vec<T>> {
+    input
+        .as_ref()
+        .trim()
+        .split(pat)
+        .map(ParseByRegex::parse_by_regex)
+        .collect()
+}
+
+pub fn read_input_as_vec_split<T: ParseByRegex>(day: usize, pat: &str) -> Result<Vec<T>> {
+    let plain = read_input(day)?;
+    input_by_split_pat(plain, pat)
+}
+
+pub fn input_as_first_and_vec_by_pat<T: ParseByRegex, S: AsRef<str>>(
+    input: S,
+    pat: &str,
+) -> Result<(String, Vec<T>)> {
+    let input = input.as_ref().trim();
+    let mut bits = input.splitn(2, '\n');
+    let first = bits.next().ok_or("No lines")?;
+    let rest = bits.next().ok_or("Only one line?")?;
+    let first = first.to_string();
+    let rest = input_by_split_pat(rest, pat)?;
+    Ok((first, rest))
+}
+
+pub fn read_input_as_first_and_vec_by_pat<T: ParseByRegex>(
+    day: usize,
+    pat: &str,
+) -> Result<(String, Vec<T>)> {
+    let plain = read_input(day)?;
+    input_as_first_and_vec_by_pat(plain, pat)
+}
+
+pub fn input_as_groups<T>(input: &str) -> Result<Vec<Vec<T>>>
+where
+    T: ParseByRegex,
+{
+    input
+        .trim()
+        .lines()
+        .group_by(|&s| s.is_empty())
+        .into_iter()
+        .filter(|i| !i.0)
+        .map(|(_, lines)| lines.map(T::parse_by_regex).collect::<Result<Vec<_>>>())
+        .collect()
+}
+
+pub fn read_input_as_groups<T>(day: usize) -> Result<Vec<Vec<T>>>
+where
+    T: ParseByRegex,
+{
+    let plain = read_input(day)?;
+    input_as_groups(&plain)
+}
+
+pub fn input_as_chunks<T>(input: &str) -> Result<Vec<T>>
+where
+    T: ParseByRegex,
+{
+    input.trim().split("\n\n").map(T::parse_by_regex).collect()
+}
+
+pub fn read_input_as_chunks<T>(day: usize) -> Result<Vec<T>>
+where
+    T: ParseByRegex,
+{
+    let plain = read_input(day)?;
+    input_as_chunks(&plain)
+}
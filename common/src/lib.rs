@@ -0,0 +1,35 @@
+//! Helpers shared by every year of Advent of Code
+//!
+//! Each year crate re-exports the [`prelude`] from its own `lib.rs` so that
+//! the day binaries can carry on doing `use aocYYYY::*;`.  Where a year needs
+//! a helper to behave differently (for example 2019's `Up`/`Down` flavoured
+//! `Facing`) it defines its own item, which shadows the one in the prelude.
+
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod locate;
+pub mod maths;
+pub mod ocr;
+pub mod options;
+pub mod registry;
+pub mod solution;
+pub mod strings;
+
+pub type StdResult<T, E> = std::result::Result<T, E>;
+pub type GenError = Box<dyn std::error::Error>;
+pub type Result<T> = std::result::Result<T, GenError>;
+
+/// Everything a year crate wants to re-export to its day binaries
+pub mod prelude {
+    pub use super::geometry::{Facing, XYPosition};
+    pub use super::grid::{Edges, Grid};
+    pub use super::input::*;
+    pub use super::maths::{hex_byte_to_value, triangle, Lcm};
+    pub use super::ocr;
+    // The `solution!` macro, which shares its name with the module
+    pub use super::solution;
+    pub use super::solution::{run_solution, DynSolution, Solution};
+    pub use super::strings::{CommaSpacedString, SpacedString};
+    pub use super::{GenError, Result, StdResult};
+}
@@ -0,0 +1,269 @@
+//! Finding a day's input
+//!
+//! In order, the input for a day comes from:
+//!
+//! 1. An explicit source, either given to a day binary as its only argument
+//!    (`2019day4 some/other/input`) or set by the runner with
+//!    [`set_explicit_input`].  A source of `-` means stdin.
+//! 2. `$AOC_INPUT_DIR/YYYY/dayN`, then `$AOC_INPUT_DIR/dayN`.
+//! 3. `inputs/dayN`, relative to wherever we are running.
+//! 4. `YYYY/inputs/dayN` in this workspace, so days can be run from anywhere.
+//!
+//! The workspace is found when we run, not when we're built, see
+//! [`workspace_dir`].
+//!
+//! The year is whatever [`set_year`] said, or failing that is worked out from
+//! the name of the running binary.
+
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::Result;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const WORKSPACE_VAR: &str = "AOC_WORKSPACE";
+
+/// Somewhere to read an input from, rather than searching for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl From<OsString> for InputSource {
+    fn from(arg: OsString) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(arg.into())
+        }
+    }
+}
+
+impl InputSource {
+    /// How to pass this source on to another process on its command line
+    pub fn as_arg(&self) -> OsString {
+        match self {
+            InputSource::Stdin => "-".into(),
+            InputSource::File(path) => path.clone().into_os_string(),
+        }
+    }
+}
+
+static YEAR: AtomicU16 = AtomicU16::new(0);
+static EXPLICIT: Mutex<Option<InputSource>> = Mutex::new(None);
+/// Plenty of days read their input more than once, stdin can only be read once
+static STDIN: Mutex<Option<String>> = Mutex::new(None);
+static WORKSPACE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Say which year is being run, for when the binary's name doesn't say
+pub fn set_year(year: u16) {
+    YEAR.store(year, Ordering::Relaxed);
+}
+
+/// Read every input from `source` instead of searching for it
+pub fn set_explicit_input(source: Option<InputSource>) {
+    *EXPLICIT.lock().unwrap() = source;
+}
+
+pub fn explicit_input() -> Option<InputSource> {
+    EXPLICIT.lock().unwrap().clone()
+}
+
+/// The root of this workspace, where each year has its own directory
+///
+/// An `aoc` which has been installed or moved since it was built should work
+/// on whichever checkout it is run in, so this is `$AOC_WORKSPACE` if that is
+/// set, and otherwise the nearest directory with a workspace `Cargo.toml` at
+/// or above where we are running, or failing that above the running binary.
+pub fn workspace_dir() -> Result<&'static Path> {
+    WORKSPACE
+        .get_or_init(find_workspace)
+        .as_deref()
+        .ok_or_else(|| {
+            format!(
+                "Unable to find the workspace, run from inside it or set {}",
+                WORKSPACE_VAR
+            )
+            .into()
+        })
+}
+
+fn is_workspace(dir: &Path) -> bool {
+    std::fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|toml| toml.contains("[workspace]"))
+}
+
+fn find_workspace() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(WORKSPACE_VAR) {
+        return Some(dir.into());
+    }
+    let starts = [std::env::current_dir().ok(), std::env::current_exe().ok()];
+    starts.into_iter().flatten().find_map(|start| {
+        start
+            .ancestors()
+            .find(|dir| is_workspace(dir))
+            .map(Path::to_path_buf)
+    })
+}
+
+/// Binaries are called `YYYYdayN` or `YYYYall`, tests have a hash on the end.
+/// The 2017 days are crates of their own, so theirs are `aoc2017dayN`.
+fn binary_name() -> Option<String> {
+    let exe = std::env::current_exe().ok()?;
+    Some(exe.file_stem()?.to_str()?.to_string())
+}
+
+fn binary_year(name: &str) -> Option<u16> {
+    let name = name.strip_prefix("aoc").unwrap_or(name);
+    name.get(..4)?.parse().ok()
+}
+
+fn current_year() -> Option<u16> {
+    match YEAR.load(Ordering::Relaxed) {
+        0 => binary_year(&binary_name()?),
+        year => Some(year),
+    }
+}
+
+/// A day binary takes an explicit input as its only argument.  Anything else
+/// (the runner, the aggregators, tests) has arguments which mean other things.
+fn command_line_input(day: usize) -> Option<InputSource> {
+    let name = binary_name()?;
+    let (_, name_day) = name.split_once("day")?;
+    if name_day.parse::<usize>().ok()? != day {
+        return None;
+    }
+    std::env::args_os().nth(1).map(InputSource::from)
+}
+
+/// Every place we would look for the input, in the order we'd look
+fn candidates(year: Option<u16>, day: usize, input_dir: Option<&Path>) -> Vec<PathBuf> {
+    let file = format!("day{}", day);
+    let mut paths = Vec::new();
+    if let Some(dir) = input_dir {
+        if let Some(year) = year {
+            paths.push(dir.join(year.to_string()).join(&file));
+        }
+        paths.push(dir.join(&file));
+    }
+    paths.push(Path::new("inputs").join(&file));
+    if let Some(path) = year.and_then(|year| default_input_path(year, day)) {
+        paths.push(path);
+    }
+    paths
+}
+
+/// Where a day's input lives in this workspace, if we can find it
+pub fn default_input_path(year: u16, day: usize) -> Option<PathBuf> {
+    let dir = workspace_dir().ok()?;
+    Some(
+        dir.join(year.to_string())
+            .join("inputs")
+            .join(format!("day{}", day)),
+    )
+}
+
+/// Where a year's downloaded inputs (and examples) belong, which is
+/// `$AOC_INPUT_DIR/YYYY` if that is set and the year's `inputs` otherwise
+pub fn input_cache_dir(year: u16) -> Result<PathBuf> {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => Ok(PathBuf::from(dir).join(year.to_string())),
+        None => Ok(workspace_dir()?.join(year.to_string()).join("inputs")),
+    }
+}
+
+/// Examples are cached next to the input as `dayN.exampleK`, counting from 1
+pub fn example_path(dir: &Path, day: usize, example: usize) -> PathBuf {
+    dir.join(format!("day{}.example{}", day, example))
+}
+
+fn read_source(source: &InputSource) -> Result<String> {
+    match source {
+        InputSource::Stdin => {
+            let mut stdin = STDIN.lock().unwrap();
+            if stdin.is_none() {
+                let mut input = String::new();
+                std::io::stdin().read_to_string(&mut input)?;
+                *stdin = Some(input);
+            }
+            Ok(stdin.clone().unwrap())
+        }
+        InputSource::File(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read input {}: {}", path.display(), e).into()),
+    }
+}
+
+/// Find and read the input for `day`
+pub fn find_input(day: usize) -> Result<String> {
+    if let Some(source) = explicit_input().or_else(|| command_line_input(day)) {
+        return read_source(&source);
+    }
+    let input_dir = std::env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    let tried = candidates(current_year(), day, input_dir.as_deref());
+    for path in &tried {
+        if path.is_file() {
+            return read_source(&InputSource::File(path.clone()));
+        }
+    }
+    // Days' `main` shows us with `{:?}`, so this all wants to be on one line
+    let tried = tried
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut message = format!("No input found for day {}, tried {}", day, tried);
+    if let Err(e) = workspace_dir() {
+        message.push_str(&format!(" ({})", e));
+    }
+    Err(message.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search_order() {
+        let paths = candidates(Some(2019), 4, Some(Path::new("/cache")));
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/cache/2019/day4"),
+                PathBuf::from("/cache/day4"),
+                PathBuf::from("inputs/day4"),
+                workspace_dir().unwrap().join("2019/inputs/day4"),
+            ]
+        );
+        assert_eq!(
+            candidates(None, 4, None),
+            vec![PathBuf::from("inputs/day4")]
+        );
+    }
+
+    #[test]
+    fn finding_the_workspace() {
+        let dir = workspace_dir().unwrap();
+        assert!(dir.join("common/src/locate.rs").is_file());
+        assert!(!is_workspace(&dir.join("common")));
+    }
+
+    #[test]
+    fn years_from_binaries() {
+        assert_eq!(binary_year("2019day4"), Some(2019));
+        assert_eq!(binary_year("2022all-0123abcd"), Some(2022));
+        assert_eq!(binary_year("aoc2017day12"), Some(2017));
+        assert_eq!(binary_year("aoc"), None);
+    }
+
+    #[test]
+    fn sources() {
+        assert_eq!(InputSource::from(OsString::from("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from(OsString::from("other")),
+            InputSource::File("other".into())
+        );
+    }
+}
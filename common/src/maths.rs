@@ -0,0 +1,35 @@
+//! Small numeric helpers
+
+use std::ops::{Div, Mul};
+
+use gcd::Gcd;
+
+pub trait Lcm {
+    type Output;
+    fn lcm(self, other: Self) -> Self::Output;
+}
+
+impl<T> Lcm for T
+where
+    T: Copy + Gcd + Mul<T>,
+    <T as Mul<T>>::Output: Div<T>,
+{
+    type Output = <<T as Mul>::Output as Div<T>>::Output;
+
+    fn lcm(self, other: Self) -> Self::Output {
+        (self * other) / self.gcd(other)
+    }
+}
+
+pub fn triangle(n: i32) -> i32 {
+    (n * (n - 1)) / 2
+}
+
+pub fn hex_byte_to_value(b: u8) -> u8 {
+    match b {
+        b'0'..=b'9' => b - b'0',
+        b'a'..=b'f' => b - b'a' + 10,
+        b'A'..=b'F' => b - b'A' + 10,
+        _ => panic!("Invalue hex digit {}", b as char),
+    }
+}
@@ -0,0 +1,198 @@
+//! Reading the block letters which some puzzles draw as their answer
+//!
+//! Two fonts turn up over the years, one 6 pixels tall (letters are 4 or 5
+//! wide) and one 10 pixels tall (letters are 6 wide).  Letters are always
+//! separated by at least one unlit column, so a drawing is split into letters
+//! at its unlit columns and the font is picked by the drawing's height.
+
+use std::collections::HashSet;
+
+use crate::Result;
+
+/// Every letter of a font, drawn side by side with a blank column between
+struct Font {
+    letters: &'static str,
+    rows: &'static [&'static str],
+}
+
+const SMALL: Font = Font {
+    letters: "ABCEFGHIJKLOPRSUYZ",
+    rows: &[
+        ".##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####",
+        "#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#",
+        "#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.",
+        "####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..",
+        "#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...",
+        "#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####",
+    ],
+};
+
+const LARGE: Font = Font {
+    letters: "ABCEFGHJKLNPRXZ",
+    rows: &[
+        "..##...#####...####..######.######..####..#....#....###.#....#.#......#....#.#####..#####..#....#.######",
+        ".#..#..#....#.#....#.#......#......#....#.#....#.....#..#...#..#......##...#.#....#.#....#.#....#......#",
+        "#....#.#....#.#......#......#......#......#....#.....#..#..#...#......##...#.#....#.#....#..#..#.......#",
+        "#....#.#....#.#......#......#......#......#....#.....#..#.#....#......#.#..#.#....#.#....#..#..#......#.",
+        "#....#.#####..#......#####..#####..#......######.....#..##.....#......#.#..#.#####..#####....##......#..",
+        "######.#....#.#......#......#......#..###.#....#.....#..##.....#......#..#.#.#......#..#.....##.....#...",
+        "#....#.#....#.#......#......#......#....#.#....#.....#..#.#....#......#..#.#.#......#...#...#..#...#....",
+        "#....#.#....#.#......#......#......#....#.#....#.#...#..#..#...#......#...##.#......#...#...#..#..#.....",
+        "#....#.#....#.#....#.#......#......#...##.#....#.#...#..#...#..#......#...##.#......#....#.#....#.#.....",
+        "#....#.#####...####..######.#.......###.#.#....#..###...#....#.######.#....#.#......#....#.#....#.######",
+    ],
+};
+
+impl Font {
+    fn for_height(height: usize) -> Option<&'static Font> {
+        [&SMALL, &LARGE]
+            .into_iter()
+            .find(|font| font.rows.len() == height)
+    }
+
+    fn glyphs(&self) -> impl Iterator<Item = (char, String)> + '_ {
+        let rows: Vec<Vec<bool>> = self
+            .rows
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        self.letters.chars().zip(glyphs(&rows))
+    }
+}
+
+/// Is this character a lit pixel, as each year has drawn them
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | 'X' | '█')
+}
+
+/// Split a drawing into its glyphs, each rendered as `#`/`.` rows
+fn glyphs(rows: &[Vec<bool>]) -> Vec<String> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize| rows.iter().any(|row| row.get(x).copied().unwrap_or(false));
+    let mut glyphs = Vec::new();
+    let mut x = 0;
+    while x < width {
+        if !lit(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && lit(x) {
+            x += 1;
+        }
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                (start..x)
+                    .map(|x| {
+                        if row.get(x).copied().unwrap_or(false) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        glyphs.push(glyph);
+    }
+    glyphs
+}
+
+/// Read the letters drawn by a set of lit points
+pub fn read_points<I: IntoIterator<Item = (i64, i64)>>(points: I) -> Result<String> {
+    let points: HashSet<(i64, i64)> = points.into_iter().collect();
+    if points.is_empty() {
+        return Err("There are no letters to read".into());
+    }
+    let (minx, maxx) = points
+        .iter()
+        .map(|p| p.0)
+        .fold((i64::MAX, i64::MIN), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (miny, maxy) = points
+        .iter()
+        .map(|p| p.1)
+        .fold((i64::MAX, i64::MIN), |(lo, hi), y| (lo.min(y), hi.max(y)));
+    let rows: Vec<Vec<bool>> = (miny..=maxy)
+        .map(|y| (minx..=maxx).map(|x| points.contains(&(x, y))).collect())
+        .collect();
+    let font = Font::for_height(rows.len())
+        .ok_or_else(|| format!("Letters are 6 or 10 pixels tall, these are {}", rows.len()))?;
+    let known: Vec<(char, String)> = font.glyphs().collect();
+    glyphs(&rows)
+        .into_iter()
+        .map(|glyph| {
+            known
+                .iter()
+                .find(|(_, g)| *g == glyph)
+                .map(|(c, _)| *c)
+                .ok_or_else(|| format!("Unknown letter:\n{}", glyph).into())
+        })
+        .collect()
+}
+
+/// Read the letters in a drawing, where `#`, `X` or `█` are lit and anything
+/// else is not
+pub fn read_grid(text: &str) -> Result<String> {
+    read_points(text.lines().enumerate().flat_map(|(y, line)| {
+        line.chars()
+            .enumerate()
+            .filter(|&(_, c)| is_lit(c))
+            .map(move |(x, _)| (x as i64, y as i64))
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fonts_are_complete() {
+        for font in [&SMALL, &LARGE] {
+            assert_eq!(font.glyphs().count(), font.letters.len());
+            let drawn = font.rows.join("\n");
+            assert_eq!(read_grid(&drawn).unwrap(), font.letters);
+        }
+    }
+
+    #[test]
+    fn reading() {
+        // Letters in the small font, spaced as 2022 day 10's CRT draws them
+        let drawing = "\
+###..#..#.###....##.#....###..#..#.#....
+#..#.#.#..#..#....#.#....#..#.#..#.#....
+#..#.##...#..#....#.#....#..#.#..#.#....
+###..#.#..###.....#.#....###..#..#.#....
+#.#..#.#..#....#..#.#....#....#..#.#....
+#..#.#..#.#.....##..####.#.....##..####.";
+        assert_eq!(read_grid(drawing).unwrap(), "RKPJLPUL");
+        // Unlit padding all round is ignored, as are other unlit characters
+        let padded = format!("\n\n{}\n", drawing.replace('.', " ").replace('#', "X"));
+        assert_eq!(read_grid(&padded).unwrap(), "RKPJLPUL");
+        let points = [(0, 0), (0, 1), (0, 2), (0, 3), (0, 4), (0, 5)];
+        assert!(read_points(points.iter().map(|&(x, y)| (x + 7, y - 3))).is_err());
+        assert!(read_grid("##\n##").is_err());
+        assert!(read_grid("").is_err());
+    }
+
+    #[test]
+    fn reading_large() {
+        // 2018 day 10's message, as the points in our input line up
+        let drawing = "\
+#....#..#.......######....##....#....#..#####....####...######
+#....#..#............#...#..#...#...#...#....#..#....#.......#
+.#..#...#............#..#....#..#..#....#....#..#............#
+.#..#...#...........#...#....#..#.#.....#....#..#...........#.
+..##....#..........#....#....#..##......#####...#..........#..
+..##....#.........#.....######..##......#....#..#..###....#...
+.#..#...#........#......#....#..#.#.....#....#..#....#...#....
+.#..#...#.......#.......#....#..#..#....#....#..#....#..#.....
+#....#..#.......#.......#....#..#...#...#....#..#...##..#.....
+#....#..######..######..#....#..#....#..#####....###.#..######";
+        assert_eq!(read_grid(drawing).unwrap(), "XLZAKBGZ");
+        // Without its last row it is neither font's height
+        let (short, _) = drawing.rsplit_once('\n').unwrap();
+        assert!(read_grid(short).is_err());
+    }
+}
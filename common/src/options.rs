@@ -0,0 +1,44 @@
+//! What the runner has asked of the day it is running, beyond its answers
+//!
+//! `aoc` can ask a day to [explain](explaining) how it got its answers, to
+//! [draw](take_dot_output) what it worked on, or to play a [script] before
+//! handing over to the terminal.  Days which can't do what's asked just give
+//! their answers as usual.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static EXPLAIN: AtomicBool = AtomicBool::new(false);
+static DOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+static SCRIPT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Ask days to show how they work out their answers, for `aoc explain`
+pub fn set_explain(explain: bool) {
+    EXPLAIN.store(explain, Ordering::Relaxed);
+}
+
+/// Whether the day being run should explain itself
+pub fn explaining() -> bool {
+    EXPLAIN.load(Ordering::Relaxed)
+}
+
+/// Ask the day being run to draw itself, in Graphviz's DOT, at `path`
+pub fn set_dot_output(path: Option<PathBuf>) {
+    *DOT.lock().unwrap() = path;
+}
+
+/// Where to draw the day being run, if anywhere.  Days which can draw take
+/// the path, so that `aoc dot` can tell whether anything was drawn.
+pub fn take_dot_output() -> Option<PathBuf> {
+    DOT.lock().unwrap().take()
+}
+
+/// Give interactive days a script of commands to run first
+pub fn set_script(path: Option<PathBuf>) {
+    *SCRIPT.lock().unwrap() = path;
+}
+
+pub fn script() -> Option<PathBuf> {
+    SCRIPT.lock().unwrap().clone()
+}
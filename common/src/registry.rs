@@ -0,0 +1,213 @@
+//! The registry of every day's solution
+//!
+//! Each year crate's build script calls [`generate_days`], which scans that
+//! crate's `src/bin` for `YYYYdayN.rs` files and writes a `days.rs` into
+//! `OUT_DIR`.  The year's `lib.rs` includes that file, giving a [`Year`] which
+//! the aggregators and the `aoc` runner can iterate over.  2017's days are
+//! crates of their own, which [`generate_crate_days`] gathers up instead.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::solution::DynSolution;
+use crate::{GenError, Result, StdResult};
+
+/// A single day which can be run
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub main: fn() -> Result<()>,
+    /// Set for days which implement [`Solution`](crate::solution::Solution)
+    pub solution: Option<&'static dyn DynSolution>,
+}
+
+/// All the days we know about for a given year
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+    /// Days which exist but should not be run as part of a whole year,
+    /// along with the reason why
+    pub skipped: &'static [(u8, &'static str)],
+}
+
+impl Year {
+    pub fn day(&self, day: u8) -> Option<&Day> {
+        self.days.iter().find(|d| d.day == day)
+    }
+
+    pub fn skip_reason(&self, day: u8) -> Option<&'static str> {
+        self.skipped
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map(|(_, reason)| *reason)
+    }
+
+    /// The year crate's directory, which day binaries expect to run from
+    pub fn base_dir(&self) -> Result<PathBuf> {
+        Ok(crate::locate::workspace_dir()?.join(self.year.to_string()))
+    }
+
+    /// Run from the year crate's directory, if we can find it.  Days find
+    /// their input wherever they're run from, so carrying on from here when
+    /// we can't is not a problem.
+    pub fn enter(&self) -> Result<()> {
+        if let Ok(dir) = self.base_dir() {
+            std::env::set_current_dir(dir)?;
+        }
+        Ok(())
+    }
+
+    /// Days in `1..=25` which have no solution at all
+    pub fn missing(&self) -> Vec<u8> {
+        (1..=25).filter(|&d| self.day(d).is_none()).collect()
+    }
+}
+
+/// Run every day of a year which isn't skipped, in order
+///
+/// This is what the `YYYYall` aggregators do.
+pub fn run_year(year: &Year) -> Result<()> {
+    year.enter()?;
+    crate::locate::set_year(year.year);
+    for day in year.days {
+        if year.skip_reason(day.day).is_none() {
+            (day.main)()?;
+        }
+    }
+    Ok(())
+}
+
+/// Turn whatever a day's `main()` returned into our `Result`
+///
+/// Older years have their own idea of what `Result` is, so the generated
+/// registry passes everything through here.
+pub fn day_result<E: Into<GenError>>(res: StdResult<(), E>) -> Result<()> {
+    res.map_err(Into::into)
+}
+
+/// A day as the generated registry sees it
+struct DayEntry {
+    day: u8,
+    /// The module (or crate) whose `main` and `SOLUTION` we refer to
+    module: String,
+    returns_result: bool,
+    has_solution: bool,
+}
+
+impl DayEntry {
+    fn new(day: u8, module: String, path: &Path) -> Self {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let body = fs::read_to_string(path).expect("Unable to read day");
+        let returns_result = if body.contains("pub fn main() -> ") {
+            true
+        } else if body.contains("pub fn main() {") {
+            false
+        } else {
+            panic!("{} does not have a `pub fn main()`", path.display());
+        };
+        let has_solution = body.contains("pub static SOLUTION: ") || body.contains("solution! {");
+        DayEntry {
+            day,
+            module,
+            returns_result,
+            has_solution,
+        }
+    }
+}
+
+/// Generate `days.rs` for the year crate whose build script is calling us
+///
+/// Every `src/bin/YYYYdayN.rs` must have a `pub fn main()`, either returning
+/// a `Result` or nothing at all.  Days which also have a
+/// `pub static SOLUTION`, usually from [`solution!`](crate::solution!), are
+/// registered as solutions too.
+pub fn generate_days(year: u16) {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("No CARGO_MANIFEST_DIR");
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let prefix = format!("{}day", year);
+    let mut days = Vec::new();
+    for entry in fs::read_dir(&bin_dir).expect("Unable to read src/bin") {
+        let path = entry.expect("Unable to read src/bin entry").path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let day = name
+            .strip_prefix(&prefix)
+            .and_then(|s| s.strip_suffix(".rs"))
+            .and_then(|s| s.parse::<u8>().ok());
+        if let Some(day) = day {
+            days.push((DayEntry::new(day, format!("day{}", day), &path), path));
+        }
+    }
+    days.sort_by_key(|(entry, _)| entry.day);
+
+    let mut out = String::new();
+    for (entry, path) in &days {
+        writeln!(out, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(out, "pub mod {};", entry.module).unwrap();
+    }
+    writeln!(out).unwrap();
+    let days: Vec<_> = days.into_iter().map(|(entry, _)| entry).collect();
+    write_year(&mut out, year, &days);
+}
+
+/// Generate `days.rs` for a year whose days are crates of their own
+///
+/// This is 2017, where each `dayN` directory next to the calling crate holds
+/// a crate called `aocYYYYdayN`.  Those must be dependencies of the calling
+/// crate, and their `src/lib.rs` must have a `pub fn main()` just like the
+/// day binaries of the other years.
+pub fn generate_crate_days(year: u16) {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("No CARGO_MANIFEST_DIR");
+    println!("cargo:rerun-if-changed={}", manifest_dir);
+    let mut days = Vec::new();
+    for entry in fs::read_dir(&manifest_dir).expect("Unable to read the year's directory") {
+        let dir = entry.expect("Unable to read the year's directory").path();
+        let name = dir.file_name().unwrap().to_string_lossy().into_owned();
+        let day = name.strip_prefix("day").and_then(|s| s.parse::<u8>().ok());
+        let lib = dir.join("src").join("lib.rs");
+        if let Some(day) = day.filter(|_| lib.is_file()) {
+            days.push(DayEntry::new(day, format!("aoc{}day{}", year, day), &lib));
+        }
+    }
+    days.sort_by_key(|entry| entry.day);
+
+    let mut out = String::new();
+    write_year(&mut out, year, &days);
+}
+
+/// Write the `YEAR` table for `days` and put it where `include!` expects it
+fn write_year(out: &mut String, year: u16, days: &[DayEntry]) {
+    writeln!(
+        out,
+        "pub static YEAR: aoc_common::registry::Year = aoc_common::registry::Year {{"
+    )
+    .unwrap();
+    writeln!(out, "    year: {},", year).unwrap();
+    writeln!(out, "    days: &[").unwrap();
+    for entry in days {
+        let call = if entry.returns_result {
+            format!("aoc_common::registry::day_result({}::main())", entry.module)
+        } else {
+            format!("{{ {}::main(); Ok(()) }}", entry.module)
+        };
+        let solution = if entry.has_solution {
+            format!("Some({}::SOLUTION)", entry.module)
+        } else {
+            "None".to_string()
+        };
+        writeln!(
+            out,
+            "        aoc_common::registry::Day {{ year: {}, day: {}, main: || {}, solution: {} }},",
+            year, entry.day, call, solution
+        )
+        .unwrap();
+    }
+    writeln!(out, "    ],").unwrap();
+    writeln!(out, "    skipped: super::SKIPPED_DAYS,").unwrap();
+    writeln!(out, "}};").unwrap();
+
+    let out_dir = std::env::var("OUT_DIR").expect("No OUT_DIR");
+    fs::write(Path::new(&out_dir).join("days.rs"), out).expect("Unable to write days.rs");
+}
@@ -0,0 +1,213 @@
+//! Solutions which can be driven as a library rather than only by `main()`
+//!
+//! A day implements [`Solution`] on a private unit struct and then exposes it
+//! to the registry through a `pub static SOLUTION: &dyn DynSolution`, since the
+//! parsed input type is almost always private to the day.  Its `main()` then
+//! just becomes a call to [`run_solution`].  The [`solution!`] macro writes the
+//! struct, the impl and the static, so a day only needs to say how to parse
+//! its input and what its parts answer with.
+//!
+//! Parts usually answer with a plain number or string, but any part can say
+//! it failed by answering with a `Result` of one instead.
+
+use std::any::Any;
+
+use crate::input::read_input;
+use crate::Result;
+
+/// Something a part can answer with
+pub trait Answer {
+    fn into_answer(self) -> Result<String>;
+}
+
+macro_rules! display_answers {
+    ($($t:ty),*) => {
+        $(
+            impl Answer for $t {
+                fn into_answer(self) -> Result<String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_answers!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String, &str
+);
+
+impl<T: Answer> Answer for Result<T> {
+    fn into_answer(self) -> Result<String> {
+        self?.into_answer()
+    }
+}
+
+pub trait Solution {
+    /// The puzzle input, once parsed
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// A [`Solution`] with its types erased, so that the registry can hold any day
+pub trait DynSolution: Sync {
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    fn part1(&self, input: &dyn Any) -> Result<String>;
+    fn part2(&self, input: &dyn Any) -> Result<String>;
+
+    /// Compute the answer to the given part, which must be 1 or 2
+    fn part(&self, input: &dyn Any, part: u8) -> Result<String> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => panic!("There is no part {}", part),
+        }
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<String> {
+        S::part1(
+            input
+                .downcast_ref()
+                .expect("Input parsed by another solution"),
+        )
+        .into_answer()
+    }
+
+    fn part2(&self, input: &dyn Any) -> Result<String> {
+        S::part2(
+            input
+                .downcast_ref()
+                .expect("Input parsed by another solution"),
+        )
+        .into_answer()
+    }
+}
+
+/// Implement [`Solution`] for a day by handing off to its own functions
+///
+/// ```ignore
+/// solution! {
+///     Day5: Almanac = |input| Ok(parse_almanac(input)),
+///     part1 -> u64,
+///     part2 -> u64 = |input| part2(input, 1_000_000),
+/// }
+/// ```
+///
+/// This declares `struct Day5` and implements [`Solution`] for it, parsing
+/// with the body given and answering with the day's own `part1` and `part2`
+/// functions, unless a part says otherwise.  The solution is exposed to the
+/// registry as `pub static SOLUTION`.
+#[macro_export]
+macro_rules! solution {
+    (
+        $name:ident: $input:ty = |$arg:ident| $parse:expr,
+        part1 -> $part1:ty $(= |$arg1:ident| $solve1:expr)?,
+        part2 -> $part2:ty $(= |$arg2:ident| $solve2:expr)? $(,)?
+    ) => {
+        struct $name;
+
+        impl $crate::solution::Solution for $name {
+            type Input = $input;
+            type Part1 = $part1;
+            type Part2 = $part2;
+
+            fn parse($arg: &str) -> $crate::Result<$input> {
+                $parse
+            }
+
+            $crate::solution_part!(part1, $input, $part1 $(, $arg1, $solve1)?);
+            $crate::solution_part!(part2, $input, $part2 $(, $arg2, $solve2)?);
+        }
+
+        pub static SOLUTION: &dyn $crate::solution::DynSolution = &$name;
+    };
+}
+
+/// One part of a [`solution!`], answered by the day's function of the same
+/// name unless it was given something else
+#[doc(hidden)]
+#[macro_export]
+macro_rules! solution_part {
+    ($part:ident, $input:ty, $answer:ty) => {
+        fn $part(input: &$input) -> $answer {
+            $part(input)
+        }
+    };
+    ($part:ident, $input:ty, $answer:ty, $arg:ident, $solve:expr) => {
+        fn $part($arg: &$input) -> $answer {
+            $solve
+        }
+    };
+}
+
+/// Read the input for `day`, solve it, and print both parts
+pub fn run_solution(solution: &dyn DynSolution, day: usize) -> Result<()> {
+    let input = solution.parse(&read_input(day)?)?;
+    println!("Part 1: {}", solution.part1(&*input)?);
+    println!("Part 2: {}", solution.part2(&*input)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Unfinished;
+
+    impl Solution for Unfinished {
+        type Input = usize;
+        type Part1 = usize;
+        type Part2 = Result<usize>;
+
+        fn parse(input: &str) -> Result<usize> {
+            Ok(input.len())
+        }
+
+        fn part1(input: &usize) -> usize {
+            *input
+        }
+
+        fn part2(_: &usize) -> Result<usize> {
+            Err("part 2 not implemented".into())
+        }
+    }
+
+    fn part1(input: &usize) -> usize {
+        *input
+    }
+
+    crate::solution! {
+        Doubled: usize = |input| Ok(input.len()),
+        part1 -> usize,
+        part2 -> usize = |input| input * 2,
+    }
+
+    #[test]
+    fn generated_solutions() {
+        let input = SOLUTION.parse("abc").unwrap();
+        assert_eq!(SOLUTION.part(&*input, 1).unwrap(), "3");
+        assert_eq!(SOLUTION.part(&*input, 2).unwrap(), "6");
+    }
+
+    #[test]
+    fn failing_parts() {
+        let input = Unfinished.parse("abc").unwrap();
+        assert_eq!(Unfinished.part(&*input, 1).unwrap(), "3");
+        let err = Unfinished.part(&*input, 2).unwrap_err();
+        assert_eq!(err.to_string(), "part 2 not implemented");
+    }
+}
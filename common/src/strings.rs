@@ -0,0 +1,60 @@
+//! Whitespace and comma separated string lists which can be parsed by regex
+
+use std::convert::Infallible;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::input::input_by_split_pat;
+use crate::StdResult;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpacedString(Vec<String>);
+
+impl FromStr for SpacedString {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> StdResult<Self, Self::Err> {
+        let v = input_by_split_pat(value, " ").unwrap();
+        Ok(Self(v))
+    }
+}
+
+impl Deref for SpacedString {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SpacedString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommaSpacedString(Vec<String>);
+
+impl FromStr for CommaSpacedString {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> StdResult<Self, Self::Err> {
+        let v = input_by_split_pat(value, ", ").unwrap();
+        Ok(Self(v))
+    }
+}
+
+impl Deref for CommaSpacedString {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for CommaSpacedString {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}